@@ -23,6 +23,12 @@ namespace Dreamcaller.Schema
         [JsonProperty("connectResponse", Required = Required.Always)]
         public ConnectResponse ConnectResponse { get; set; }
 
+        [JsonProperty("errorResponse", Required = Required.Always)]
+        public ErrorResponse ErrorResponse { get; set; }
+
+        [JsonProperty("listBattlesResponse", Required = Required.Always)]
+        public ListBattlesResponse ListBattlesResponse { get; set; }
+
         [JsonProperty("performActionRequest", Required = Required.Always)]
         public PerformActionRequest PerformActionRequest { get; set; }
 
@@ -34,12 +40,54 @@ namespace Dreamcaller.Schema
 
         [JsonProperty("pollResponse", Required = Required.Always)]
         public PollResponse PollResponse { get; set; }
+
+        [JsonProperty("replayRequest", Required = Required.Always)]
+        public ReplayRequest ReplayRequest { get; set; }
+
+        [JsonProperty("replayResponse", Required = Required.Always)]
+        public ReplayResponse ReplayResponse { get; set; }
+
+        [JsonProperty("spectateRequest", Required = Required.Always)]
+        public SpectateRequest SpectateRequest { get; set; }
+
+        [JsonProperty("spectateResponse", Required = Required.Always)]
+        public SpectateResponse SpectateResponse { get; set; }
+
+        [JsonProperty("streamRequest", Required = Required.Always)]
+        public StreamRequest StreamRequest { get; set; }
+
+        [JsonProperty("streamResponse", Required = Required.Always)]
+        public StreamResponse StreamResponse { get; set; }
     }
 
     public partial class ConnectRequest
     {
+        /// <summary>
+        /// If true, battle updates after the initial snapshot are sent as `PatchBattle` commands
+        /// containing only the changes to the battle view.
+        /// </summary>
+        [JsonProperty("battlePatches", NullValueHandling = NullValueHandling.Ignore)]
+        public bool? BattlePatches { get; set; }
+
         [JsonProperty("metadata", Required = Required.Always)]
         public Metadata Metadata { get; set; }
+
+        /// <summary>
+        /// User to play against instead of the AI.
+        ///
+        /// If the opponent has started a battle against this user, this user joins it. Otherwise a
+        /// new battle is started, which the opponent can join by connecting with this user as their
+        /// opponent.
+        /// </summary>
+        [JsonProperty("opponent")]
+        public Guid? Opponent { get; set; }
+
+        /// <summary>
+        /// Encoding to use for the response to this request and for all subsequent messages.
+        /// Defaults to JSON.
+        /// </summary>
+        [JsonProperty("wireFormat", NullValueHandling = NullValueHandling.Ignore)]
+        public WireFormat? WireFormat { get; set; }
     }
 
     public partial class Metadata
@@ -49,6 +97,17 @@ namespace Dreamcaller.Schema
 
         [JsonProperty("userId", Required = Required.Always)]
         public Guid UserId { get; set; }
+
+        /// <summary>
+        /// Version of the battle state last observed by the client.
+        ///
+        /// Responses contain the current version of the battle, including poll responses and
+        /// streamed updates. Actions which specify a different version are not performed, and the
+        /// client is instead sent the current state of the battle. Actions which only change what
+        /// the user is looking at, such as browsing cards, do not change the version.
+        /// </summary>
+        [JsonProperty("version")]
+        public long? Version { get; set; }
     }
 
     public partial class ConnectResponse
@@ -58,6 +117,12 @@ namespace Dreamcaller.Schema
 
         [JsonProperty("metadata", Required = Required.Always)]
         public Metadata Metadata { get; set; }
+
+        /// <summary>
+        /// Encoding which will be used for all subsequent messages.
+        /// </summary>
+        [JsonProperty("wireFormat", Required = Required.Always)]
+        public WireFormat WireFormat { get; set; }
     }
 
     /// <summary>
@@ -86,6 +151,9 @@ namespace Dreamcaller.Schema
         [JsonProperty("updateBattle", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
         public UpdateBattleCommand UpdateBattle { get; set; }
 
+        [JsonProperty("patchBattle", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
+        public PatchBattleCommand PatchBattle { get; set; }
+
         [JsonProperty("wait", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
         public Milliseconds Wait { get; set; }
 
@@ -396,6 +464,8 @@ namespace Dreamcaller.Schema
     ///
     /// Object is in a player's status zone
     ///
+    /// Object is in a player's dreamwell, waiting to be revealed
+    ///
     /// Object is hidden within a card
     /// </summary>
     public partial class PositionClass
@@ -424,6 +494,9 @@ namespace Dreamcaller.Schema
         [JsonProperty("inPlayerStatus", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
         public PlayerName? InPlayerStatus { get; set; }
 
+        [JsonProperty("inDreamwell", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
+        public PlayerName? InDreamwell { get; set; }
+
         [JsonProperty("hiddenWithinCard", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
         public CardId HiddenWithinCard { get; set; }
     }
@@ -1180,6 +1253,70 @@ namespace Dreamcaller.Schema
         public Milliseconds WaitDuration { get; set; }
     }
 
+    /// <summary>
+    /// Updates the visual state of the battle by applying changes to the battle view from a
+    /// previous [UpdateBattleCommand] or [PatchBattleCommand].
+    /// </summary>
+    public partial class PatchBattleCommand
+    {
+        /// <summary>
+        /// Revision of the battle view this patch applies to.
+        ///
+        /// A client whose battle view has a different revision is out of sync and should send a
+        /// poll request with `resync` set to receive a full snapshot.
+        /// </summary>
+        [JsonProperty("baseViewRevision", Required = Required.Always)]
+        public long BaseViewRevision { get; set; }
+
+        /// <summary>
+        /// Cards which were added or changed. Replaces any existing card with the same ID.
+        /// </summary>
+        [JsonProperty("cards", Required = Required.Always)]
+        public List<CardView> Cards { get; set; }
+
+        /// <summary>
+        /// New state of the enemy, if changed.
+        /// </summary>
+        [JsonProperty("enemy")]
+        public PlayerView Enemy { get; set; }
+
+        /// <summary>
+        /// New interface state, if changed.
+        /// </summary>
+        [JsonProperty("interface")]
+        public InterfaceView Interface { get; set; }
+
+        /// <summary>
+        /// Cards which were removed from the battle view.
+        /// </summary>
+        [JsonProperty("removedCards", Required = Required.Always)]
+        public List<CardId> RemovedCards { get; set; }
+
+        /// <summary>
+        /// New status description, if changed.
+        /// </summary>
+        [JsonProperty("statusDescription")]
+        public string StatusDescription { get; set; }
+
+        /// <summary>
+        /// Sound to play when the battle is updated.
+        /// </summary>
+        [JsonProperty("updateSound")]
+        public AudioClipAddress UpdateSound { get; set; }
+
+        /// <summary>
+        /// New state of the user, if changed.
+        /// </summary>
+        [JsonProperty("user")]
+        public PlayerView User { get; set; }
+
+        /// <summary>
+        /// Revision of the battle view after applying this patch.
+        /// </summary>
+        [JsonProperty("viewRevision", Required = Required.Always)]
+        public long ViewRevision { get; set; }
+    }
+
     public partial class UpdateBattleCommand
     {
         /// <summary>
@@ -1193,6 +1330,16 @@ namespace Dreamcaller.Schema
         /// </summary>
         [JsonProperty("updateSound")]
         public AudioClipAddress UpdateSound { get; set; }
+
+        /// <summary>
+        /// Revision of the client's battle view after this update, used to detect whether a
+        /// subsequent [PatchBattleCommand] applies to it.
+        ///
+        /// View revisions count the battle updates sent to one client and are unrelated to the
+        /// battle version in the request metadata.
+        /// </summary>
+        [JsonProperty("viewRevision", Required = Required.Always)]
+        public long ViewRevision { get; set; }
     }
 
     /// <summary>
@@ -1240,10 +1387,14 @@ namespace Dreamcaller.Schema
     }
 
     /// <summary>
-    /// Opponent of user
+    /// New state of the enemy, if changed.
     ///
     /// Represents the visual state of a player in a game
     ///
+    /// New state of the user, if changed.
+    ///
+    /// Opponent of user
+    ///
     /// Player who is operating the client
     /// </summary>
     public partial class PlayerView
@@ -1260,6 +1411,12 @@ namespace Dreamcaller.Schema
         [JsonProperty("energy", Required = Required.Always)]
         public long Energy { get; set; }
 
+        /// <summary>
+        /// Energy this player gains at the start of each of their turns
+        /// </summary>
+        [JsonProperty("producedEnergy", Required = Required.Always)]
+        public long ProducedEnergy { get; set; }
+
         /// <summary>
         /// Current score total
         /// </summary>
@@ -1274,9 +1431,11 @@ namespace Dreamcaller.Schema
     }
 
     /// <summary>
-    /// UI to display to the player.
+    /// New interface state, if changed.
     ///
     /// User interaction options
+    ///
+    /// UI to display to the player.
     /// </summary>
     public partial class InterfaceView
     {
@@ -1293,6 +1452,45 @@ namespace Dreamcaller.Schema
         public FlexNode ScreenOverlay { get; set; }
     }
 
+    /// <summary>
+    /// Describes why a request could not be handled.
+    /// </summary>
+    public partial class ErrorResponse
+    {
+        [JsonProperty("errorType", Required = Required.Always)]
+        public ErrorType ErrorType { get; set; }
+
+        [JsonProperty("message", Required = Required.Always)]
+        public string Message { get; set; }
+    }
+
+    public partial class ListBattlesResponse
+    {
+        [JsonProperty("battles", Required = Required.Always)]
+        public List<BattleSummary> Battles { get; set; }
+    }
+
+    /// <summary>
+    /// Summary of a battle which can be watched by a spectator.
+    /// </summary>
+    public partial class BattleSummary
+    {
+        [JsonProperty("activePlayer", Required = Required.Always)]
+        public Seat ActivePlayer { get; set; }
+
+        [JsonProperty("battleId", Required = Required.Always)]
+        public Guid BattleId { get; set; }
+
+        [JsonProperty("turnNumber", Required = Required.Always)]
+        public long TurnNumber { get; set; }
+
+        [JsonProperty("userId", Required = Required.Always)]
+        public Guid UserId { get; set; }
+
+        [JsonProperty("version", Required = Required.Always)]
+        public long Version { get; set; }
+    }
+
     public partial class PerformActionRequest
     {
         [JsonProperty("action", Required = Required.Always)]
@@ -1327,6 +1525,16 @@ namespace Dreamcaller.Schema
     {
         [JsonProperty("metadata", Required = Required.Always)]
         public Metadata Metadata { get; set; }
+
+        /// <summary>
+        /// If true, the response contains a full snapshot of the battle in place of any queued
+        /// updates, which are discarded.
+        ///
+        /// Clients should set this when their battle view is out of sync, e.g. because they
+        /// received a `PatchBattle` command whose base view revision does not match their view.
+        /// </summary>
+        [JsonProperty("resync", NullValueHandling = NullValueHandling.Ignore)]
+        public bool? Resync { get; set; }
     }
 
     public partial class PollResponse
@@ -1338,6 +1546,133 @@ namespace Dreamcaller.Schema
         public Metadata Metadata { get; set; }
     }
 
+    /// <summary>
+    /// Request to replay one turn of a battle.
+    /// </summary>
+    public partial class ReplayRequest
+    {
+        [JsonProperty("battleId", Required = Required.Always)]
+        public Guid BattleId { get; set; }
+
+        /// <summary>
+        /// Seat whose view of the battle is displayed.
+        /// </summary>
+        [JsonProperty("perspective", Required = Required.Always)]
+        public Seat Perspective { get; set; }
+
+        /// <summary>
+        /// Turn to replay.
+        /// </summary>
+        [JsonProperty("turnNumber", Required = Required.Always)]
+        public long TurnNumber { get; set; }
+
+        /// <summary>
+        /// User playing the battle
+        /// </summary>
+        [JsonProperty("userId", Required = Required.Always)]
+        public Guid UserId { get; set; }
+    }
+
+    public partial class ReplayResponse
+    {
+        /// <summary>
+        /// Commands to display the state of the battle at the start of the turn, followed by each
+        /// action taken during the turn.
+        /// </summary>
+        [JsonProperty("commands", Required = Required.Always)]
+        public CommandSequence Commands { get; set; }
+
+        /// <summary>
+        /// Most recent turn of the battle, which can be replayed up to the current state of the
+        /// battle.
+        /// </summary>
+        [JsonProperty("lastTurnNumber", Required = Required.Always)]
+        public long LastTurnNumber { get; set; }
+
+        /// <summary>
+        /// Turn which was replayed.
+        /// </summary>
+        [JsonProperty("turnNumber", Required = Required.Always)]
+        public long TurnNumber { get; set; }
+    }
+
+    /// <summary>
+    /// Request to watch a battle without participating in it.
+    /// </summary>
+    public partial class SpectateRequest
+    {
+        [JsonProperty("battleId", Required = Required.Always)]
+        public Guid BattleId { get; set; }
+
+        /// <summary>
+        /// Seat whose view of the battle is displayed. Information hidden from the player in this
+        /// seat is hidden from the spectator.
+        /// </summary>
+        [JsonProperty("perspective", Required = Required.Always)]
+        public Seat Perspective { get; set; }
+
+        /// <summary>
+        /// User playing the battle
+        /// </summary>
+        [JsonProperty("userId", Required = Required.Always)]
+        public Guid UserId { get; set; }
+    }
+
+    public partial class SpectateResponse
+    {
+        /// <summary>
+        /// Commands to display the current state of the battle.
+        /// </summary>
+        [JsonProperty("commands", Required = Required.Always)]
+        public CommandSequence Commands { get; set; }
+
+        /// <summary>
+        /// Current version of the battle
+        /// </summary>
+        [JsonProperty("version", Required = Required.Always)]
+        public long Version { get; set; }
+    }
+
+    /// <summary>
+    /// A request sent by the client over a streaming connection.
+    /// </summary>
+    public partial class StreamRequest
+    {
+        [JsonProperty("connect", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
+        public ConnectRequest Connect { get; set; }
+
+        [JsonProperty("performAction", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
+        public PerformActionRequest PerformAction { get; set; }
+
+        /// <summary>
+        /// Requests a full snapshot of the connected battle, which is sent as an `Update` message.
+        /// See [PollRequest::resync].
+        /// </summary>
+        [JsonProperty("resync", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
+        public Metadata Resync { get; set; }
+    }
+
+    /// <summary>
+    /// A message sent to the client over a streaming connection.
+    ///
+    /// Each request produces exactly one response message. Updates are sent whenever commands
+    /// are produced outside of a user request, such as enemy actions.
+    /// </summary>
+    public partial class StreamResponse
+    {
+        [JsonProperty("connect", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
+        public ConnectResponse Connect { get; set; }
+
+        [JsonProperty("performAction", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
+        public PerformActionResponse PerformAction { get; set; }
+
+        [JsonProperty("update", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
+        public PollResponse Update { get; set; }
+
+        [JsonProperty("error", Required = Required.DisallowNull, NullValueHandling = NullValueHandling.Ignore)]
+        public ErrorResponse Error { get; set; }
+    }
+
     /// <summary>
     /// Identifies a player in an ongoing battle.
     ///
@@ -1349,6 +1684,36 @@ namespace Dreamcaller.Schema
     /// </summary>
     public enum PlayerName { Enemy, User };
 
+    /// <summary>
+    /// Identifies a seat in an ongoing battle, independent of which user is viewing it.
+    ///
+    /// Battle state always refers to players by seat. A seat is converted to a [PlayerName]
+    /// relative to the user viewing the battle when it is rendered.
+    ///
+    /// Seat whose view of the battle is displayed.
+    ///
+    /// Seat whose view of the battle is displayed. Information hidden from the player in this
+    /// seat is hidden from the spectator.
+    ///
+    /// Seat of the user who created the battle
+    ///
+    /// Seat of the host's opponent, either the AI enemy or the user who joined the battle
+    /// </summary>
+    public enum Seat { Guest, Host };
+
+    /// <summary>
+    /// Encoding which will be used for all subsequent messages.
+    ///
+    /// Encoding used for request and response messages exchanged with the client.
+    ///
+    /// The format is chosen by the client in its `ConnectRequest`. Connect requests themselves
+    /// are always sent as JSON.
+    ///
+    /// Encoding to use for the response to this request and for all subsequent messages.
+    /// Defaults to JSON.
+    /// </summary>
+    public enum WireFormat { Json, MessagePack };
+
     public enum GameMessageType { Defeat, EnemyTurn, Victory, YourTurn };
 
     /// <summary>
@@ -1368,6 +1733,8 @@ namespace Dreamcaller.Schema
     ///
     /// Position for cards to be shown to the user immediately after they're drawn.
     ///
+    /// Dreamwell card which was revealed to produce energy this turn
+    ///
     /// Object is being displayed in a card browser, e.g. to select from a list of cards while
     /// searching
     ///
@@ -1376,7 +1743,7 @@ namespace Dreamcaller.Schema
     /// Object is in a temporary holding space for cards in hand while resolving some other 'play
     /// card' ability.
     /// </summary>
-    public enum PositionEnum { Browser, CardSelectionChoices, Default, Drawn, HandStorage, Offscreen, OnStack };
+    public enum PositionEnum { Browser, CardSelectionChoices, Default, Drawn, DreamwellActivation, HandStorage, Offscreen, OnStack };
 
     /// <summary>
     /// Represents the general category of card being displayed.
@@ -1385,8 +1752,10 @@ namespace Dreamcaller.Schema
 
     /// <summary>
     /// Close the card browser
+    ///
+    /// End the user's turn, passing the turn to the enemy
     /// </summary>
-    public enum BattleActionEnum { CloseCardBrowser };
+    public enum BattleActionEnum { CloseCardBrowser, EndTurn };
 
     public enum CardBrowserType { EnemyDeck, EnemyStatus, EnemyVoid, UserDeck, UserStatus, UserVoid };
 
@@ -1400,6 +1769,8 @@ namespace Dreamcaller.Schema
     /// </summary>
     public enum CardFrame { Character, Default, Event };
 
+    public enum ErrorType { IllegalAction, Internal, InvalidRequest, MissingBattleId, UnknownBattle };
+
     public enum RevealedCardStatus { CanPlay, CanSelectNegative, CanSelectPositive, Selected };
 
     public enum FlexAlign { Auto, Center, FlexEnd, FlexStart, Stretch };
@@ -1485,6 +1856,8 @@ namespace Dreamcaller.Schema
             Converters =
             {
                 PlayerNameConverter.Singleton,
+                SeatConverter.Singleton,
+                WireFormatConverter.Singleton,
                 GameMessageTypeConverter.Singleton,
                 CardFacingConverter.Singleton,
                 PositionConverter.Singleton,
@@ -1495,6 +1868,7 @@ namespace Dreamcaller.Schema
                 BattleActionEnumConverter.Singleton,
                 DebugActionConverter.Singleton,
                 CardFrameConverter.Singleton,
+                ErrorTypeConverter.Singleton,
                 RevealedCardStatusConverter.Singleton,
                 FlexAlignConverter.Singleton,
                 DimensionUnitConverter.Singleton,
@@ -1561,6 +1935,88 @@ namespace Dreamcaller.Schema
         public static readonly PlayerNameConverter Singleton = new PlayerNameConverter();
     }
 
+    internal class SeatConverter : JsonConverter
+    {
+        public override bool CanConvert(Type t) => t == typeof(Seat) || t == typeof(Seat?);
+
+        public override object ReadJson(JsonReader reader, Type t, object existingValue, JsonSerializer serializer)
+        {
+            if (reader.TokenType == JsonToken.Null) return null;
+            var value = serializer.Deserialize<string>(reader);
+            switch (value)
+            {
+                case "guest":
+                    return Seat.Guest;
+                case "host":
+                    return Seat.Host;
+            }
+            throw new Exception("Cannot unmarshal type Seat");
+        }
+
+        public override void WriteJson(JsonWriter writer, object untypedValue, JsonSerializer serializer)
+        {
+            if (untypedValue == null)
+            {
+                serializer.Serialize(writer, null);
+                return;
+            }
+            var value = (Seat)untypedValue;
+            switch (value)
+            {
+                case Seat.Guest:
+                    serializer.Serialize(writer, "guest");
+                    return;
+                case Seat.Host:
+                    serializer.Serialize(writer, "host");
+                    return;
+            }
+            throw new Exception("Cannot marshal type Seat");
+        }
+
+        public static readonly SeatConverter Singleton = new SeatConverter();
+    }
+
+    internal class WireFormatConverter : JsonConverter
+    {
+        public override bool CanConvert(Type t) => t == typeof(WireFormat) || t == typeof(WireFormat?);
+
+        public override object ReadJson(JsonReader reader, Type t, object existingValue, JsonSerializer serializer)
+        {
+            if (reader.TokenType == JsonToken.Null) return null;
+            var value = serializer.Deserialize<string>(reader);
+            switch (value)
+            {
+                case "json":
+                    return WireFormat.Json;
+                case "messagePack":
+                    return WireFormat.MessagePack;
+            }
+            throw new Exception("Cannot unmarshal type WireFormat");
+        }
+
+        public override void WriteJson(JsonWriter writer, object untypedValue, JsonSerializer serializer)
+        {
+            if (untypedValue == null)
+            {
+                serializer.Serialize(writer, null);
+                return;
+            }
+            var value = (WireFormat)untypedValue;
+            switch (value)
+            {
+                case WireFormat.Json:
+                    serializer.Serialize(writer, "json");
+                    return;
+                case WireFormat.MessagePack:
+                    serializer.Serialize(writer, "messagePack");
+                    return;
+            }
+            throw new Exception("Cannot marshal type WireFormat");
+        }
+
+        public static readonly WireFormatConverter Singleton = new WireFormatConverter();
+    }
+
     internal class GameMessageTypeConverter : JsonConverter
     {
         public override bool CanConvert(Type t) => t == typeof(GameMessageType) || t == typeof(GameMessageType?);
@@ -1674,6 +2130,8 @@ namespace Dreamcaller.Schema
                             return new Position { Enum = PositionEnum.Default };
                         case "drawn":
                             return new Position { Enum = PositionEnum.Drawn };
+                        case "dreamwellActivation":
+                            return new Position { Enum = PositionEnum.DreamwellActivation };
                         case "handStorage":
                             return new Position { Enum = PositionEnum.HandStorage };
                         case "offscreen":
@@ -1708,6 +2166,9 @@ namespace Dreamcaller.Schema
                     case PositionEnum.Drawn:
                         serializer.Serialize(writer, "drawn");
                         return;
+                    case PositionEnum.DreamwellActivation:
+                        serializer.Serialize(writer, "dreamwellActivation");
+                        return;
                     case PositionEnum.HandStorage:
                         serializer.Serialize(writer, "handStorage");
                         return;
@@ -1748,6 +2209,8 @@ namespace Dreamcaller.Schema
                     return PositionEnum.Default;
                 case "drawn":
                     return PositionEnum.Drawn;
+                case "dreamwellActivation":
+                    return PositionEnum.DreamwellActivation;
                 case "handStorage":
                     return PositionEnum.HandStorage;
                 case "offscreen":
@@ -1780,6 +2243,9 @@ namespace Dreamcaller.Schema
                 case PositionEnum.Drawn:
                     serializer.Serialize(writer, "drawn");
                     return;
+                case PositionEnum.DreamwellActivation:
+                    serializer.Serialize(writer, "dreamwellActivation");
+                    return;
                 case PositionEnum.HandStorage:
                     serializer.Serialize(writer, "handStorage");
                     return;
@@ -1863,9 +2329,12 @@ namespace Dreamcaller.Schema
                 case JsonToken.String:
                 case JsonToken.Date:
                     var stringValue = serializer.Deserialize<string>(reader);
-                    if (stringValue == "closeCardBrowser")
+                    switch (stringValue)
                     {
-                        return new BattleAction { Enum = BattleActionEnum.CloseCardBrowser };
+                        case "closeCardBrowser":
+                            return new BattleAction { Enum = BattleActionEnum.CloseCardBrowser };
+                        case "endTurn":
+                            return new BattleAction { Enum = BattleActionEnum.EndTurn };
                     }
                     break;
                 case JsonToken.StartObject:
@@ -1880,10 +2349,14 @@ namespace Dreamcaller.Schema
             var value = (BattleAction)untypedValue;
             if (value.Enum != null)
             {
-                if (value.Enum == BattleActionEnum.CloseCardBrowser)
+                switch (value.Enum)
                 {
-                    serializer.Serialize(writer, "closeCardBrowser");
-                    return;
+                    case BattleActionEnum.CloseCardBrowser:
+                        serializer.Serialize(writer, "closeCardBrowser");
+                        return;
+                    case BattleActionEnum.EndTurn:
+                        serializer.Serialize(writer, "endTurn");
+                        return;
                 }
             }
             if (value.BattleActionClass != null)
@@ -1966,9 +2439,12 @@ namespace Dreamcaller.Schema
         {
             if (reader.TokenType == JsonToken.Null) return null;
             var value = serializer.Deserialize<string>(reader);
-            if (value == "closeCardBrowser")
+            switch (value)
             {
-                return BattleActionEnum.CloseCardBrowser;
+                case "closeCardBrowser":
+                    return BattleActionEnum.CloseCardBrowser;
+                case "endTurn":
+                    return BattleActionEnum.EndTurn;
             }
             throw new Exception("Cannot unmarshal type BattleActionEnum");
         }
@@ -1981,10 +2457,14 @@ namespace Dreamcaller.Schema
                 return;
             }
             var value = (BattleActionEnum)untypedValue;
-            if (value == BattleActionEnum.CloseCardBrowser)
+            switch (value)
             {
-                serializer.Serialize(writer, "closeCardBrowser");
-                return;
+                case BattleActionEnum.CloseCardBrowser:
+                    serializer.Serialize(writer, "closeCardBrowser");
+                    return;
+                case BattleActionEnum.EndTurn:
+                    serializer.Serialize(writer, "endTurn");
+                    return;
             }
             throw new Exception("Cannot marshal type BattleActionEnum");
         }
@@ -2084,6 +2564,62 @@ namespace Dreamcaller.Schema
         public static readonly CardFrameConverter Singleton = new CardFrameConverter();
     }
 
+    internal class ErrorTypeConverter : JsonConverter
+    {
+        public override bool CanConvert(Type t) => t == typeof(ErrorType) || t == typeof(ErrorType?);
+
+        public override object ReadJson(JsonReader reader, Type t, object existingValue, JsonSerializer serializer)
+        {
+            if (reader.TokenType == JsonToken.Null) return null;
+            var value = serializer.Deserialize<string>(reader);
+            switch (value)
+            {
+                case "illegalAction":
+                    return ErrorType.IllegalAction;
+                case "internal":
+                    return ErrorType.Internal;
+                case "invalidRequest":
+                    return ErrorType.InvalidRequest;
+                case "missingBattleId":
+                    return ErrorType.MissingBattleId;
+                case "unknownBattle":
+                    return ErrorType.UnknownBattle;
+            }
+            throw new Exception("Cannot unmarshal type ErrorType");
+        }
+
+        public override void WriteJson(JsonWriter writer, object untypedValue, JsonSerializer serializer)
+        {
+            if (untypedValue == null)
+            {
+                serializer.Serialize(writer, null);
+                return;
+            }
+            var value = (ErrorType)untypedValue;
+            switch (value)
+            {
+                case ErrorType.IllegalAction:
+                    serializer.Serialize(writer, "illegalAction");
+                    return;
+                case ErrorType.Internal:
+                    serializer.Serialize(writer, "internal");
+                    return;
+                case ErrorType.InvalidRequest:
+                    serializer.Serialize(writer, "invalidRequest");
+                    return;
+                case ErrorType.MissingBattleId:
+                    serializer.Serialize(writer, "missingBattleId");
+                    return;
+                case ErrorType.UnknownBattle:
+                    serializer.Serialize(writer, "unknownBattle");
+                    return;
+            }
+            throw new Exception("Cannot marshal type ErrorType");
+        }
+
+        public static readonly ErrorTypeConverter Singleton = new ErrorTypeConverter();
+    }
+
     internal class RevealedCardStatusConverter : JsonConverter
     {
         public override bool CanConvert(Type t) => t == typeof(RevealedCardStatus) || t == typeof(RevealedCardStatus?);
@@ -14,15 +14,22 @@ namespace Dreamcaller.Services
 {
   public class ActionService : Service
   {
+    const string UserIdKey = "UserId";
+    const float PollIntervalSeconds = 0.25f;
+
+    Metadata _metadata = null!;
+
     IEnumerator Start()
     {
       yield return new WaitForEndOfFrame();
+      // The server resumes the user's saved battle when no battle ID is given.
+      _metadata = new Metadata
+      {
+        UserId = LoadOrCreateUserId()
+      };
       var request = new ConnectRequest
       {
-        Metadata = new Metadata
-        {
-          UserId = Guid.NewGuid()
-        }
+        Metadata = _metadata
       };
       if (Application.isEditor)
       {
@@ -30,9 +37,31 @@ namespace Dreamcaller.Services
       }
       else
       {
+        Plugin.Initialize(Application.persistentDataPath);
         var response = Plugin.Connect(request);
+        UpdateMetadata(response.Metadata);
         StartCoroutine(ApplyCommands(response.Commands, animate: false));
       }
+
+      StartCoroutine(PollForUpdates());
+    }
+
+    static Guid LoadOrCreateUserId()
+    {
+      if (Guid.TryParse(PlayerPrefs.GetString(UserIdKey), out var userId))
+      {
+        return userId;
+      }
+
+      userId = Guid.NewGuid();
+      PlayerPrefs.SetString(UserIdKey, userId.ToString());
+      PlayerPrefs.Save();
+      return userId;
+    }
+
+    void UpdateMetadata(Metadata metadata)
+    {
+      _metadata = metadata;
     }
 
     public void PerformAction(UserAction? action)
@@ -44,10 +73,7 @@ namespace Dreamcaller.Services
 
       var request = new PerformActionRequest
       {
-        Metadata = new Metadata
-        {
-          UserId = Guid.NewGuid()
-        },
+        Metadata = _metadata,
         Action = action
       };
       if (Application.isEditor)
@@ -57,17 +83,57 @@ namespace Dreamcaller.Services
       else
       {
         var response = Plugin.PerformAction(request);
+        UpdateMetadata(response.Metadata);
         StartCoroutine(ApplyCommands(response.Commands, animate: true));
       }
     }
 
+    IEnumerator PollForUpdates()
+    {
+      while (true)
+      {
+        yield return new WaitForSeconds(PollIntervalSeconds);
+        if (_metadata.BattleId == null)
+        {
+          continue;
+        }
+
+        var request = new PollRequest
+        {
+          Metadata = _metadata
+        };
+        if (Application.isEditor)
+        {
+          yield return SendRequest<PollRequest, PollResponse>(
+            request,
+            "poll",
+            UnityWebRequest.kHttpVerbPOST,
+            response =>
+            {
+              UpdateMetadata(response.Metadata);
+              return ApplyCommands(response.Commands, animate: true);
+            });
+        }
+        else
+        {
+          var response = Plugin.Poll(request);
+          UpdateMetadata(response.Metadata);
+          yield return ApplyCommands(response.Commands, animate: true);
+        }
+      }
+    }
+
     private IEnumerator DevServerConnectAsync(ConnectRequest request)
     {
       yield return SendRequest<ConnectRequest, ConnectResponse>(
         request,
         "connect",
         UnityWebRequest.kHttpVerbGET,
-        response => ApplyCommands(response.Commands, animate: false));
+        response =>
+        {
+          UpdateMetadata(response.Metadata);
+          return ApplyCommands(response.Commands, animate: false);
+        });
     }
 
     private IEnumerator PerformDevServerActionAsync(PerformActionRequest request)
@@ -76,7 +142,11 @@ namespace Dreamcaller.Services
         request,
         "perform_action",
         UnityWebRequest.kHttpVerbPOST,
-        response => ApplyCommands(response.Commands, animate: true));
+        response =>
+        {
+          UpdateMetadata(response.Metadata);
+          return ApplyCommands(response.Commands, animate: true);
+        });
     }
 
     private IEnumerator SendRequest<TRequest, TResponse>(
@@ -106,7 +176,7 @@ namespace Dreamcaller.Services
       }
       else
       {
-        Debug.LogError($"{endpoint} request failed: {webRequest.error}");
+        Debug.LogError($"{endpoint} request failed: {webRequest.error} {webRequest.downloadHandler.text}");
       }
     }
 
@@ -260,6 +260,11 @@ namespace Dreamcaller.Services
         return Registry.Layout.Browser;
       }
 
+      if (position.Enum == PositionEnum.DreamwellActivation)
+      {
+        return Registry.Layout.DrawnCardsPosition;
+      }
+
       if (position.PositionClass == null)
       {
         throw new InvalidOperationException($"Unknown layout position: ${position.Enum}");
@@ -315,6 +320,11 @@ namespace Dreamcaller.Services
         };
       }
 
+      if (position.PositionClass.InDreamwell is { })
+      {
+        return Registry.Layout.Offscreen;
+      }
+
       if (position.PositionClass.SelectingTargets is { } selectingTargets)
       {
         return selectingTargets switch
@@ -9,32 +9,91 @@ using Newtonsoft.Json;
 
 static class Plugin
 {
-    const int BufferSize = 1_000_000;
+    /// <summary>
+    /// Identifies the JSON wire format in plugin function arguments.
+    /// </summary>
+    const int JsonWireFormat = 0;
 
-    public static ConnectResponse Connect(ConnectRequest request)
+    delegate int OwnedResponseFunction(byte[] request, int requestLength, out IntPtr response);
+
+    public static void Initialize(string saveDirectory)
+    {
+        var encoded = Encoding.UTF8.GetBytes(saveDirectory);
+        CheckResult(dreamcaller_initialize(encoded, encoded.Length));
+    }
+
+    public static ConnectResponse Connect(ConnectRequest request) =>
+        Call<ConnectRequest, ConnectResponse>(request, dreamcaller_connect_owned, "connect");
+
+    public static PerformActionResponse PerformAction(PerformActionRequest request) =>
+        Call<PerformActionRequest, PerformActionResponse>(
+          request,
+          (byte[] bytes, int length, out IntPtr response) =>
+            dreamcaller_perform_action_owned(bytes, length, JsonWireFormat, out response),
+          "action");
+
+    public static PollResponse Poll(PollRequest request) =>
+        Call<PollRequest, PollResponse>(
+          request,
+          (byte[] bytes, int length, out IntPtr response) =>
+            dreamcaller_poll_owned(bytes, length, JsonWireFormat, out response),
+          "poll");
+
+    /// <summary>
+    /// Invokes a plugin function which returns its response in a buffer owned
+    /// by the plugin, releasing the buffer once the response has been read.
+    /// </summary>
+    static TResponse Call<TRequest, TResponse>(TRequest request, OwnedResponseFunction function, string name)
+        where TResponse : class
     {
         var serialized = JsonConvert.SerializeObject(request, Converter.Settings);
         var encoded = Encoding.UTF8.GetBytes(serialized);
 
-        byte[] response = new byte[BufferSize];
-        int responseLength = Errors.CheckNonNegative(
-            dreamcaller_connect(encoded, encoded.Length, response, BufferSize));
-        var json = Encoding.UTF8.GetString(response, 0, responseLength);
-        var deserialized = JsonConvert.DeserializeObject<ConnectResponse>(json, Converter.Settings);
-        return Errors.CheckNotNull(deserialized, "Error deserializing connect response");
+        int responseLength = CheckResult(function(encoded, encoded.Length, out var response));
+        try
+        {
+            var bytes = new byte[responseLength];
+            Marshal.Copy(response, bytes, 0, responseLength);
+            var json = Encoding.UTF8.GetString(bytes);
+            var deserialized = JsonConvert.DeserializeObject<TResponse>(json, Converter.Settings);
+            return Errors.CheckNotNull(deserialized, $"Error deserializing {name} response");
+        }
+        finally
+        {
+            dreamcaller_free(response, responseLength);
+        }
     }
 
-    public static PerformActionResponse PerformAction(PerformActionRequest request)
+    /// <summary>
+    /// Checks the result of a plugin call, throwing an exception describing
+    /// the plugin's last error if it failed.
+    /// </summary>
+    static int CheckResult(int result)
     {
-        var serialized = JsonConvert.SerializeObject(request, Converter.Settings);
-        var encoded = Encoding.UTF8.GetBytes(serialized);
+        if (result >= 0)
+        {
+            return result;
+        }
+
+        int responseLength = dreamcaller_last_error_owned(JsonWireFormat, out var response);
+        if (responseLength <= 0)
+        {
+            throw new InvalidOperationException("Plugin error: Unknown error");
+        }
 
-        byte[] response = new byte[BufferSize];
-        int responseLength = Errors.CheckNonNegative(
-            dreamcaller_perform_action(encoded, encoded.Length, response, BufferSize));
-        var json = Encoding.UTF8.GetString(response, 0, responseLength);
-        var deserialized = JsonConvert.DeserializeObject<PerformActionResponse>(json, Converter.Settings);
-        return Errors.CheckNotNull(deserialized, "Error deserializing action response");
+        string error;
+        try
+        {
+            var bytes = new byte[responseLength];
+            Marshal.Copy(response, bytes, 0, responseLength);
+            error = Encoding.UTF8.GetString(bytes);
+        }
+        finally
+        {
+            dreamcaller_free(response, responseLength);
+        }
+
+        throw new InvalidOperationException($"Plugin error: {error}");
     }
 
 #if !UNITY_EDITOR && (UNITY_IOS || UNITY_WEBGL)
@@ -42,20 +101,51 @@ static class Plugin
 #else
     [DllImport("plugin")]
 #endif
-    public static extern int dreamcaller_connect(
+    public static extern int dreamcaller_initialize(byte[] path, int pathLength);
+
+#if !UNITY_EDITOR && (UNITY_IOS || UNITY_WEBGL)
+    [DllImport("__Internal")]
+#else
+    [DllImport("plugin")]
+#endif
+    public static extern int dreamcaller_connect_owned(
       byte[] request,
       int requestLength,
-      [Out] byte[] response,
-      int responseLength);
+      out IntPtr response);
 
 #if !UNITY_EDITOR && (UNITY_IOS || UNITY_WEBGL)
     [DllImport("__Internal")]
 #else
     [DllImport("plugin")]
 #endif
-    public static extern int dreamcaller_perform_action(
+    public static extern int dreamcaller_perform_action_owned(
       byte[] request,
       int requestLength,
-      [Out] byte[] response,
-      int responseLength);
+      int wireFormat,
+      out IntPtr response);
+
+#if !UNITY_EDITOR && (UNITY_IOS || UNITY_WEBGL)
+    [DllImport("__Internal")]
+#else
+    [DllImport("plugin")]
+#endif
+    public static extern int dreamcaller_poll_owned(
+      byte[] request,
+      int requestLength,
+      int wireFormat,
+      out IntPtr response);
+
+#if !UNITY_EDITOR && (UNITY_IOS || UNITY_WEBGL)
+    [DllImport("__Internal")]
+#else
+    [DllImport("plugin")]
+#endif
+    public static extern void dreamcaller_free(IntPtr buffer, int length);
+
+#if !UNITY_EDITOR && (UNITY_IOS || UNITY_WEBGL)
+    [DllImport("__Internal")]
+#else
+    [DllImport("plugin")]
+#endif
+    public static extern int dreamcaller_last_error_owned(int wireFormat, out IntPtr response);
 }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use strum::EnumDiscriminants;
 
 use crate::activated_ability::ActivatedAbility;
 use crate::effect::Effect;
@@ -8,7 +9,12 @@ use crate::triggered_ability::TriggeredAbility;
 /// An 'ability' represents a paragraph of text present on a card or a specific
 /// keyword which maps to text defined by the game rules. Abilities on cards are
 /// evaluated from top to bottom in order to apply their game effects.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, EnumDiscriminants)]
+#[strum_discriminants(
+    name(AbilityKind),
+    derive(PartialOrd, Ord, Hash, Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
 #[serde(rename_all = "camelCase")]
 pub enum Ability {
     /// An event ability happens immediately when an event card is played, and
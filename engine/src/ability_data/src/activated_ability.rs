@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::cost::Cost;
 use crate::effect::Effect;
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ActivatedAbilityOptions {
     /// True if this ability can be activated in response to enemy game actions.
@@ -20,7 +20,7 @@ pub struct ActivatedAbilityOptions {
 /// An activated ability is present on a character card and allows the
 /// controlling player to pay some cost in order to achieve an effect. This is
 /// written as "> cost: effect".
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ActivatedAbility {
     /// Costs to activate this ability, paid before it is put on the stack.
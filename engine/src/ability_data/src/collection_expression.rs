@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 /// Expression for describing a variable quantity of targets. For example, this
 /// is used in parsing "Banish up to two other characters you control, then
 /// materialize them."
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum CollectionExpression {
     All,
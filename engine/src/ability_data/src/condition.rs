@@ -5,7 +5,7 @@ use crate::predicate::Predicate;
 /// A boolean predicate over the state of the game. Usually represented in rules
 /// text by the word "if", for example "if you control 2 other warriors, draw a
 /// card".
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Condition {
     CardsDiscardedThisTurn { count: u32 },
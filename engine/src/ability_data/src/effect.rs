@@ -1,3 +1,6 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
 use serde::{Deserialize, Serialize};
 
 use crate::condition::Condition;
@@ -5,16 +8,43 @@ use crate::cost::Cost;
 use crate::standard_effect::StandardEffect;
 
 /// Represents a mutation to the game state.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Effect {
     Effect(StandardEffect),
     WithOptions(EffectWithOptions),
     List(Vec<EffectWithOptions>),
+    Modal(ModalEffect),
+}
+
+impl Effect {
+    /// Returns the standard effects within this effect in the order they are
+    /// written, including those within the modes of a modal effect and within
+    /// triggered abilities created by the effect.
+    pub fn standard_effects(&self) -> Vec<&StandardEffect> {
+        let effects = match self {
+            Effect::Effect(effect) => vec![effect],
+            Effect::WithOptions(options) => vec![&options.effect],
+            Effect::List(list) => list.iter().map(|options| &options.effect).collect(),
+            Effect::Modal(modal) => {
+                return modal.modes.iter().flat_map(|mode| mode.effect.standard_effects()).collect()
+            }
+        };
+        effects
+            .into_iter()
+            .flat_map(|effect| {
+                let mut result = vec![effect];
+                if let StandardEffect::CreateTriggerUntilEndOfTurn { trigger } = effect {
+                    result.extend(trigger.effect.standard_effects());
+                }
+                result
+            })
+            .collect()
+    }
 }
 
 /// Provides an effect along with configuration options.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EffectWithOptions {
     /// Effect to apply
@@ -29,11 +59,16 @@ pub struct EffectWithOptions {
     /// Indicates an effect set which occurs only if some condition is met,
     /// usually phrased as "If {condition}, {effect}"
     pub condition: Option<Condition>,
+
+    /// True if this effect continues the sentence of the previous effect in
+    /// an [Effect::List], phrased as "{previous effect}, then {effect}".
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub then: bool,
 }
 
 impl EffectWithOptions {
     pub fn new(effect: StandardEffect) -> Self {
-        Self { effect, optional: None, condition: None }
+        Self { effect, optional: None, condition: None, then: false }
     }
 
     pub fn with_condition(&self, condition: Condition) -> Self {
@@ -54,3 +89,88 @@ impl EffectWithOptions {
         }
     }
 }
+
+/// An effect where the controller chooses which of several modes to apply,
+/// usually phrased as "Choose one: $br • $1: Draw a card. $br • $3: Dissolve
+/// an enemy character."
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModalEffect {
+    /// Number of different modes the controller chooses.
+    pub choose: u32,
+
+    pub modes: Vec<ModalEffectMode>,
+}
+
+/// One of the modes of a [ModalEffect].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModalEffectMode {
+    /// Additional cost to pay in order to choose this mode, written before
+    /// the mode's effect, e.g. "$3" in "• $3: Dissolve an enemy character."
+    pub cost: Option<Cost>,
+
+    pub effect: Effect,
+}
+
+/// Reasons a [ModalEffect] is not a valid effect.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ModalEffectError {
+    /// The number of modes to choose is zero.
+    ChooseNone,
+
+    /// The effect has no more modes than the number to choose, so no choice is
+    /// possible.
+    NotEnoughModes { choose: u32, modes: usize },
+
+    /// Two modes have the same cost and effect.
+    DuplicateMode(usize),
+
+    /// A mode is itself a modal effect.
+    NestedModal(usize),
+}
+
+impl Display for ModalEffectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ChooseNone => write!(f, "Must choose at least one mode"),
+            Self::NotEnoughModes { choose, modes } => {
+                write!(
+                    f,
+                    "Choosing {choose} requires more than {choose} modes, but there are {modes}"
+                )
+            }
+            Self::DuplicateMode(index) => {
+                write!(f, "Mode {} duplicates an earlier mode", index + 1)
+            }
+            Self::NestedModal(index) => write!(f, "Mode {} is itself a modal effect", index + 1),
+        }
+    }
+}
+
+impl Error for ModalEffectError {}
+
+impl ModalEffect {
+    /// Checks that this effect offers a meaningful choice between distinct,
+    /// non-modal modes.
+    pub fn validate(&self) -> Result<(), ModalEffectError> {
+        if self.choose == 0 {
+            return Err(ModalEffectError::ChooseNone);
+        }
+        if self.modes.len() <= self.choose as usize {
+            return Err(ModalEffectError::NotEnoughModes {
+                choose: self.choose,
+                modes: self.modes.len(),
+            });
+        }
+        for (index, mode) in self.modes.iter().enumerate() {
+            if matches!(mode.effect, Effect::Modal(_)) {
+                return Err(ModalEffectError::NestedModal(index));
+            }
+            if self.modes[..index].contains(mode) {
+                return Err(ModalEffectError::DuplicateMode(index));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+use crate::ability::Ability;
+use crate::effect::Effect;
+use crate::standard_effect::StandardEffect;
+use crate::static_ability::{StandardStaticAbility, StaticAbility, StaticAbilityWithOptions};
+use crate::trigger_event::{TriggerEvent, TriggerKeyword};
+use crate::triggered_ability::TriggeredAbility;
+
+/// A keyword used in card rules text whose meaning is defined by the game
+/// rules, such as `{kw: reclaim}` or `$materialized`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+#[serde(rename_all = "camelCase")]
+pub enum Keyword {
+    Aegis,
+    Discover,
+    Dissolved,
+    Fast,
+    Foresee,
+    Judgment,
+    Kindle,
+    Materialized,
+    Reclaim,
+    Silent,
+}
+
+impl Keyword {
+    /// Name of this keyword as displayed to the user, e.g. "Reclaim".
+    pub fn name(self) -> &'static str {
+        match self {
+            Keyword::Aegis => "Aegis",
+            Keyword::Discover => "Discover",
+            Keyword::Dissolved => "Dissolved",
+            Keyword::Fast => "Fast",
+            Keyword::Foresee => "Foresee",
+            Keyword::Judgment => "Judgment",
+            Keyword::Kindle => "Kindle",
+            Keyword::Materialized => "Materialized",
+            Keyword::Reclaim => "Reclaim",
+            Keyword::Silent => "Silent",
+        }
+    }
+
+    /// Syntax for this keyword in the ability text understood by the parser,
+    /// e.g. "{kw: reclaim}".
+    pub fn ability_text(self) -> &'static str {
+        match self {
+            Keyword::Aegis => "{kw: aegis}",
+            Keyword::Discover => "{kw: discover}",
+            Keyword::Dissolved => "$dissolved",
+            Keyword::Fast => "$fast",
+            Keyword::Foresee => "{kw: foresee}",
+            Keyword::Judgment => "$judgment",
+            Keyword::Kindle => "{kw: kindle}",
+            Keyword::Materialized => "$materialized",
+            Keyword::Reclaim => "{kw: reclaim}",
+            Keyword::Silent => "{kw: silent}",
+        }
+    }
+
+    /// Canonical reminder text explaining the meaning of this keyword.
+    pub fn reminder_text(self) -> &'static str {
+        match self {
+            Keyword::Aegis => "This character cannot be affected by enemy cards.",
+            Keyword::Discover => "Pick one of 4 cards with different types to put into your hand.",
+            Keyword::Dissolved => "Triggers when this character is dissolved.",
+            Keyword::Fast => "Can be played during the enemy's turn.",
+            Keyword::Foresee => {
+                "Look at that many cards from the top of your deck. You may put them into your \
+                 void or back in any order."
+            }
+            Keyword::Judgment => "Triggers at the start of your turn.",
+            Keyword::Kindle => "Your leftmost character gains that much spark.",
+            Keyword::Materialized => "Triggers when this character enters play.",
+            Keyword::Reclaim => "You may play this card from your void, then banish it.",
+            Keyword::Silent => "Its \"$materialized\" abilities do not trigger.",
+        }
+    }
+}
+
+impl From<TriggerKeyword> for Keyword {
+    fn from(keyword: TriggerKeyword) -> Self {
+        match keyword {
+            TriggerKeyword::Materialized => Keyword::Materialized,
+            TriggerKeyword::Judgment => Keyword::Judgment,
+            TriggerKeyword::Dissolved => Keyword::Dissolved,
+        }
+    }
+}
+
+/// Returns the keywords used by `abilities`, in the order in which they first
+/// appear and without duplicates.
+///
+/// This includes keywords granted to other cards, such as "Characters in your
+/// hand have '$fast'", as well as keywords within effects and triggered
+/// abilities created by an effect.
+pub fn keywords(abilities: &[Ability]) -> Vec<Keyword> {
+    let mut result = vec![];
+    for ability in abilities {
+        for keyword in ability_keywords(ability) {
+            if !result.contains(&keyword) {
+                result.push(keyword);
+            }
+        }
+    }
+    result
+}
+
+fn ability_keywords(ability: &Ability) -> Vec<Keyword> {
+    match ability {
+        Ability::Event(effect) => effect_keywords(effect),
+        Ability::Static(StaticAbility::StaticAbility(ability))
+        | Ability::Static(StaticAbility::WithOptions(StaticAbilityWithOptions {
+            ability, ..
+        })) => static_ability_keywords(ability),
+        Ability::Activated(ability) => {
+            let is_fast = ability.options.as_ref().is_some_and(|options| options.is_fast);
+            is_fast
+                .then_some(Keyword::Fast)
+                .into_iter()
+                .chain(effect_keywords(&ability.effect))
+                .collect()
+        }
+        Ability::Triggered(ability) => triggered_ability_keywords(ability),
+    }
+}
+
+fn triggered_ability_keywords(ability: &TriggeredAbility) -> Vec<Keyword> {
+    trigger_keywords(ability).into_iter().chain(effect_keywords(&ability.effect)).collect()
+}
+
+fn trigger_keywords(ability: &TriggeredAbility) -> Vec<Keyword> {
+    match &ability.trigger {
+        TriggerEvent::Keywords(keywords) => keywords.iter().cloned().map(Keyword::from).collect(),
+        _ => vec![],
+    }
+}
+
+fn static_ability_keywords(ability: &StandardStaticAbility) -> Vec<Keyword> {
+    match ability {
+        StandardStaticAbility::CardsInYourVoidHaveReclaim { .. }
+        | StandardStaticAbility::Reclaim { .. } => vec![Keyword::Reclaim],
+        StandardStaticAbility::CharactersInHandHaveFast => vec![Keyword::Fast],
+        StandardStaticAbility::JudgmentTriggersWhenMaterialized { .. } => {
+            vec![Keyword::Judgment, Keyword::Materialized]
+        }
+        StandardStaticAbility::PlayForAlternateCost(alternate) => {
+            alternate.if_you_do.iter().flat_map(effect_keywords).collect()
+        }
+        StandardStaticAbility::PlayFromVoid(play) => {
+            play.if_you_do.iter().flat_map(effect_keywords).collect()
+        }
+        // The Materialized reminder text describes a card's own abilities, so
+        // it does not explain disabling the abilities of enemy cards.
+        StandardStaticAbility::DisableEnemyMaterializedAbilities
+        | StandardStaticAbility::CostReductionForEach { .. }
+        | StandardStaticAbility::EnemyCardsCostIncrease { .. }
+        | StandardStaticAbility::HasAllCharacterTypes
+        | StandardStaticAbility::OncePerTurnPlayFromVoid { .. }
+        | StandardStaticAbility::PlayOnlyFromVoid
+        | StandardStaticAbility::SparkBonusYourCharacters { .. }
+        | StandardStaticAbility::SparkBonusOtherCharacters { .. }
+        | StandardStaticAbility::SparkEqualToPredicateCount { .. }
+        | StandardStaticAbility::YouMayLookAtTopCardOfYourDeck
+        | StandardStaticAbility::YouMayPlayFromTopOfDeck { .. }
+        | StandardStaticAbility::YourCardsCostIncrease { .. }
+        | StandardStaticAbility::YourCardsCostReduction { .. } => vec![],
+    }
+}
+
+fn effect_keywords(effect: &Effect) -> Vec<Keyword> {
+    effect.standard_effects().into_iter().flat_map(standard_effect_keywords).collect()
+}
+
+fn standard_effect_keywords(effect: &StandardEffect) -> Vec<Keyword> {
+    match effect {
+        StandardEffect::CardsInVoidGainReclaimThisTurn { .. }
+        | StandardEffect::GainsReclaimUntilEndOfTurn { .. } => vec![Keyword::Reclaim],
+        // Keywords in the created ability's effect are found by
+        // [Effect::standard_effects].
+        StandardEffect::CreateTriggerUntilEndOfTurn { trigger } => trigger_keywords(trigger),
+        StandardEffect::Discover { .. } | StandardEffect::DiscoverAndThenMaterialize { .. } => {
+            vec![Keyword::Discover]
+        }
+        StandardEffect::Foresee { .. } => vec![Keyword::Foresee],
+        StandardEffect::GainsAegisThisTurn { .. } => vec![Keyword::Aegis],
+        StandardEffect::Kindle { .. } => vec![Keyword::Kindle],
+        StandardEffect::MaterializeSilentCopy { .. } => vec![Keyword::Silent],
+        StandardEffect::TriggerJudgmentAbility { .. } => vec![Keyword::Judgment],
+        StandardEffect::AbandonAndGainEnergyForSpark { .. }
+        | StandardEffect::AbandonAtEndOfTurn { .. }
+        | StandardEffect::BanishCardsFromEnemyVoid { .. }
+        | StandardEffect::BanishEnemyVoid
+        | StandardEffect::BanishCharacter { .. }
+        | StandardEffect::BanishCharacterUntilLeavesPlay { .. }
+        | StandardEffect::BanishUntilNextMain { .. }
+        | StandardEffect::BanishCollection { .. }
+        | StandardEffect::Copy { .. }
+        | StandardEffect::CopyNextPlayed { .. }
+        | StandardEffect::DisableActivatedAbilitiesWhileInPlay { .. }
+        | StandardEffect::DiscardCardFromEnemyHand { .. }
+        | StandardEffect::DiscardCardFromEnemyHandThenTheyDraw { .. }
+        | StandardEffect::DiscardCards { .. }
+        | StandardEffect::DissolveCharacter { .. }
+        | StandardEffect::DissolveCharactersCount { .. }
+        | StandardEffect::DissolveCharactersQuantity { .. }
+        | StandardEffect::DoubleYourEnergy
+        | StandardEffect::DrawCards { .. }
+        | StandardEffect::DrawMatchingCard { .. }
+        | StandardEffect::DrawCardsForEach { .. }
+        | StandardEffect::EachMatchingGainsSpark { .. }
+        | StandardEffect::EachMatchingGainsSparkUntilNextMain { .. }
+        | StandardEffect::EachPlayerAbandonsCharacters { .. }
+        | StandardEffect::EachPlayerDiscardCards { .. }
+        | StandardEffect::EnemyGainsPoints { .. }
+        | StandardEffect::EnemyGainsPointsEqualToItsSpark
+        | StandardEffect::EnemyLosesPoints { .. }
+        | StandardEffect::GainControl { .. }
+        | StandardEffect::GainEnergy { .. }
+        | StandardEffect::GainEnergyForEach { .. }
+        | StandardEffect::GainPoints { .. }
+        | StandardEffect::GainPointsForEach { .. }
+        | StandardEffect::GainsSpark { .. }
+        | StandardEffect::GainsSparkForQuantity { .. }
+        | StandardEffect::GainsSparkUntilYourNextMainForEach { .. }
+        | StandardEffect::GainTwiceThatMuchEnergyInstead
+        | StandardEffect::LosePoints { .. }
+        | StandardEffect::MaterializeCharacter { .. }
+        | StandardEffect::MaterializeCharacterAtEndOfTurn { .. }
+        | StandardEffect::MaterializeCharacterFromVoid { .. }
+        | StandardEffect::MaterializeRandomFromDeck { .. }
+        | StandardEffect::Negate { .. }
+        | StandardEffect::PayCost { .. }
+        | StandardEffect::PutCardsFromYourDeckIntoVoid { .. }
+        | StandardEffect::PutCardsFromVoidOnTopOfDeck { .. }
+        | StandardEffect::PutOnTopOfEnemyDeck { .. }
+        | StandardEffect::ReturnCharactersToHandDrawCardForEach { .. }
+        | StandardEffect::ReturnFromYourVoidToHand { .. }
+        | StandardEffect::ReturnFromYourVoidToPlay { .. }
+        | StandardEffect::ReturnToHand { .. }
+        | StandardEffect::ShuffleHandAndDeckAndDraw { .. }
+        | StandardEffect::SparkBecomes { .. }
+        | StandardEffect::SpendAllEnergy
+        | StandardEffect::SpendAllEnergyDissolveEnemy
+        | StandardEffect::TakeExtraTurn
+        | StandardEffect::ThenMaterializeIt
+        | StandardEffect::YouWinTheGame => vec![],
+    }
+}
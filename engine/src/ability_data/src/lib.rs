@@ -4,7 +4,9 @@ pub mod collection_expression;
 pub mod condition;
 pub mod cost;
 pub mod effect;
+pub mod keyword;
 pub mod predicate;
+pub mod quantity;
 pub mod quantity_expression;
 pub mod standard_effect;
 pub mod static_ability;
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// This is used for both targeting constraints as well as describing the
 /// implicit target of an effect.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Predicate {
     /// Predicate which only matches the owning card.
@@ -48,7 +48,42 @@ pub enum Predicate {
     EnemyVoid(CardPredicate),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Predicate {
+    /// Returns a card predicate describing the same set of cards, with the
+    /// controller and zone of this predicate written as
+    /// [CardPredicate::ControlledBy] and [CardPredicate::InZone] filters.
+    ///
+    /// Returns None for predicates which depend on the card being evaluated,
+    /// such as [Predicate::This] or [Predicate::Another].
+    pub fn card_predicate(&self) -> Option<CardPredicate> {
+        let (controller, zone, predicate) = match self {
+            Predicate::Your(p) => (Some(Controller::You), CardZone::Battlefield, p),
+            Predicate::Enemy(p) => (Some(Controller::Enemy), CardZone::Battlefield, p),
+            Predicate::Any(p) => (None, CardZone::Battlefield, p),
+            Predicate::YourVoid(p) => (Some(Controller::You), CardZone::Void, p),
+            Predicate::EnemyVoid(p) => (Some(Controller::Enemy), CardZone::Void, p),
+            _ => return None,
+        };
+        let mut predicates = vec![];
+        predicates.extend(controller.map(CardPredicate::ControlledBy));
+        predicates.push(CardPredicate::InZone(zone));
+        match predicate {
+            CardPredicate::And(p) => predicates.extend(p.iter().cloned()),
+            p => predicates.push(p.clone()),
+        }
+        Some(CardPredicate::And(predicates))
+    }
+}
+
+/// Describes which cards match.
+///
+/// Controller and zone are usually specified by the [Predicate] wrapping a
+/// card predicate, e.g. [Predicate::Enemy] or [Predicate::YourVoid], which
+/// [Predicate::card_predicate] converts into [CardPredicate::ControlledBy] and
+/// [CardPredicate::InZone] filters. Card types and stats can be combined via
+/// [CardPredicate::And], [CardPredicate::Or] and [CardPredicate::Not] to
+/// describe new sets of cards without adding a dedicated variant for each one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum CardPredicate {
     Card,
@@ -56,13 +91,6 @@ pub enum CardPredicate {
     Event,
     Dream,
     CharacterType(CharacterType),
-    NotCharacterType(CharacterType),
-    CharacterWithSpark(Spark, Operator<Spark>),
-    CardWithCost {
-        target: Box<CardPredicate>,
-        cost_operator: Operator<Energy>,
-        cost: Energy,
-    },
     CharacterWithCostComparedToControlled {
         target: Box<CardPredicate>,
         cost_operator: Operator<Energy>,
@@ -85,9 +113,51 @@ pub enum CardPredicate {
         target: Box<CardPredicate>,
     },
     CharacterWithMultiActivatedAbility,
+
+    /// Cards matching every predicate, e.g. "non-{cardtype: warrior}
+    /// character" or "event with cost $2 and spark 1 or more".
+    And(Vec<CardPredicate>),
+
+    /// Cards matching any of the predicates, e.g. "character or event".
+    Or(Vec<CardPredicate>),
+
+    /// Cards which do not match a predicate. Written as the "non-" prefix of
+    /// a card type, e.g. "non-{cardtype: warrior}".
+    Not(Box<CardPredicate>),
+
+    /// Cards with a cost matching an operator, e.g. "with cost $2 or less".
+    WithCost(Energy, Operator<Energy>),
+
+    /// Cards with spark matching an operator, e.g. "with spark 3 or more".
+    WithSpark(Spark, Operator<Spark>),
+
+    /// Cards controlled by a player, relative to the controller of the
+    /// ability, e.g. "you control".
+    ControlledBy(Controller),
+
+    /// Cards in a zone, e.g. "in your void".
+    InZone(CardZone),
+}
+
+/// Player controlling a card, relative to the controller of an ability.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Controller {
+    You,
+    Enemy,
+}
+
+/// Zone a card predicate can be restricted to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CardZone {
+    Battlefield,
+    Hand,
+    Void,
+    Deck,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Operator<T> {
     LowerBy(T),
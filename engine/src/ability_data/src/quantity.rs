@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::quantity_expression::QuantityExpression;
+
+/// An amount used by an effect, such as the number of cards to draw or the
+/// energy to gain.
+///
+/// Amounts which are multiplied by a "for each" count, such as "$1" in "gain
+/// $1 for each character you control", are always fixed and do not use this
+/// type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Quantity<T> {
+    /// A fixed amount, for example "2" in "draw 2 cards".
+    Fixed(T),
+
+    /// An amount written as "X" and defined by a trailing "where X is ..."
+    /// clause, for example "draw X cards, where X is the number of cards in
+    /// your void".
+    X(QuantityExpression),
+}
+
+impl<T> Quantity<T> {
+    /// Returns the definition of X if this amount is written as "X".
+    pub fn x(&self) -> Option<&QuantityExpression> {
+        match self {
+            Quantity::Fixed(_) => None,
+            Quantity::X(x) => Some(x),
+        }
+    }
+}
@@ -4,7 +4,7 @@ use crate::predicate::{CardPredicate, Predicate};
 
 /// Represents some quantity, such as the number of cards you have drawn in a
 /// turn.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum QuantityExpression {
     AbandonedThisTurn(CardPredicate),
@@ -12,6 +12,7 @@ pub enum QuantityExpression {
     CardsDrawnThisTurn(CardPredicate),
     DiscardedThisTurn(CardPredicate),
     DissolvedThisTurn(CardPredicate),
+    EnergySpentThisWay,
     Matching(Predicate),
     PlayedThisTurn(CardPredicate),
 }
@@ -10,13 +10,13 @@ use crate::quantity_expression::QuantityExpression;
 /// A static ability represents something which modifies the rules of the game,
 /// either for this specific card or globally. Static abilities do not 'happen',
 /// they're just something that is always true.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StaticAbility {
     StaticAbility(StandardStaticAbility),
     WithOptions(StaticAbilityWithOptions),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StaticAbilityWithOptions {
     pub ability: StandardStaticAbility,
 
@@ -25,7 +25,7 @@ pub struct StaticAbilityWithOptions {
 }
 
 /// Basic static abilities
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum StandardStaticAbility {
     CardsInYourVoidHaveReclaim { matching: CardPredicate },
@@ -49,7 +49,7 @@ pub enum StandardStaticAbility {
     YourCardsCostReduction { matching: CardPredicate, reduction: Energy },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayFromVoid {
     pub energy_cost: Option<Energy>,
@@ -57,7 +57,7 @@ pub struct PlayFromVoid {
     pub if_you_do: Option<Effect>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AlternateCost {
     pub energy_cost: Energy,
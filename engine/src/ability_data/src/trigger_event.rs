@@ -4,7 +4,7 @@ use crate::predicate::Predicate;
 
 /// Describes possible game events which may cause a triggered ability to
 /// trigger.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TriggerEvent {
     Abandon(Predicate),
@@ -21,7 +21,7 @@ pub enum TriggerEvent {
     PlayFromHand(Predicate),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TriggerKeyword {
     Materialized,
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::effect::Effect;
 use crate::trigger_event::TriggerEvent;
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TriggeredAbilityOptions {
     /// True if this ability can only trigger once per turn.
@@ -16,7 +16,7 @@ pub struct TriggeredAbilityOptions {
 /// A triggered ability is an effect which happens when some triggering
 /// event occurs, typically while its card is in play. Indicated in card
 /// text by "When", "Whenever", "At", or by a trigger keyword.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TriggeredAbility {
     pub trigger: TriggerEvent,
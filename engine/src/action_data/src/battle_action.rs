@@ -15,6 +15,8 @@ pub enum BattleAction {
     BrowseCards(CardBrowserType),
     /// Close the card browser
     CloseCardBrowser,
+    /// End the user's turn, passing the turn to the enemy
+    EndTurn,
 }
 
 impl From<BattleAction> for UserAction {
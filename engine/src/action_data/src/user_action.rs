@@ -11,3 +11,15 @@ pub enum UserAction {
     DebugAction(DebugAction),
     BattleAction(BattleAction),
 }
+
+impl UserAction {
+    /// Returns true if this action only changes what the acting user is
+    /// looking at, such as opening the card browser, without changing the
+    /// state of the battle itself.
+    pub fn is_display_only(&self) -> bool {
+        matches!(
+            self,
+            UserAction::BattleAction(BattleAction::BrowseCards(_) | BattleAction::CloseCardBrowser)
+        )
+    }
+}
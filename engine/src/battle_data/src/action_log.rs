@@ -0,0 +1,29 @@
+use action_data::user_action::UserAction;
+use core_data::types::Seat;
+use serde::{Deserialize, Serialize};
+
+/// An action which changed the state of a battle, recorded so that the battle
+/// can later be replayed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    /// Turn on which the action was taken
+    pub turn_number: u32,
+
+    /// The action which was taken
+    pub action: LoggedAction,
+}
+
+/// Possible actions which change the state of a battle.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LoggedAction {
+    /// The user in the given seat performed an action.
+    Player(Seat, UserAction),
+
+    /// The enemy AI took one step of its turn.
+    ///
+    /// The AI is deterministic, so the step can be replayed by running the AI
+    /// again on the same battle state.
+    EnemyAi,
+}
@@ -0,0 +1,75 @@
+use std::collections::BTreeSet;
+
+use card_data::card_definition::CardDefinition;
+use card_data::card_definitions;
+use card_data::card_name::CardName;
+use core_data::identifiers::CardId;
+use core_data::types::Seat;
+use serde::{Deserialize, Serialize};
+
+/// State of a card within an ongoing battle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BattleCard {
+    /// Identifier for this card within its battle
+    pub id: CardId,
+
+    /// Name of the card definition for this card
+    pub name: CardName,
+
+    /// Player who owns this card
+    pub owner: Seat,
+
+    /// Current location of this card
+    pub zone: Zone,
+
+    /// Location of this card before it moved to its current zone, or None if
+    /// it has not moved since it was created.
+    #[serde(default)]
+    pub previous_zone: Option<Zone>,
+
+    /// Determines the order of cards within a zone. Cards with higher sorting
+    /// keys were added to their zone more recently. For the deck, the card
+    /// with the highest sorting key is the top card.
+    pub sorting_key: u32,
+
+    /// Players who can see this card even though it is in a zone which is
+    /// hidden from them, e.g. because of an effect which reveals the enemy's
+    /// hand. Cleared when the card changes zones.
+    #[serde(default)]
+    pub revealed_to: BTreeSet<Seat>,
+}
+
+impl BattleCard {
+    pub fn definition(&self) -> &'static CardDefinition {
+        card_definitions::get(self.name)
+    }
+}
+
+impl Zone {
+    /// Returns true if cards in this zone are not visible to every player.
+    pub fn is_hidden(self) -> bool {
+        matches!(self, Zone::Deck | Zone::Hand | Zone::Dreamwell)
+    }
+}
+
+/// Locations in which cards can exist during a battle
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Zone {
+    Deck,
+    Hand,
+    Stack,
+    Battlefield,
+    Void,
+    Banished,
+
+    /// Cards which are not part of gameplay but modify the rules for their
+    /// owner, such as dreamsigns.
+    Status,
+
+    /// Dreamwell cards which produce energy for their owner. The card with
+    /// the lowest sorting key is revealed next, after which it returns to the
+    /// end of the dreamwell.
+    Dreamwell,
+}
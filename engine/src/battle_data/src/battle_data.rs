@@ -0,0 +1,210 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use card_data::card_name::CardName;
+use core_data::identifiers::{BattleId, CardId, UserId};
+use core_data::types::Seat;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256StarStar;
+use serde::{Deserialize, Serialize};
+use slotmap::SlotMap;
+
+use crate::action_log::LogEntry;
+use crate::battle_card::{BattleCard, Zone};
+use crate::player_data::PlayerData;
+use crate::turn_data::{PendingTrigger, TurnData};
+
+/// Complete state of an ongoing dream battle.
+///
+/// This is the canonical representation of a battle, which is rendered into
+/// a `BattleView` in order to be displayed to the user.
+///
+/// Players are identified by their [Seat] in the battle, which is converted
+/// to a `PlayerName` relative to each user when the battle is rendered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BattleData {
+    /// Unique identifier for this dream battle
+    pub id: BattleId,
+
+    /// Incremented each time the battle changes as the result of an action,
+    /// used to detect actions from a client which has not yet observed the
+    /// latest state.
+    pub version: u64,
+
+    /// Player in the [Seat::Host] seat
+    pub user: PlayerData,
+
+    /// Player in the [Seat::Guest] seat
+    pub enemy: PlayerData,
+
+    /// User in the [Seat::Guest] seat in a battle between two users, or
+    /// None if the enemy is controlled by the AI.
+    #[serde(default)]
+    pub guest: Option<UserId>,
+
+    /// All cards in this battle
+    pub cards: SlotMap<CardId, BattleCard>,
+
+    /// Sorting key to assign to the next card which changes zones
+    pub next_sorting_key: u32,
+
+    /// Choice the user must make before the battle can continue, if any.
+    pub prompt: Option<BattlePrompt>,
+
+    /// State of the current turn
+    pub turn: TurnData,
+
+    /// Triggered abilities which have triggered but not yet resolved, in the
+    /// order in which they will resolve.
+    pub pending_triggers: VecDeque<PendingTrigger>,
+
+    /// Seed which [Self::rng] was created from.
+    ///
+    /// This determines the order of each deck and so must be kept secret. It
+    /// is 0 for battles saved before the seed was recorded.
+    #[serde(default)]
+    pub seed: u64,
+
+    /// Random number generator for this battle
+    pub rng: Xoshiro256StarStar,
+
+    /// Secret used to derive the opaque identifiers which are displayed in
+    /// place of the real identifiers of hidden cards.
+    ///
+    /// This is drawn from OS randomness rather than from [Self::rng], so that
+    /// identifiers cannot be predicted from the battle's seed or from the
+    /// order of shuffled decks.
+    ///
+    /// Battles saved before this was added are given a new random seed when
+    /// loaded.
+    #[serde(default = "rand::random")]
+    pub hidden_card_seed: u64,
+
+    /// Actions which have changed the state of this battle, in the order they
+    /// were taken.
+    #[serde(default)]
+    pub action_log: Vec<LogEntry>,
+}
+
+/// A choice the user must make before the battle can continue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BattlePrompt {
+    /// Select a character controlled by `target_controller` as the target of
+    /// the `source` card.
+    SelectTarget { source: CardId, target_controller: Seat },
+}
+
+impl BattleData {
+    /// Creates an empty battle whose random number generator is seeded from
+    /// `seed`, which must not be derived from public information such as the
+    /// battle ID.
+    pub fn new(id: BattleId, seed: u64) -> Self {
+        Self {
+            id,
+            version: 0,
+            user: PlayerData::new(Seat::Host),
+            enemy: PlayerData::new(Seat::Guest),
+            guest: None,
+            cards: SlotMap::with_key(),
+            next_sorting_key: 1,
+            prompt: None,
+            turn: TurnData::default(),
+            pending_triggers: VecDeque::new(),
+            seed,
+            rng: Xoshiro256StarStar::seed_from_u64(seed),
+            hidden_card_seed: rand::random(),
+            action_log: vec![],
+        }
+    }
+
+    pub fn player(&self, name: Seat) -> &PlayerData {
+        match name {
+            Seat::Host => &self.user,
+            Seat::Guest => &self.enemy,
+        }
+    }
+
+    pub fn player_mut(&mut self, name: Seat) -> &mut PlayerData {
+        match name {
+            Seat::Host => &mut self.user,
+            Seat::Guest => &mut self.enemy,
+        }
+    }
+
+    pub fn card(&self, id: CardId) -> Option<&BattleCard> {
+        self.cards.get(id)
+    }
+
+    pub fn card_mut(&mut self, id: CardId) -> Option<&mut BattleCard> {
+        self.cards.get_mut(id)
+    }
+
+    /// Returns all cards owned by `owner` in the given zone, in sorting key
+    /// order.
+    pub fn cards_in_zone(&self, owner: Seat, zone: Zone) -> Vec<&BattleCard> {
+        let mut result =
+            self.cards.values().filter(|c| c.owner == owner && c.zone == zone).collect::<Vec<_>>();
+        result.sort_by_key(|c| c.sorting_key);
+        result
+    }
+
+    /// Creates a new card and places it on top of the indicated zone.
+    pub fn create_card(&mut self, name: CardName, owner: Seat, zone: Zone) -> CardId {
+        let sorting_key = self.new_sorting_key();
+        self.cards.insert_with_key(|id| BattleCard {
+            id,
+            name,
+            owner,
+            zone,
+            previous_zone: None,
+            sorting_key,
+            revealed_to: BTreeSet::new(),
+        })
+    }
+
+    /// Moves a card to the top of the indicated zone, hiding it again from any
+    /// players it was revealed to.
+    ///
+    /// Returns false if no card with this ID exists.
+    pub fn move_card(&mut self, id: CardId, zone: Zone) -> bool {
+        let sorting_key = self.new_sorting_key();
+        if let Some(card) = self.cards.get_mut(id) {
+            card.previous_zone = Some(card.zone);
+            card.zone = zone;
+            card.sorting_key = sorting_key;
+            card.revealed_to.clear();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the top card of the `owner`'s deck, if any.
+    pub fn top_of_deck(&self, owner: Seat) -> Option<CardId> {
+        self.cards_in_zone(owner, Zone::Deck).last().map(|c| c.id)
+    }
+
+    /// Returns the card which will next be revealed from the `owner`'s
+    /// dreamwell, if any.
+    pub fn next_dreamwell_card(&self, owner: Seat) -> Option<CardId> {
+        self.cards_in_zone(owner, Zone::Dreamwell).first().map(|c| c.id)
+    }
+
+    /// Randomizes the order of cards in the `owner`'s deck.
+    pub fn shuffle_deck(&mut self, owner: Seat) {
+        let mut ids =
+            self.cards_in_zone(owner, Zone::Deck).iter().map(|c| c.id).collect::<Vec<_>>();
+        ids.shuffle(&mut self.rng);
+        for id in ids {
+            self.move_card(id, Zone::Deck);
+        }
+    }
+
+    fn new_sorting_key(&mut self) -> u32 {
+        let result = self.next_sorting_key;
+        self.next_sorting_key += 1;
+        result
+    }
+}
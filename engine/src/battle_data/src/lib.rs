@@ -0,0 +1,5 @@
+pub mod action_log;
+pub mod battle_card;
+pub mod battle_data;
+pub mod player_data;
+pub mod turn_data;
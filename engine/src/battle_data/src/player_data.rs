@@ -0,0 +1,38 @@
+use action_data::battle_action::CardBrowserType;
+use core_data::numerics::{Energy, Points};
+use core_data::types::Seat;
+use serde::{Deserialize, Serialize};
+
+/// State of a player within an ongoing battle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerData {
+    /// Identifies this player
+    pub name: Seat,
+
+    /// Current score total
+    pub points: Points,
+
+    /// Energy currently available to this player
+    pub energy: Energy,
+
+    /// Energy this player gains at the start of each of their turns,
+    /// determined by the cards revealed from their dreamwell.
+    pub produced_energy: Energy,
+
+    /// Cards currently being displayed in this player's card browser, if any.
+    #[serde(default)]
+    pub card_browser: Option<CardBrowserType>,
+}
+
+impl PlayerData {
+    pub fn new(name: Seat) -> Self {
+        Self {
+            name,
+            points: Points(0),
+            energy: Energy(0),
+            produced_energy: Energy(0),
+            card_browser: None,
+        }
+    }
+}
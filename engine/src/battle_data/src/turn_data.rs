@@ -0,0 +1,48 @@
+use std::collections::BTreeSet;
+
+use core_data::identifiers::CardId;
+use core_data::types::Seat;
+use serde::{Deserialize, Serialize};
+
+/// State of the current turn of a battle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TurnData {
+    /// Player whose turn it is
+    pub active_player: Seat,
+
+    /// Number of turns which have started in this battle
+    pub turn_number: u32,
+
+    /// Card revealed from the active player's dreamwell this turn, if any.
+    pub dreamwell_card: Option<CardId>,
+
+    /// "Once per turn" triggered abilities which have already triggered this
+    /// turn, identified by card and ability index.
+    pub triggered_this_turn: BTreeSet<(CardId, usize)>,
+}
+
+impl Default for TurnData {
+    fn default() -> Self {
+        Self {
+            active_player: Seat::Host,
+            turn_number: 0,
+            dreamwell_card: None,
+            triggered_this_turn: BTreeSet::new(),
+        }
+    }
+}
+
+/// A triggered ability which is waiting to resolve.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTrigger {
+    /// Card with the triggered ability
+    pub card: CardId,
+
+    /// Index of the ability within the card's abilities
+    pub ability_index: usize,
+
+    /// Player who controls the triggered ability
+    pub controller: Seat,
+}
@@ -0,0 +1,97 @@
+use core_data::character_type::CharacterType;
+use core_data::numerics::{Energy, Spark};
+use core_data::types::Rarity;
+use serde::{Deserialize, Serialize};
+
+use crate::card_name::CardName;
+
+/// Describes the rules and visual presentation of a card.
+#[derive(Debug, Clone)]
+pub struct CardDefinition {
+    /// Identifier for this card definition
+    pub name: CardName,
+
+    /// Name of this card as displayed to the player, also used in decklists.
+    pub displayed_name: &'static str,
+
+    /// Primary type of this card
+    pub card_type: CardType,
+
+    /// Subtype to display on this card, e.g. "Ancient".
+    pub subtype: Option<&'static str>,
+
+    /// Character type of this card, used to evaluate predicates such as
+    /// "non-{cardtype: warrior} character".
+    pub character_type: Option<CharacterType>,
+
+    /// Energy cost to play this card, if any.
+    pub cost: Option<Energy>,
+
+    /// Base spark value of this card, if it is a character.
+    pub spark: Option<Spark>,
+
+    /// Rarity of this card
+    pub rarity: Rarity,
+
+    /// Rules text to display for this card
+    pub rules_text: &'static str,
+
+    /// Rules text in the syntax understood by the ability parser, used to
+    /// determine this card's abilities. None if this card has no abilities or
+    /// they cannot yet be parsed.
+    pub ability_text: Option<&'static str>,
+
+    /// Help text describing keywords used in this card's rules text.
+    ///
+    /// Only displayed if no keywords are found in the abilities parsed from
+    /// [Self::ability_text], since reminder text is otherwise generated for
+    /// those keywords.
+    pub supplemental_info: Option<&'static str>,
+
+    /// Address of the image for this card
+    pub image: &'static str,
+
+    /// True if this card can be played during the opponent's turn
+    pub is_fast: bool,
+
+    /// Change to its owner's energy production when this card is revealed
+    /// from the dreamwell. Only present for dreamwell cards.
+    pub energy_production: Option<EnergyProduction>,
+}
+
+impl CardDefinition {
+    /// True if this card can be included in a player's deck.
+    pub fn is_deck_card(&self) -> bool {
+        matches!(self.card_type, CardType::Character | CardType::Event)
+    }
+}
+
+/// Possible primary types of cards.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CardType {
+    Character,
+    Event,
+
+    /// The card representing an AI opponent
+    Enemy,
+
+    /// A card which persists between battles, modifying the rules for its
+    /// owner.
+    Dreamsign,
+
+    /// A card which is revealed from its owner's dreamwell at the start of
+    /// their turn to produce energy.
+    Dreamwell,
+}
+
+/// How a dreamwell card modifies its owner's energy production.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EnergyProduction {
+    /// Energy production becomes this value
+    Set(Energy),
+
+    /// Energy production increases by this amount
+    Increase(Energy),
+}
@@ -0,0 +1,313 @@
+use core_data::character_type::CharacterType;
+use core_data::numerics::{Energy, Spark};
+use core_data::types::Rarity;
+use strum::IntoEnumIterator;
+
+use crate::card_definition::{CardDefinition, CardType, EnergyProduction};
+use crate::card_name::CardName;
+
+/// Returns the definition for the card with the given name.
+pub fn get(name: CardName) -> &'static CardDefinition {
+    match name {
+        CardName::TitanOfForgottenEchoes => &TITAN_OF_FORGOTTEN_ECHOES,
+        CardName::BeaconOfTomorrow => &BEACON_OF_TOMORROW,
+        CardName::ScrapReclaimer => &SCRAP_RECLAIMER,
+        CardName::EvacuationEnforcer => &EVACUATION_ENFORCER,
+        CardName::EmberwardVanguard => &EMBERWARD_VANGUARD,
+        CardName::MoonlitVoyage => &MOONLIT_VOYAGE,
+        CardName::Korrak => &KORRAK,
+        CardName::DragonEgg => &DRAGON_EGG,
+        CardName::ShimmeringLantern => &SHIMMERING_LANTERN,
+        CardName::HourglassOfEchoes => &HOURGLASS_OF_ECHOES,
+        CardName::EmberChalice => &EMBER_CHALICE,
+        CardName::Skypath => &SKYPATH,
+        CardName::AutumnGlade => &AUTUMN_GLADE,
+        CardName::TwilightRadiance => &TWILIGHT_RADIANCE,
+        CardName::AstralInterface => &ASTRAL_INTERFACE,
+        CardName::MidnightGathering => &MIDNIGHT_GATHERING,
+    }
+}
+
+/// Returns an iterator over all card definitions.
+pub fn all() -> impl Iterator<Item = &'static CardDefinition> {
+    CardName::iter().map(get)
+}
+
+/// Looks up a card by its displayed name, ignoring case.
+pub fn find_by_displayed_name(displayed_name: &str) -> Option<CardName> {
+    all().find(|d| d.displayed_name.eq_ignore_ascii_case(displayed_name.trim())).map(|d| d.name)
+}
+
+static TITAN_OF_FORGOTTEN_ECHOES: CardDefinition = CardDefinition {
+    name: CardName::TitanOfForgottenEchoes,
+    displayed_name: "Titan of Forgotten Echoes",
+    card_type: CardType::Character,
+    subtype: Some("Ancient"),
+    character_type: None,
+    cost: Some(Energy(6)),
+    spark: Some(Spark(4)),
+    rarity: Rarity::Rare,
+    rules_text: "When you materialize your second character in a turn, return this character from your void to play.",
+    ability_text: None,
+    supplemental_info: Some("<b>Materialize</b>: A character entering play."),
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/2521694543.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static BEACON_OF_TOMORROW: CardDefinition = CardDefinition {
+    name: CardName::BeaconOfTomorrow,
+    displayed_name: "Beacon of Tomorrow",
+    card_type: CardType::Event,
+    subtype: None,
+    character_type: None,
+    cost: Some(Energy(2)),
+    spark: None,
+    rarity: Rarity::Uncommon,
+    rules_text: "Discover a card with cost (2).",
+    ability_text: None,
+    supplemental_info: Some(
+        "<b>Discover</b>: Pick one of 4 cards with different types to put into your hand.",
+    ),
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/1633431262.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static SCRAP_RECLAIMER: CardDefinition = CardDefinition {
+    name: CardName::ScrapReclaimer,
+    displayed_name: "Scrap Reclaimer",
+    card_type: CardType::Character,
+    subtype: Some("Tinkerer"),
+    character_type: None,
+    cost: Some(Energy(4)),
+    spark: Some(Spark(0)),
+    rarity: Rarity::Common,
+    rules_text: "Judgment: Return this character from your void to your hand. Born from rust and resilience.",
+    ability_text: None,
+    supplemental_info: Some("<b>Judgment</b>: Triggers at the start of your turn."),
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/2269064817.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static EVACUATION_ENFORCER: CardDefinition = CardDefinition {
+    name: CardName::EvacuationEnforcer,
+    displayed_name: "Evacuation Enforcer",
+    card_type: CardType::Character,
+    subtype: Some("Trooper"),
+    character_type: None,
+    cost: Some(Energy(2)),
+    spark: Some(Spark(0)),
+    rarity: Rarity::Common,
+    rules_text: "> Draw 2 cards. Discard 3 cards.\nPromises under a stormy sky.",
+    ability_text: None,
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/2269064809.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static EMBERWARD_VANGUARD: CardDefinition = CardDefinition {
+    name: CardName::EmberwardVanguard,
+    displayed_name: "Emberward Vanguard",
+    card_type: CardType::Character,
+    subtype: Some("Warrior"),
+    character_type: Some(CharacterType::Warrior),
+    cost: Some(Energy(3)),
+    spark: Some(Spark(2)),
+    rarity: Rarity::Common,
+    rules_text: "The first to stand against the tide of dreams.",
+    ability_text: None,
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/2269064809.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static MOONLIT_VOYAGE: CardDefinition = CardDefinition {
+    name: CardName::MoonlitVoyage,
+    displayed_name: "Moonlit Voyage",
+    card_type: CardType::Event,
+    subtype: None,
+    character_type: None,
+    cost: Some(Energy(2)),
+    spark: None,
+    rarity: Rarity::Uncommon,
+    rules_text: "Draw 2 cards. Discard 2 cards.\nReclaim",
+    ability_text: Some("Draw 2 cards. Discard 2 cards.$br{kw: Reclaim}."),
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/2027158310.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static KORRAK: CardDefinition = CardDefinition {
+    name: CardName::Korrak,
+    displayed_name: "<size=200%>Korrak</size>\nHellfire Sovereign",
+    card_type: CardType::Enemy,
+    subtype: None,
+    character_type: None,
+    cost: None,
+    spark: None,
+    rarity: Rarity::Legendary,
+    rules_text: ">Judgment: A character you control gains +2 spark.",
+    ability_text: None,
+    supplemental_info: Some("<b>Judgment</b>: Triggers at the start of enemy's turn."),
+    image: "Assets/ThirdParty/GameAssets/CardImages/Enemy/Korrak.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static DRAGON_EGG: CardDefinition = CardDefinition {
+    name: CardName::DragonEgg,
+    displayed_name: "Dragon Egg",
+    card_type: CardType::Dreamsign,
+    subtype: None,
+    character_type: None,
+    cost: None,
+    spark: None,
+    rarity: Rarity::Rare,
+    rules_text: ">Judgment: If you control 3 characters with the same type, draw a card.",
+    ability_text: None,
+    supplemental_info: Some("<b>Judgment</b>: Triggers at the start of enemy's turn."),
+    image: "Assets/ThirdParty/GameAssets/CardImages/Dreamsign/DragonEgg.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static SHIMMERING_LANTERN: CardDefinition = CardDefinition {
+    name: CardName::ShimmeringLantern,
+    displayed_name: "Shimmering Lantern",
+    card_type: CardType::Dreamsign,
+    subtype: None,
+    character_type: None,
+    cost: None,
+    spark: None,
+    rarity: Rarity::Uncommon,
+    rules_text: "Characters cost you 1 less.",
+    ability_text: Some("Characters cost you $1 less."),
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Dreamsign/DragonEgg.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static HOURGLASS_OF_ECHOES: CardDefinition = CardDefinition {
+    name: CardName::HourglassOfEchoes,
+    displayed_name: "Hourglass of Echoes",
+    card_type: CardType::Dreamsign,
+    subtype: None,
+    character_type: None,
+    cost: None,
+    spark: None,
+    rarity: Rarity::Rare,
+    rules_text: "Characters in your hand have <b>fast</b>.",
+    ability_text: Some("Characters in your hand have '$fast'."),
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Dreamsign/DragonEgg.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static EMBER_CHALICE: CardDefinition = CardDefinition {
+    name: CardName::EmberChalice,
+    displayed_name: "Ember Chalice",
+    card_type: CardType::Dreamsign,
+    subtype: None,
+    character_type: None,
+    cost: None,
+    spark: None,
+    rarity: Rarity::Uncommon,
+    rules_text: "Once per turn, when you gain energy, draw a card.",
+    ability_text: Some("Once per turn, when you gain energy, draw a card."),
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Dreamsign/DragonEgg.png",
+    is_fast: false,
+    energy_production: None,
+};
+
+static SKYPATH: CardDefinition = CardDefinition {
+    name: CardName::Skypath,
+    displayed_name: "Skypath",
+    card_type: CardType::Dreamwell,
+    subtype: None,
+    character_type: None,
+    cost: None,
+    spark: None,
+    rarity: Rarity::Common,
+    rules_text: "+1 energy production.",
+    ability_text: None,
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/2521694543.png",
+    is_fast: false,
+    energy_production: Some(EnergyProduction::Increase(Energy(1))),
+};
+
+static AUTUMN_GLADE: CardDefinition = CardDefinition {
+    name: CardName::AutumnGlade,
+    displayed_name: "Autumn Glade",
+    card_type: CardType::Dreamwell,
+    subtype: None,
+    character_type: None,
+    cost: None,
+    spark: None,
+    rarity: Rarity::Common,
+    rules_text: "+1 energy production.",
+    ability_text: None,
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/1633431262.png",
+    is_fast: false,
+    energy_production: Some(EnergyProduction::Increase(Energy(1))),
+};
+
+static TWILIGHT_RADIANCE: CardDefinition = CardDefinition {
+    name: CardName::TwilightRadiance,
+    displayed_name: "Twilight Radiance",
+    card_type: CardType::Dreamwell,
+    subtype: None,
+    character_type: None,
+    cost: None,
+    spark: None,
+    rarity: Rarity::Common,
+    rules_text: "+1 energy production.\nGain 1 energy.",
+    ability_text: Some("Gain $1."),
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/2269064817.png",
+    is_fast: false,
+    energy_production: Some(EnergyProduction::Increase(Energy(1))),
+};
+
+static ASTRAL_INTERFACE: CardDefinition = CardDefinition {
+    name: CardName::AstralInterface,
+    displayed_name: "Astral Interface",
+    card_type: CardType::Dreamwell,
+    subtype: None,
+    character_type: None,
+    cost: None,
+    spark: None,
+    rarity: Rarity::Common,
+    rules_text: "Energy production becomes 3.",
+    ability_text: None,
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/2269064809.png",
+    is_fast: false,
+    energy_production: Some(EnergyProduction::Set(Energy(3))),
+};
+
+static MIDNIGHT_GATHERING: CardDefinition = CardDefinition {
+    name: CardName::MidnightGathering,
+    displayed_name: "Midnight Gathering",
+    card_type: CardType::Dreamwell,
+    subtype: None,
+    character_type: None,
+    cost: None,
+    spark: None,
+    rarity: Rarity::Common,
+    rules_text: "+1 energy production.\nDouble the energy in your energy pool.",
+    ability_text: Some("Double the amount of energy in your energy pool."),
+    supplemental_info: None,
+    image: "Assets/ThirdParty/GameAssets/CardImages/Standard/2027158310.png",
+    is_fast: false,
+    energy_production: Some(EnergyProduction::Increase(Energy(1))),
+};
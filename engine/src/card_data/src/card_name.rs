@@ -0,0 +1,42 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+/// Identifies a card definition.
+///
+/// Every card-like object which can appear in a battle or quest has a unique
+/// name. The rules for each name are defined in
+/// [crate::card_definitions::get].
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    EnumIter,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum CardName {
+    TitanOfForgottenEchoes,
+    BeaconOfTomorrow,
+    ScrapReclaimer,
+    EvacuationEnforcer,
+    EmberwardVanguard,
+    MoonlitVoyage,
+    Korrak,
+    DragonEgg,
+    ShimmeringLantern,
+    HourglassOfEchoes,
+    EmberChalice,
+    Skypath,
+    AutumnGlade,
+    TwilightRadiance,
+    AstralInterface,
+    MidnightGathering,
+}
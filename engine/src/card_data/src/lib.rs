@@ -0,0 +1,3 @@
+pub mod card_definition;
+pub mod card_definitions;
+pub mod card_name;
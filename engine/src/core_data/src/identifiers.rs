@@ -19,6 +19,13 @@ pub struct UserId(pub Uuid);
 #[serde(rename_all = "camelCase")]
 pub struct BattleId(pub Uuid);
 
+/// A Quest ID
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestId(pub Uuid);
+
 new_key_type! {
     /// Identifies a card or card-like object such as:
     ///
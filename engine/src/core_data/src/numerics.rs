@@ -91,3 +91,32 @@ pub struct Spark(pub u32);
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Points(pub u32);
+
+/// Currency earned during a quest, used to purchase cards from shops.
+#[derive(
+    Debug,
+    Display,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Ord,
+    PartialOrd,
+    Hash,
+    From,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Sum,
+    AddAssign,
+    SubAssign,
+    MulAssign,
+    DivAssign,
+    Into,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Essence(pub u32);
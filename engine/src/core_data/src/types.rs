@@ -23,3 +23,75 @@ pub enum PlayerName {
     /// Opponent of user, i.e. the AI enemy
     Enemy,
 }
+
+impl PlayerName {
+    /// Returns the other player in a battle.
+    pub fn opponent(self) -> Self {
+        match self {
+            PlayerName::User => PlayerName::Enemy,
+            PlayerName::Enemy => PlayerName::User,
+        }
+    }
+}
+
+/// Identifies a seat in an ongoing battle, independent of which user is
+/// viewing it.
+///
+/// Battle state always refers to players by seat. A seat is converted to a
+/// [PlayerName] relative to the user viewing the battle when it is rendered.
+#[derive(
+    Clone, Debug, Eq, PartialEq, Copy, Hash, Ord, PartialOrd, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Seat {
+    /// Seat of the user who created the battle
+    #[serde(alias = "user")]
+    Host,
+
+    /// Seat of the host's opponent, either the AI enemy or the user who
+    /// joined the battle
+    #[serde(alias = "enemy")]
+    Guest,
+}
+
+impl Seat {
+    /// Returns the other seat in a battle.
+    pub fn opponent(self) -> Self {
+        match self {
+            Seat::Host => Seat::Guest,
+            Seat::Guest => Seat::Host,
+        }
+    }
+
+    /// Returns the player in this seat as seen by the user in the `viewer`
+    /// seat, who sees themselves as [PlayerName::User].
+    pub fn player_name(self, viewer: Seat) -> PlayerName {
+        if self == viewer {
+            PlayerName::User
+        } else {
+            PlayerName::Enemy
+        }
+    }
+
+    /// Returns the seat of `player` as seen by the user in the `viewer` seat.
+    /// This is the inverse of [Self::player_name].
+    pub fn of_player(player: PlayerName, viewer: Seat) -> Self {
+        match player {
+            PlayerName::User => viewer,
+            PlayerName::Enemy => viewer.opponent(),
+        }
+    }
+}
+
+/// How rare a card is. Deck-building rules restrict how many cards of each
+/// rarity can be included in a deck.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Legendary,
+}
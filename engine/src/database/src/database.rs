@@ -0,0 +1,57 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+use core_data::identifiers::UserId;
+
+use crate::save_file::SaveFile;
+
+/// Storage for user save files.
+pub trait Database: Send + Sync {
+    /// Reads the save file for a user, or returns None if they do not have
+    /// one.
+    fn fetch_save(&self, user_id: UserId) -> Result<Option<SaveFile>, DatabaseError>;
+
+    /// Replaces the stored save file for the user who owns `save`.
+    fn write_save(&self, save: &SaveFile) -> Result<(), DatabaseError>;
+
+    /// Returns the IDs of all users who have a save file.
+    fn list_users(&self) -> Result<Vec<UserId>, DatabaseError>;
+}
+
+/// Reasons a save file could not be read or written.
+#[derive(Debug)]
+pub enum DatabaseError {
+    Io(io::Error),
+    Serialization(serde_json::Error),
+}
+
+impl Display for DatabaseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Error accessing save file: {error}"),
+            Self::Serialization(error) => write!(f, "Error serializing save file: {error}"),
+        }
+    }
+}
+
+impl Error for DatabaseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Serialization(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for DatabaseError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for DatabaseError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialization(error)
+    }
+}
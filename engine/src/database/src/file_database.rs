@@ -0,0 +1,62 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use core_data::identifiers::UserId;
+use uuid::Uuid;
+
+use crate::database::{Database, DatabaseError};
+use crate::save_file::SaveFile;
+
+/// Stores each user's save file as a JSON file within a directory.
+#[derive(Debug, Clone)]
+pub struct FileDatabase {
+    directory: PathBuf,
+}
+
+impl FileDatabase {
+    /// Creates a database which stores files in `directory`, creating the
+    /// directory if it does not already exist.
+    pub fn new(directory: impl Into<PathBuf>) -> Result<Self, DatabaseError> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        Ok(Self { directory })
+    }
+
+    fn path(&self, user_id: UserId) -> PathBuf {
+        self.directory.join(format!("{}.json", user_id.0))
+    }
+}
+
+impl Database for FileDatabase {
+    fn fetch_save(&self, user_id: UserId) -> Result<Option<SaveFile>, DatabaseError> {
+        match fs::read(self.path(user_id)) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn write_save(&self, save: &SaveFile) -> Result<(), DatabaseError> {
+        // Write to a temporary file first so that a crash mid-write cannot
+        // leave a truncated save file behind.
+        let path = self.path(save.user_id);
+        let temporary = path.with_extension("json.tmp");
+        fs::write(&temporary, serde_json::to_vec(save)?)?;
+        fs::rename(temporary, path)?;
+        Ok(())
+    }
+
+    fn list_users(&self) -> Result<Vec<UserId>, DatabaseError> {
+        let mut result = vec![];
+        for entry in fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "json") {
+                if let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    result.extend(Uuid::parse_str(id).ok().map(UserId));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
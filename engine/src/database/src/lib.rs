@@ -0,0 +1,3 @@
+pub mod database;
+pub mod file_database;
+pub mod save_file;
@@ -0,0 +1,50 @@
+use battle_data::battle_data::BattleData;
+use core_data::identifiers::{BattleId, UserId};
+use quest_data::quest_data::QuestData;
+use serde::{Deserialize, Serialize};
+
+/// Persistent state for a single user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveFile {
+    /// User who owns this save file
+    pub user_id: UserId,
+
+    /// Quest the user is currently playing, if any.
+    ///
+    /// Quests are not yet started or advanced by client requests, which
+    /// always play [Self::battle] directly, so this is never set by the engine.
+    /// Quest state is managed by the engine's `quest_creation` and
+    /// `quest_mutations` modules until requests for it are added.
+    pub quest: Option<QuestData>,
+
+    /// Battle the user is currently playing, if any.
+    pub battle: Option<BattleData>,
+
+    /// State of the current battle before any actions were taken, from which
+    /// it can be replayed using its action log.
+    #[serde(default)]
+    pub battle_start: Option<BattleData>,
+
+    /// Battle stored in another user's save file which this user has joined
+    /// as their opponent, if any.
+    #[serde(default)]
+    pub joined_battle: Option<JoinedBattle>,
+}
+
+/// Reference to a battle between two users, stored in the save file of the
+/// user who created it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JoinedBattle {
+    /// User who created the battle
+    pub host: UserId,
+
+    pub battle_id: BattleId,
+}
+
+impl SaveFile {
+    pub fn new(user_id: UserId) -> Self {
+        Self { user_id, quest: None, battle: None, battle_start: None, joined_battle: None }
+    }
+}
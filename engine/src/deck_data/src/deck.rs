@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use card_data::card_name::CardName;
+use serde::{Deserialize, Serialize};
+
+/// A collection of cards a player brings into a dream battle.
+///
+/// Decks are unordered, they only track how many copies of each card are
+/// present. Card order is determined when the deck is shuffled at the start of
+/// a battle.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deck {
+    cards: BTreeMap<CardName, usize>,
+}
+
+impl Deck {
+    /// Adds `count` copies of a card to this deck.
+    pub fn add(&mut self, name: CardName, count: usize) {
+        if count > 0 {
+            *self.cards.entry(name).or_default() += count;
+        }
+    }
+
+    /// Removes a single copy of a card from this deck. Returns false if no
+    /// copy of this card was present.
+    pub fn remove(&mut self, name: CardName) -> bool {
+        match self.cards.get_mut(&name) {
+            Some(count) if *count > 1 => {
+                *count -= 1;
+                true
+            }
+            Some(_) => {
+                self.cards.remove(&name);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Number of copies of a card in this deck.
+    pub fn count(&self, name: CardName) -> usize {
+        self.cards.get(&name).copied().unwrap_or_default()
+    }
+
+    /// Total number of cards in this deck.
+    pub fn len(&self) -> usize {
+        self.cards.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Iterator over each distinct card in this deck along with its number of
+    /// copies, in card name order.
+    pub fn entries(&self) -> impl Iterator<Item = (CardName, usize)> + '_ {
+        self.cards.iter().map(|(&name, &count)| (name, count))
+    }
+
+    /// Iterator over every card in this deck, repeating each name once per
+    /// copy.
+    pub fn cards(&self) -> impl Iterator<Item = CardName> + '_ {
+        self.entries().flat_map(|(name, count)| std::iter::repeat_n(name, count))
+    }
+}
+
+impl FromIterator<CardName> for Deck {
+    fn from_iter<T: IntoIterator<Item = CardName>>(iter: T) -> Self {
+        let mut result = Self::default();
+        for name in iter {
+            result.add(name, 1);
+        }
+        result
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use card_data::card_definitions;
+use card_data::card_name::CardName;
+use core_data::types::Rarity;
+
+use crate::deck::Deck;
+
+/// Deck-building restrictions which a deck must satisfy in order to be used in
+/// a battle.
+#[derive(Debug, Clone)]
+pub struct DeckRules {
+    /// Smallest number of cards a deck can contain
+    pub minimum_size: usize,
+
+    /// Largest number of cards a deck can contain
+    pub maximum_size: usize,
+
+    /// Maximum number of copies of any individual card
+    pub maximum_copies: usize,
+
+    /// Maximum total number of cards of a given rarity. Rarities which are not
+    /// present are unrestricted.
+    pub rarity_caps: BTreeMap<Rarity, usize>,
+}
+
+impl Default for DeckRules {
+    fn default() -> Self {
+        Self {
+            minimum_size: 20,
+            maximum_size: 50,
+            maximum_copies: 4,
+            rarity_caps: BTreeMap::from([(Rarity::Rare, 8), (Rarity::Legendary, 1)]),
+        }
+    }
+}
+
+/// A way in which a deck fails to satisfy [DeckRules].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DeckRuleViolation {
+    TooFewCards {
+        count: usize,
+        minimum: usize,
+    },
+    TooManyCards {
+        count: usize,
+        maximum: usize,
+    },
+    TooManyCopies {
+        card: CardName,
+        count: usize,
+        maximum: usize,
+    },
+    RarityCapExceeded {
+        rarity: Rarity,
+        count: usize,
+        maximum: usize,
+    },
+
+    /// Card cannot be included in a deck, e.g. because it is a dreamsign.
+    NotADeckCard(CardName),
+}
+
+impl Display for DeckRuleViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooFewCards { count, minimum } => {
+                write!(f, "Deck has {count} cards, but must have at least {minimum}")
+            }
+            Self::TooManyCards { count, maximum } => {
+                write!(f, "Deck has {count} cards, but can have at most {maximum}")
+            }
+            Self::TooManyCopies { card, count, maximum } => write!(
+                f,
+                "Deck has {count} copies of {}, but can have at most {maximum}",
+                card_definitions::get(*card).displayed_name
+            ),
+            Self::RarityCapExceeded { rarity, count, maximum } => {
+                write!(f, "Deck has {count} {rarity:?} cards, but can have at most {maximum}")
+            }
+            Self::NotADeckCard(card) => {
+                write!(
+                    f,
+                    "{} cannot be included in a deck",
+                    card_definitions::get(*card).displayed_name
+                )
+            }
+        }
+    }
+}
+
+impl DeckRules {
+    /// Checks a deck against these rules, returning a list of all rule
+    /// violations. An empty list indicates the deck is legal.
+    pub fn validate(&self, deck: &Deck) -> Vec<DeckRuleViolation> {
+        let mut result = vec![];
+        let size = deck.len();
+        if size < self.minimum_size {
+            result.push(DeckRuleViolation::TooFewCards { count: size, minimum: self.minimum_size });
+        }
+        if size > self.maximum_size {
+            result
+                .push(DeckRuleViolation::TooManyCards { count: size, maximum: self.maximum_size });
+        }
+
+        let mut rarity_counts = BTreeMap::<Rarity, usize>::new();
+        for (card, count) in deck.entries() {
+            let definition = card_definitions::get(card);
+            if !definition.is_deck_card() {
+                result.push(DeckRuleViolation::NotADeckCard(card));
+            }
+            if count > self.maximum_copies {
+                result.push(DeckRuleViolation::TooManyCopies {
+                    card,
+                    count,
+                    maximum: self.maximum_copies,
+                });
+            }
+            *rarity_counts.entry(definition.rarity).or_default() += count;
+        }
+
+        for (&rarity, &maximum) in &self.rarity_caps {
+            let count = rarity_counts.get(&rarity).copied().unwrap_or_default();
+            if count > maximum {
+                result.push(DeckRuleViolation::RarityCapExceeded { rarity, count, maximum });
+            }
+        }
+
+        result
+    }
+
+    /// True if the provided deck satisfies these rules.
+    pub fn is_legal(&self, deck: &Deck) -> bool {
+        self.validate(deck).is_empty()
+    }
+}
@@ -0,0 +1,93 @@
+//! Text format for importing and exporting decks.
+//!
+//! A decklist contains one card per line, written as a number of copies
+//! followed by the card's displayed name, e.g. "3 Moonlit Voyage". The count
+//! may optionally be followed by an "x" ("3x Moonlit Voyage") and may be
+//! omitted entirely to indicate a single copy. Card names are matched without
+//! regard to case. Blank lines and lines starting with `#` are ignored.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use card_data::card_definitions;
+
+use crate::deck::Deck;
+
+/// Describes a line of a decklist which could not be read.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DecklistError {
+    /// 1-indexed line number of the error
+    pub line_number: usize,
+
+    /// Text of the line which could not be read
+    pub line: String,
+
+    pub kind: DecklistErrorKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DecklistErrorKind {
+    /// The number of copies was zero or could not be read
+    InvalidCount,
+
+    /// No card exists with the given name
+    UnknownCard(String),
+}
+
+impl Display for DecklistError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            DecklistErrorKind::InvalidCount => {
+                write!(f, "Invalid card count on line {}: '{}'", self.line_number, self.line)
+            }
+            DecklistErrorKind::UnknownCard(name) => {
+                write!(f, "Unknown card '{}' on line {}", name, self.line_number)
+            }
+        }
+    }
+}
+
+impl Error for DecklistError {}
+
+/// Reads a [Deck] from its decklist text representation.
+pub fn parse(text: &str) -> Result<Deck, DecklistError> {
+    let mut deck = Deck::default();
+    for (index, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let error = |kind| DecklistError { line_number: index + 1, line: line.to_string(), kind };
+        let (count, name) =
+            split_count(trimmed).ok_or_else(|| error(DecklistErrorKind::InvalidCount))?;
+        let card = card_definitions::find_by_displayed_name(name)
+            .ok_or_else(|| error(DecklistErrorKind::UnknownCard(name.to_string())))?;
+        deck.add(card, count);
+    }
+    Ok(deck)
+}
+
+/// Writes a [Deck] in decklist text format.
+///
+/// The output lists one card per line in card name order, and can be read back
+/// via [parse].
+pub fn format(deck: &Deck) -> String {
+    deck.entries()
+        .map(|(name, count)| format!("{} {}\n", count, card_definitions::get(name).displayed_name))
+        .collect()
+}
+
+/// Splits a decklist line into its card count and card name.
+fn split_count(line: &str) -> Option<(usize, &str)> {
+    let Some((first, rest)) = line.split_once(char::is_whitespace) else {
+        return (!line.starts_with(|c: char| c.is_ascii_digit())).then_some((1, line));
+    };
+
+    if !first.starts_with(|c: char| c.is_ascii_digit()) {
+        return Some((1, line));
+    }
+
+    let count = first.strip_suffix(['x', 'X']).unwrap_or(first).parse::<usize>().ok()?;
+    (count > 0).then_some((count, rest.trim()))
+}
@@ -0,0 +1,3 @@
+pub mod deck;
+pub mod deck_rules;
+pub mod decklist;
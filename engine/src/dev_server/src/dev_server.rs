@@ -1,28 +1,158 @@
-use axum::extract::Json;
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{Json, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
 use axum::Router;
+use database::file_database::FileDatabase;
 use display_data::request_data::{
-    ConnectRequest, ConnectResponse, PerformActionRequest, PerformActionResponse,
+    ConnectRequest, ConnectResponse, ErrorResponse, ErrorType, ListBattlesResponse,
+    PerformActionRequest, PerformActionResponse, PollRequest, PollResponse, ReplayRequest,
+    ReplayResponse,
 };
-use engine::test_data;
+use display_data::wire_format::{self, WireFormat};
+use engine::request_error::RequestError;
+use engine::session_registry::SessionRegistry;
+use engine::{handle_request, spectator};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+mod spectator_socket;
+mod websocket;
+
+/// Directory in which save files are stored if `DREAMCALLER_SAVE_DIRECTORY` is
+/// not set.
+const DEFAULT_SAVE_DIRECTORY: &str = "saves";
+
+type ServerResult<T> = Result<Encoded<T>, ErrorReply>;
+
+/// An error response with its HTTP status, encoded in the wire format of the
+/// request which failed.
+type ErrorReply = (StatusCode, Encoded<ErrorResponse>);
+
+/// A response message encoded in the given wire format.
+struct Encoded<T>(WireFormat, T);
 
-async fn connect(Json(req): Json<ConnectRequest>) -> Json<ConnectResponse> {
+impl<T: Serialize> IntoResponse for Encoded<T> {
+    fn into_response(self) -> Response {
+        let Encoded(format, value) = self;
+        match wire_format::encode(format, &value) {
+            Ok(bytes) => ([(header::CONTENT_TYPE, format.content_type())], bytes).into_response(),
+            Err(error) => {
+                // Error responses can always be encoded as JSON
+                let error = ErrorResponse::new(ErrorType::Internal, error.to_string());
+                println!("Error encoding response: {:?}", error);
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        }
+    }
+}
+
+async fn connect(
+    State(registry): State<Arc<SessionRegistry>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<ConnectResponse> {
+    let (_, req) = decode_request::<ConnectRequest>(&headers, &body)?;
     println!("Got connect request: {:?}", req);
-    Json(test_data::connect(&req))
+    handle(req.wire_format, || handle_request::connect(&registry, &req))
 }
 
-async fn perform_action(Json(req): Json<PerformActionRequest>) -> Json<PerformActionResponse> {
+async fn perform_action(
+    State(registry): State<Arc<SessionRegistry>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<PerformActionResponse> {
+    let (format, req) = decode_request::<PerformActionRequest>(&headers, &body)?;
     println!("Got perform action request: {:?}", req);
-    Json(test_data::perform_action(&req))
+    handle(format, || handle_request::perform_action(&registry, &req))
+}
+
+async fn poll(
+    State(registry): State<Arc<SessionRegistry>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<PollResponse> {
+    let (format, req) = decode_request::<PollRequest>(&headers, &body)?;
+    handle(format, || handle_request::poll(&registry, &req))
+}
+
+async fn list_battles(
+    State(registry): State<Arc<SessionRegistry>>,
+) -> ServerResult<ListBattlesResponse> {
+    handle(WireFormat::Json, || spectator::list_battles(&registry))
+}
+
+async fn replay(
+    State(registry): State<Arc<SessionRegistry>>,
+    Query(req): Query<ReplayRequest>,
+) -> ServerResult<ReplayResponse> {
+    println!("Got replay request: {:?}", req);
+    handle(WireFormat::Json, || spectator::replay(&registry, &req))
+}
+
+/// Decodes a request body in the wire format given by its content type,
+/// defaulting to JSON.
+fn decode_request<T: DeserializeOwned>(
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<(WireFormat, T), ErrorReply> {
+    let format = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(WireFormat::from_content_type)
+        .unwrap_or_default();
+    let request = wire_format::decode(format, body).map_err(|error| {
+        error_response(format, ErrorResponse::new(ErrorType::InvalidRequest, error.to_string()))
+    })?;
+    Ok((format, request))
+}
+
+/// Runs a request handler, converting errors and panics into error responses.
+fn handle<T>(
+    format: WireFormat,
+    function: impl FnOnce() -> Result<T, RequestError>,
+) -> ServerResult<T> {
+    match panic::catch_unwind(AssertUnwindSafe(function)) {
+        Ok(Ok(response)) => Ok(Encoded(format, response)),
+        Ok(Err(error)) => Err(error_response(format, error.into())),
+        Err(payload) => Err(error_response(format, ErrorResponse::from_panic(payload.as_ref()))),
+    }
+}
+
+fn error_response(format: WireFormat, error: ErrorResponse) -> ErrorReply {
+    println!("Error handling request: {:?}", error);
+    let status = match error.error_type {
+        ErrorType::InvalidRequest | ErrorType::MissingBattleId => StatusCode::BAD_REQUEST,
+        ErrorType::UnknownBattle => StatusCode::NOT_FOUND,
+        ErrorType::IllegalAction => StatusCode::CONFLICT,
+        ErrorType::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Encoded(format, error))
 }
 
 #[tokio::main]
 async fn main() {
-    println!("Starting server on port 26598");
+    let directory =
+        env::var("DREAMCALLER_SAVE_DIRECTORY").unwrap_or_else(|_| DEFAULT_SAVE_DIRECTORY.into());
+    println!("Storing save files in {directory}");
+    let database = FileDatabase::new(directory).expect("Error opening save directory");
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
 
+    println!("Starting server on port 26598");
     let app = Router::new()
         .route("/connect", get(connect))
-        .route("/perform_action", post(perform_action));
+        .route("/perform_action", post(perform_action))
+        .route("/poll", post(poll))
+        .route("/ws", get(websocket::upgrade))
+        .route("/battles", get(list_battles))
+        .route("/spectate", get(spectator_socket::upgrade))
+        .route("/replay", get(replay))
+        .with_state(registry);
     let listener = tokio::net::TcpListener::bind("0.0.0.0:26598").await.unwrap();
     axum::serve(listener, app).await.unwrap();
 }
@@ -0,0 +1,137 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::response::Response;
+use display_data::request_data::{
+    ErrorResponse, ErrorType, Metadata, PollResponse, SpectateRequest, SpectateResponse,
+    StreamResponse,
+};
+use display_data::wire_format::WireFormat;
+use engine::request_error::RequestError;
+use engine::session_registry::SessionRegistry;
+use engine::spectator::Spectator;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task;
+
+use crate::websocket;
+
+/// Upgrades a request to a read-only WebSocket connection which streams a
+/// battle to a spectator.
+///
+/// The battle and perspective are given as query parameters, e.g.
+/// `/spectate?userId=...&battleId=...&perspective=enemy`. A text frame
+/// containing a JSON [StreamResponse::Update] with a full snapshot of the
+/// battle is sent on connection and whenever an action is taken in the battle,
+/// as notified by [SessionRegistry::watch_battles]. Messages from the client
+/// are ignored.
+pub async fn upgrade(
+    State(registry): State<Arc<SessionRegistry>>,
+    Query(request): Query<SpectateRequest>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| handle_socket(socket, registry, request))
+}
+
+async fn handle_socket(
+    mut socket: WebSocket,
+    registry: Arc<SessionRegistry>,
+    request: SpectateRequest,
+) {
+    // Subscribed before reading the battle, so that no changes are missed.
+    let mut changes = registry.watch_battles();
+    let started = {
+        let registry = registry.clone();
+        task::spawn_blocking(move || run(|| Spectator::new(&registry, request))).await
+    };
+    let mut spectator = match flatten(started) {
+        Ok((spectator, response)) => {
+            if !send(&mut socket, StreamResponse::Update(poll_response(&request, response))).await {
+                return;
+            }
+            spectator
+        }
+        Err(error) => {
+            send(&mut socket, StreamResponse::Error(error)).await;
+            return;
+        }
+    };
+
+    loop {
+        tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+            change = changes.recv() => match change {
+                Ok(battle_id) if battle_id != request.battle_id => continue,
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            },
+        }
+
+        // Reading the battle may take locks or read save files, so it happens
+        // on a thread where blocking is allowed.
+        let registry = registry.clone();
+        let updated = task::spawn_blocking(move || {
+            let result = run(|| spectator.update(&registry));
+            (spectator, result)
+        })
+        .await;
+        let (message, done) = match updated {
+            Ok((updated, result)) => {
+                spectator = updated;
+                match result {
+                    Ok(None) => continue,
+                    Ok(Some(response)) => {
+                        (StreamResponse::Update(poll_response(&request, response)), false)
+                    }
+                    Err(error) => (StreamResponse::Error(error), true),
+                }
+            }
+            Err(error) => {
+                println!("Error updating spectator: {error}");
+                break;
+            }
+        };
+        if !send(&mut socket, message).await || done {
+            break;
+        }
+    }
+}
+
+/// Runs a spectator operation, converting errors and panics into an error
+/// response.
+fn run<T>(function: impl FnOnce() -> Result<T, RequestError>) -> Result<T, ErrorResponse> {
+    match panic::catch_unwind(AssertUnwindSafe(function)) {
+        Ok(result) => result.map_err(ErrorResponse::from),
+        Err(payload) => Err(ErrorResponse::from_panic(payload.as_ref())),
+    }
+}
+
+/// Converts a failure to run a blocking task into an error response.
+fn flatten<T>(
+    result: Result<Result<T, ErrorResponse>, task::JoinError>,
+) -> Result<T, ErrorResponse> {
+    result.unwrap_or_else(|error| Err(ErrorResponse::new(ErrorType::Internal, error.to_string())))
+}
+
+/// Sends a message to the spectator, returning false if the connection has
+/// been closed.
+async fn send(socket: &mut WebSocket, response: StreamResponse) -> bool {
+    socket.send(websocket::encode_message(WireFormat::Json, &response)).await.is_ok()
+}
+
+/// Wraps a spectator update in the same message used to stream updates to
+/// players, so that clients can display it in the same way.
+fn poll_response(request: &SpectateRequest, response: SpectateResponse) -> PollResponse {
+    PollResponse {
+        metadata: Metadata {
+            user_id: request.user_id,
+            battle_id: Some(request.battle_id),
+            version: Some(response.version),
+        },
+        commands: response.commands,
+    }
+}
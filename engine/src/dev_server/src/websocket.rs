@@ -0,0 +1,287 @@
+use std::future;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use display_data::request_data::{
+    ConnectRequest, ErrorResponse, ErrorType, Metadata, PerformActionRequest, PollResponse,
+    StreamRequest, StreamResponse,
+};
+use display_data::wire_format::{self, WireFormat};
+use engine::battle_patches::ViewTracker;
+use engine::handle_request;
+use engine::request_error::RequestError;
+use engine::session_registry::{BattleUpdate, SessionRegistry};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+use tokio::task;
+
+/// Upgrades a request to a WebSocket connection carrying [StreamRequest]
+/// messages from the client and [StreamResponse] messages to the client.
+///
+/// Messages to the client are sent as text frames containing JSON unless a
+/// binary wire format was selected in the last connect request.
+///
+/// A connection starts streaming updates for a battle once a connect request
+/// has been handled. Clients which lose their connection can resume by opening
+/// a new one and sending a connect request with their previous battle ID. If a
+/// connection falls too far behind on updates, it is sent a new
+/// [StreamResponse::Connect] message with a full snapshot of the battle.
+pub async fn upgrade(
+    State(registry): State<Arc<SessionRegistry>>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| handle_socket(socket, registry))
+}
+
+/// State of a single WebSocket connection.
+#[derive(Default)]
+struct Connection {
+    /// The last connect request handled, used to send a new snapshot of the
+    /// battle if updates are missed.
+    request: Option<ConnectRequest>,
+
+    /// Identifies the battle to stream updates for, set once a connect request
+    /// has been handled. The version is that of the snapshot sent in response
+    /// to the connect request.
+    metadata: Option<Metadata>,
+
+    /// Updates for the connected user's battles.
+    updates: Option<Receiver<BattleUpdate>>,
+
+    /// Battle view most recently sent on this connection.
+    view: ViewTracker,
+
+    /// Encoding for messages sent to the client, as selected in the last
+    /// connect request.
+    format: WireFormat,
+}
+
+async fn handle_socket(mut socket: WebSocket, registry: Arc<SessionRegistry>) {
+    let mut connection = Connection::default();
+    loop {
+        let request = tokio::select! {
+            message = socket.recv() => {
+                let (format, bytes) = match message {
+                    Some(Ok(Message::Text(text))) => (WireFormat::Json, Bytes::from(text)),
+                    Some(Ok(Message::Binary(bytes))) => (WireFormat::MessagePack, bytes),
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(error)) => {
+                        println!("WebSocket error: {error}");
+                        break;
+                    }
+                };
+                match wire_format::decode::<StreamRequest>(format, &bytes) {
+                    Ok(request) => request,
+                    Err(error) => {
+                        let error = ErrorResponse::new(ErrorType::InvalidRequest, error.to_string());
+                        if !send(&mut socket, connection.format, StreamResponse::Error(error)).await {
+                            break;
+                        }
+                        continue;
+                    }
+                }
+            },
+            update = next_update(&mut connection.updates) => {
+                match stream_update(&mut connection, update) {
+                    Ok(Some(response)) => {
+                        if !send(&mut socket, connection.format, response).await {
+                            break;
+                        }
+                        continue;
+                    }
+                    Ok(None) => continue,
+                    Err(request) => StreamRequest::Connect(request),
+                }
+            },
+        };
+
+        // Requests take locks and write save files, so they are handled on a
+        // thread where blocking is allowed.
+        let registry = registry.clone();
+        let handled = task::spawn_blocking(move || {
+            let response = panic::catch_unwind(AssertUnwindSafe(|| {
+                handle_stream_request(&registry, &mut connection, request)
+            }))
+            .unwrap_or_else(|payload| {
+                StreamResponse::Error(ErrorResponse::from_panic(payload.as_ref()))
+            });
+            (connection, response)
+        })
+        .await;
+        let response = match handled {
+            Ok((handled, response)) => {
+                connection = handled;
+                response
+            }
+            Err(error) => {
+                println!("Error handling stream request: {error}");
+                break;
+            }
+        };
+        if !send(&mut socket, connection.format, response).await {
+            break;
+        }
+    }
+}
+
+/// Sends a message to the client, returning false if the connection has been
+/// closed.
+async fn send(socket: &mut WebSocket, format: WireFormat, response: StreamResponse) -> bool {
+    socket.send(encode_message(format, &response)).await.is_ok()
+}
+
+/// Encodes a message to the client in the given wire format.
+///
+/// If the message cannot be encoded, returns an error message describing the
+/// failure instead, or a close message if that cannot be encoded either.
+pub fn encode_message(format: WireFormat, response: &StreamResponse) -> Message {
+    try_encode_message(format, response).unwrap_or_else(|error| {
+        println!("Error encoding response: {error}");
+        let error = StreamResponse::Error(ErrorResponse::new(ErrorType::Internal, error));
+        try_encode_message(format, &error).unwrap_or(Message::Close(None))
+    })
+}
+
+fn try_encode_message(format: WireFormat, response: &StreamResponse) -> Result<Message, String> {
+    let bytes = wire_format::encode(format, response).map_err(|error| error.to_string())?;
+    Ok(match format {
+        WireFormat::Json => {
+            Message::Text(String::from_utf8(bytes).map_err(|error| error.to_string())?.into())
+        }
+        WireFormat::MessagePack => Message::Binary(bytes.into()),
+    })
+}
+
+/// Waits for the next update for the connected user, or forever if the
+/// connection is not streaming updates.
+async fn next_update(
+    updates: &mut Option<Receiver<BattleUpdate>>,
+) -> Result<BattleUpdate, RecvError> {
+    match updates {
+        Some(updates) => updates.recv().await,
+        None => future::pending().await,
+    }
+}
+
+/// Returns a message containing an update for the connection's battle, or
+/// None if the update should not be sent.
+///
+/// Updates for other battles, and updates which are already included in the
+/// snapshot sent on connection, are skipped. Returns the last connect request
+/// as an error if updates have been missed, so that the client can be sent a
+/// new snapshot.
+fn stream_update(
+    connection: &mut Connection,
+    update: Result<BattleUpdate, RecvError>,
+) -> Result<Option<StreamResponse>, ConnectRequest> {
+    let Some(metadata) = connection.metadata else {
+        return Ok(None);
+    };
+    match update {
+        Ok(update) => {
+            let is_new = Some(update.battle_id) == metadata.battle_id
+                && metadata.version.is_none_or(|version| update.version > version);
+            Ok(is_new.then(|| {
+                StreamResponse::Update(PollResponse {
+                    metadata: Metadata { version: Some(update.version), ..metadata },
+                    commands: connection.view.track(update.commands),
+                })
+            }))
+        }
+        Err(RecvError::Lagged(_)) => match &connection.request {
+            Some(request) => Err(ConnectRequest {
+                metadata: Metadata { version: None, ..metadata },
+                ..request.clone()
+            }),
+            None => Ok(None),
+        },
+        Err(RecvError::Closed) => {
+            connection.updates = None;
+            Ok(None)
+        }
+    }
+}
+
+/// Handles a request from the client.
+fn handle_stream_request(
+    registry: &Arc<SessionRegistry>,
+    connection: &mut Connection,
+    request: StreamRequest,
+) -> StreamResponse {
+    println!("Got stream request: {:?}", request);
+    let response = match request {
+        StreamRequest::Connect(request) => connect(registry, connection, request),
+        StreamRequest::PerformAction(request) => perform_action(registry, connection, request),
+        StreamRequest::Resync(metadata) => resync(registry, connection, metadata),
+    };
+    response.unwrap_or_else(|error| StreamResponse::Error(error.into()))
+}
+
+/// Handles a request to perform an action on behalf of the connected user.
+fn perform_action(
+    registry: &Arc<SessionRegistry>,
+    connection: &mut Connection,
+    request: PerformActionRequest,
+) -> Result<StreamResponse, RequestError> {
+    check_connected(connection, &request.metadata)?;
+    handle_request::perform_action_stream(registry, &request, &mut connection.view)
+        .map(StreamResponse::PerformAction)
+}
+
+/// Handles a request for a full snapshot of the connected battle, e.g.
+/// because the client's battle view no longer matches the patches it has
+/// received.
+///
+/// Updates which are older than the snapshot are skipped from then on, as
+/// after a connect request.
+fn resync(
+    registry: &Arc<SessionRegistry>,
+    connection: &mut Connection,
+    metadata: Metadata,
+) -> Result<StreamResponse, RequestError> {
+    check_connected(connection, &metadata)?;
+    let response = handle_request::resync_stream(registry, &metadata, &mut connection.view)?;
+    connection.metadata = Some(response.metadata);
+    Ok(StreamResponse::Update(response))
+}
+
+/// Returns an error if `metadata` names a user other than the one connected
+/// on this stream.
+///
+/// The connection's battle view and updates belong to the user named in the
+/// last connect request, so requests naming any other user are rejected.
+fn check_connected(connection: &Connection, metadata: &Metadata) -> Result<(), RequestError> {
+    let connected = connection.metadata.map(|metadata| metadata.user_id);
+    if connected != Some(metadata.user_id) {
+        return Err(RequestError::InvalidRequest(format!(
+            "User {} is not connected on this stream",
+            metadata.user_id.0
+        )));
+    }
+    Ok(())
+}
+
+/// Handles a connect request, streaming updates for the connected battle from
+/// then on.
+///
+/// The connection subscribes to updates before connecting, so that no updates
+/// are missed. Updates which are older than the returned snapshot are skipped
+/// by [stream_update].
+fn connect(
+    registry: &Arc<SessionRegistry>,
+    connection: &mut Connection,
+    request: ConnectRequest,
+) -> Result<StreamResponse, RequestError> {
+    let updates = registry.subscribe(request.metadata.user_id)?;
+    let response = handle_request::connect_stream(registry, &request, &mut connection.view)?;
+    connection.metadata = Some(response.metadata);
+    connection.format = response.wire_format;
+    connection.updates = Some(updates);
+    connection.request = Some(request);
+    Ok(StreamResponse::Connect(response))
+}
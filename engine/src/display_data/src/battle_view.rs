@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use crate::card_view::CardView;
 
 /// Represents the visual state of an ongoing dream battle
-#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BattleView {
     /// Unique identifier for this dream battle
@@ -30,7 +30,7 @@ pub struct BattleView {
 }
 
 /// User interaction options
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InterfaceView {
     /// Content to display on top of all other game UI.
@@ -41,7 +41,7 @@ pub struct InterfaceView {
 }
 
 /// Button to perform some game action
-#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ButtonView {
     pub label: String,
@@ -72,6 +72,9 @@ pub struct PlayerView {
     /// Energy available to this player
     pub energy: Energy,
 
+    /// Energy this player gains at the start of each of their turns
+    pub produced_energy: Energy,
+
     /// Total spark for this player
     pub total_spark: Spark,
 }
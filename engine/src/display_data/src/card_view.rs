@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use crate::object_position::ObjectPosition;
 
 /// Represents the visual state of a card or ability in a game
-#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CardView {
     /// Identifier for this card
@@ -49,7 +49,7 @@ pub struct CardView {
 }
 
 /// Visual state of a revealed card
-#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RevealedCardView {
     /// Image for this card
@@ -89,14 +89,14 @@ pub struct RevealedCardView {
     pub effects: CardEffects,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DisplayImage {
     /// Image texture address for this card
     pub address: SpriteAddress,
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CardActions {
     /// True if this card can currently be played from hand.
@@ -109,7 +109,7 @@ pub struct CardActions {
     pub on_click: Option<UserAction>,
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CardEffects {
     /// Projectile to display as a trail behind this card.
@@ -6,7 +6,7 @@ use masonry::flex_style::FlexVector3;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::battle_view::BattleView;
+use crate::battle_view::{BattleView, InterfaceView, PlayerView};
 use crate::card_view::CardView;
 use crate::object_position::ObjectPosition;
 
@@ -53,10 +53,12 @@ pub struct ParallelCommandGroup {
 }
 
 /// Represents an animated update to the visual state of the game.
+#[allow(clippy::large_enum_variant)] // Commands are built once and serialized immediately
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum Command {
     UpdateBattle(UpdateBattleCommand),
+    PatchBattle(PatchBattleCommand),
     Wait(Milliseconds),
     FireProjectile(FireProjectileCommand),
     DissolveCard(DissolveCardCommand),
@@ -74,14 +76,59 @@ pub struct UpdateBattleCommand {
 
     /// Sound to play when the battle is updated.
     pub update_sound: Option<AudioClipAddress>,
+
+    /// Revision of the client's battle view after this update, used to
+    /// detect whether a subsequent [PatchBattleCommand] applies to it.
+    ///
+    /// View revisions count the battle updates sent to one client and are
+    /// unrelated to the battle version in the request metadata.
+    pub view_revision: u64,
 }
 
 impl UpdateBattleCommand {
     pub fn new(battle: BattleView) -> Self {
-        Self { battle, update_sound: None }
+        Self { battle, update_sound: None, view_revision: 0 }
     }
 }
 
+/// Updates the visual state of the battle by applying changes to the battle
+/// view from a previous [UpdateBattleCommand] or [PatchBattleCommand].
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchBattleCommand {
+    /// Revision of the battle view this patch applies to.
+    ///
+    /// A client whose battle view has a different revision is out of sync and
+    /// should send a poll request with `resync` set to receive a full
+    /// snapshot.
+    pub base_view_revision: u64,
+
+    /// Revision of the battle view after applying this patch.
+    pub view_revision: u64,
+
+    /// New state of the user, if changed.
+    pub user: Option<PlayerView>,
+
+    /// New state of the enemy, if changed.
+    pub enemy: Option<PlayerView>,
+
+    /// Cards which were added or changed. Replaces any existing card with the
+    /// same ID.
+    pub cards: Vec<CardView>,
+
+    /// Cards which were removed from the battle view.
+    pub removed_cards: Vec<CardId>,
+
+    /// New status description, if changed.
+    pub status_description: Option<String>,
+
+    /// New interface state, if changed.
+    pub interface: Option<InterfaceView>,
+
+    /// Sound to play when the battle is updated.
+    pub update_sound: Option<AudioClipAddress>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FireProjectileCommand {
@@ -3,3 +3,4 @@ pub mod card_view;
 pub mod command;
 pub mod object_position;
 pub mod request_data;
+pub mod wire_format;
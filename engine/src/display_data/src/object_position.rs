@@ -66,6 +66,12 @@ pub enum Position {
     /// Object is in a player's status zone
     InPlayerStatus(PlayerName),
 
+    /// Object is in a player's dreamwell, waiting to be revealed
+    InDreamwell(PlayerName),
+
+    /// Dreamwell card which was revealed to produce energy this turn
+    DreamwellActivation,
+
     /// Object is being displayed in a card browser, e.g. to select from a list
     /// of cards while searching
     Browser,
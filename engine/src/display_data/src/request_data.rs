@@ -1,21 +1,53 @@
+use std::any::Any;
+
 use action_data::user_action::UserAction;
 use core_data::identifiers::{BattleId, UserId};
+use core_data::types::Seat;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::command::CommandSequence;
+use crate::wire_format::WireFormat;
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Metadata {
     pub user_id: UserId,
     pub battle_id: Option<BattleId>,
+
+    /// Version of the battle state last observed by the client.
+    ///
+    /// Responses contain the current version of the battle, including poll
+    /// responses and streamed updates. Actions which specify a different
+    /// version are not performed, and the client is instead sent the current
+    /// state of the battle. Actions which only change what the user is looking
+    /// at, such as browsing cards, do not change the version.
+    #[serde(default)]
+    pub version: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ConnectRequest {
     pub metadata: Metadata,
+
+    /// Encoding to use for the response to this request and for all
+    /// subsequent messages. Defaults to JSON.
+    #[serde(default)]
+    pub wire_format: WireFormat,
+
+    /// If true, battle updates after the initial snapshot are sent as
+    /// `PatchBattle` commands containing only the changes to the battle view.
+    #[serde(default)]
+    pub battle_patches: bool,
+
+    /// User to play against instead of the AI.
+    ///
+    /// If the opponent has started a battle against this user, this user
+    /// joins it. Otherwise a new battle is started, which the opponent can
+    /// join by connecting with this user as their opponent.
+    #[serde(default)]
+    pub opponent: Option<UserId>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
@@ -23,6 +55,9 @@ pub struct ConnectRequest {
 pub struct ConnectResponse {
     pub metadata: Metadata,
     pub commands: CommandSequence,
+
+    /// Encoding which will be used for all subsequent messages.
+    pub wire_format: WireFormat,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
@@ -43,6 +78,15 @@ pub struct PerformActionResponse {
 #[serde(rename_all = "camelCase")]
 pub struct PollRequest {
     pub metadata: Metadata,
+
+    /// If true, the response contains a full snapshot of the battle in place
+    /// of any queued updates, which are discarded.
+    ///
+    /// Clients should set this when their battle view is out of sync, e.g.
+    /// because they received a `PatchBattle` command whose base view revision
+    /// does not match their view.
+    #[serde(default)]
+    pub resync: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
@@ -52,6 +96,146 @@ pub struct PollResponse {
     pub commands: CommandSequence,
 }
 
+/// Summary of a battle which can be watched by a spectator.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BattleSummary {
+    pub user_id: UserId,
+    pub battle_id: BattleId,
+    pub version: u64,
+    pub turn_number: u32,
+    pub active_player: Seat,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBattlesResponse {
+    pub battles: Vec<BattleSummary>,
+}
+
+/// Request to watch a battle without participating in it.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpectateRequest {
+    /// User playing the battle
+    pub user_id: UserId,
+    pub battle_id: BattleId,
+
+    /// Seat whose view of the battle is displayed. Information hidden from
+    /// the player in this seat is hidden from the spectator.
+    pub perspective: Seat,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpectateResponse {
+    /// Current version of the battle
+    pub version: u64,
+
+    /// Commands to display the current state of the battle.
+    pub commands: CommandSequence,
+}
+
+/// Request to replay one turn of a battle.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayRequest {
+    /// User playing the battle
+    pub user_id: UserId,
+    pub battle_id: BattleId,
+
+    /// Seat whose view of the battle is displayed.
+    pub perspective: Seat,
+
+    /// Turn to replay.
+    pub turn_number: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayResponse {
+    /// Turn which was replayed.
+    pub turn_number: u32,
+
+    /// Most recent turn of the battle, which can be replayed up to the
+    /// current state of the battle.
+    pub last_turn_number: u32,
+
+    /// Commands to display the state of the battle at the start of the turn,
+    /// followed by each action taken during the turn.
+    pub commands: CommandSequence,
+}
+
+/// Describes why a request could not be handled.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorResponse {
+    pub error_type: ErrorType,
+    pub message: String,
+}
+
+impl ErrorResponse {
+    pub fn new(error_type: ErrorType, message: impl Into<String>) -> Self {
+        Self { error_type, message: message.into() }
+    }
+
+    /// Creates an [ErrorType::Internal] response describing a caught panic.
+    pub fn from_panic(payload: &(dyn Any + Send)) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Unknown error");
+        Self::new(ErrorType::Internal, format!("Panic: {message}"))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorType {
+    /// The request could not be deserialized.
+    InvalidRequest,
+
+    /// The request metadata did not include a battle ID.
+    MissingBattleId,
+
+    /// The battle referenced by the request no longer exists, e.g. because
+    /// the client's state is stale and the battle has been replaced.
+    UnknownBattle,
+
+    /// The requested action cannot be performed in the current battle state.
+    IllegalAction,
+
+    /// An unexpected error occurred while handling the request.
+    Internal,
+}
+
+/// A request sent by the client over a streaming connection.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum StreamRequest {
+    Connect(ConnectRequest),
+    PerformAction(PerformActionRequest),
+
+    /// Requests a full snapshot of the connected battle, which is sent as an
+    /// `Update` message. See [PollRequest::resync].
+    Resync(Metadata),
+}
+
+/// A message sent to the client over a streaming connection.
+///
+/// Each request produces exactly one response message. Updates are sent
+/// whenever commands are produced outside of a user request, such as enemy
+/// actions.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum StreamResponse {
+    Connect(ConnectResponse),
+    PerformAction(PerformActionResponse),
+    Update(PollResponse),
+    Error(ErrorResponse),
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SchemaTypes {
@@ -61,4 +245,12 @@ pub struct SchemaTypes {
     pub perform_action_response: PerformActionResponse,
     pub poll_request: PollRequest,
     pub poll_response: PollResponse,
+    pub stream_request: StreamRequest,
+    pub stream_response: StreamResponse,
+    pub error_response: ErrorResponse,
+    pub list_battles_response: ListBattlesResponse,
+    pub spectate_request: SpectateRequest,
+    pub spectate_response: SpectateResponse,
+    pub replay_request: ReplayRequest,
+    pub replay_response: ReplayResponse,
 }
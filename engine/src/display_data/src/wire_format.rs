@@ -0,0 +1,94 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Encoding used for request and response messages exchanged with the client.
+///
+/// The format is chosen by the client in its `ConnectRequest`. Connect
+/// requests themselves are always sent as JSON.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum WireFormat {
+    /// Human-readable JSON, useful for debugging.
+    #[default]
+    Json,
+
+    /// Compact binary MessagePack encoding.
+    ///
+    /// Messages have the same structure as their JSON equivalents, with
+    /// structs encoded as maps keyed by field name.
+    MessagePack,
+}
+
+impl WireFormat {
+    /// HTTP content type for messages in this format.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            WireFormat::Json => "application/json",
+            WireFormat::MessagePack => "application/msgpack",
+        }
+    }
+
+    /// Returns the format with the given HTTP content type, if any.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        [WireFormat::Json, WireFormat::MessagePack]
+            .into_iter()
+            .find(|format| content_type.starts_with(format.content_type()))
+    }
+}
+
+/// Error encoding or decoding a message.
+#[derive(Debug)]
+pub enum WireFormatError {
+    Json(serde_json::Error),
+    Encode(rmp_serde::encode::Error),
+    Decode(rmp_serde::decode::Error),
+}
+
+impl Display for WireFormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(error) => write!(f, "{error}"),
+            Self::Encode(error) => write!(f, "{error}"),
+            Self::Decode(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for WireFormatError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Json(error) => Some(error),
+            Self::Encode(error) => Some(error),
+            Self::Decode(error) => Some(error),
+        }
+    }
+}
+
+/// Serializes a message in the given format.
+pub fn encode(format: WireFormat, value: &impl Serialize) -> Result<Vec<u8>, WireFormatError> {
+    match format {
+        WireFormat::Json => serde_json::to_vec(value).map_err(WireFormatError::Json),
+        WireFormat::MessagePack => {
+            let mut result = vec![];
+            let mut serializer =
+                rmp_serde::Serializer::new(&mut result).with_struct_map().with_human_readable();
+            value.serialize(&mut serializer).map_err(WireFormatError::Encode)?;
+            Ok(result)
+        }
+    }
+}
+
+/// Deserializes a message in the given format.
+pub fn decode<T: DeserializeOwned>(format: WireFormat, bytes: &[u8]) -> Result<T, WireFormatError> {
+    match format {
+        WireFormat::Json => serde_json::from_slice(bytes).map_err(WireFormatError::Json),
+        WireFormat::MessagePack => {
+            let mut deserializer = rmp_serde::Deserializer::new(bytes).with_human_readable();
+            T::deserialize(&mut deserializer).map_err(WireFormatError::Decode)
+        }
+    }
+}
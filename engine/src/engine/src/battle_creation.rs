@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use battle_data::battle_card::Zone;
+use battle_data::battle_data::BattleData;
+use card_data::card_name::CardName;
+use core_data::identifiers::BattleId;
+use core_data::types::Seat;
+use deck_data::deck::Deck;
+use deck_data::deck_rules::{DeckRuleViolation, DeckRules};
+use deck_data::decklist::{self, DecklistError};
+use quest_data::quest_data::QuestData;
+
+use crate::effects::EffectError;
+use crate::mutations;
+
+/// Number of cards each player draws at the start of a battle.
+pub const STARTING_HAND_SIZE: usize = 5;
+
+/// Cards in each player's dreamwell, in the order they are revealed.
+pub const DREAMWELL: [CardName; 5] = [
+    CardName::Skypath,
+    CardName::AutumnGlade,
+    CardName::TwilightRadiance,
+    CardName::AstralInterface,
+    CardName::MidnightGathering,
+];
+
+/// Reasons a battle could not be started from a pair of decklists.
+#[derive(Debug, Clone)]
+pub enum BattleCreationError {
+    /// The decklist for the given player could not be read.
+    InvalidDecklist(Seat, DecklistError),
+
+    /// The deck for the given player does not satisfy the deck-building rules.
+    IllegalDeck(Seat, Vec<DeckRuleViolation>),
+
+    /// An ability applied at the start of the battle cannot yet be applied.
+    UnsupportedEffect(EffectError),
+}
+
+impl Display for BattleCreationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDecklist(player, error) => write!(f, "{player:?} decklist: {error}"),
+            Self::IllegalDeck(player, violations) => {
+                write!(f, "{player:?} deck is illegal: ")?;
+                let messages = violations.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                write!(f, "{}", messages.join("; "))
+            }
+            Self::UnsupportedEffect(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for BattleCreationError {}
+
+/// Starts a new battle between two decks.
+///
+/// Each player's deck is shuffled and they draw their opening hand, then the
+/// user's first turn begins. The shuffle is seeded from `seed`, so the same
+/// seed always produces the same starting state. The seed determines hidden
+/// information and must not be derived from the battle ID or anything else
+/// which is sent to clients.
+///
+/// Returns an error if an ability applied at the start of the user's first
+/// turn cannot be applied, see [mutations::start_turn].
+pub fn create(
+    id: BattleId,
+    seed: u64,
+    user_deck: &Deck,
+    enemy_deck: &Deck,
+) -> Result<BattleData, EffectError> {
+    let mut battle = new_battle(id, seed, user_deck, enemy_deck);
+    mutations::start_turn(&mut battle, Seat::Host)?;
+    Ok(battle)
+}
+
+/// Starts a new battle for a quest, using the quest deck for the user.
+///
+/// The `enemy` card and each of the user's dreamsigns are placed in their
+/// owner's status zone, where their static abilities apply for the rest of the
+/// battle. The battle is seeded from `seed` and errors are returned as
+/// described in [create].
+pub fn for_quest(
+    id: BattleId,
+    seed: u64,
+    quest: &QuestData,
+    enemy: CardName,
+    enemy_deck: &Deck,
+) -> Result<BattleData, EffectError> {
+    let mut battle = new_battle(id, seed, &quest.deck, enemy_deck);
+    battle.create_card(enemy, Seat::Guest, Zone::Status);
+    for &dreamsign in &quest.dreamsigns {
+        battle.create_card(dreamsign, Seat::Host, Zone::Status);
+    }
+    mutations::start_turn(&mut battle, Seat::Host)?;
+    Ok(battle)
+}
+
+/// Starts a new battle from two decklists in the format described in
+/// [decklist], checking both decks against the provided [DeckRules]. The
+/// battle is seeded from `seed` as described in [create].
+pub fn from_decklists(
+    id: BattleId,
+    seed: u64,
+    user_decklist: &str,
+    enemy_decklist: &str,
+    rules: &DeckRules,
+) -> Result<BattleData, BattleCreationError> {
+    let user_deck = checked_deck(Seat::Host, user_decklist, rules)?;
+    let enemy_deck = checked_deck(Seat::Guest, enemy_decklist, rules)?;
+    create(id, seed, &user_deck, &enemy_deck).map_err(BattleCreationError::UnsupportedEffect)
+}
+
+fn new_battle(id: BattleId, seed: u64, user_deck: &Deck, enemy_deck: &Deck) -> BattleData {
+    let mut battle = BattleData::new(id, seed);
+    for (player, deck) in [(Seat::Host, user_deck), (Seat::Guest, enemy_deck)] {
+        for name in deck.cards() {
+            battle.create_card(name, player, Zone::Deck);
+        }
+        for name in DREAMWELL {
+            battle.create_card(name, player, Zone::Dreamwell);
+        }
+        battle.shuffle_deck(player);
+        mutations::draw_cards(&mut battle, player, STARTING_HAND_SIZE);
+    }
+    battle
+}
+
+fn checked_deck(player: Seat, text: &str, rules: &DeckRules) -> Result<Deck, BattleCreationError> {
+    let deck = decklist::parse(text)
+        .map_err(|error| BattleCreationError::InvalidDecklist(player, error))?;
+    let violations = rules.validate(&deck);
+    if violations.is_empty() {
+        Ok(deck)
+    } else {
+        Err(BattleCreationError::IllegalDeck(player, violations))
+    }
+}
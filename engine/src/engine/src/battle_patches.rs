@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use display_data::battle_view::BattleView;
+use display_data::command::{Command, CommandSequence, PatchBattleCommand, UpdateBattleCommand};
+
+/// Tracks the battle view most recently sent to a client, so that subsequent
+/// battle updates can be sent as patches.
+#[derive(Debug, Default)]
+pub struct ViewTracker {
+    /// Whether the client has requested updates to be sent as patches.
+    enabled: bool,
+
+    /// Revision of the most recently sent battle view. Revisions increase
+    /// monotonically and are never reused, even after a reset.
+    revision: u64,
+
+    /// The most recently sent battle view, if the client is known to have
+    /// one.
+    view: Option<BattleView>,
+}
+
+impl ViewTracker {
+    /// Forgets the client's current battle view, so the next battle update is
+    /// sent as a full snapshot.
+    ///
+    /// If `enabled` is provided, it controls whether subsequent updates are
+    /// sent as patches.
+    pub fn reset(&mut self, enabled: Option<bool>) {
+        if let Some(enabled) = enabled {
+            self.enabled = enabled;
+        }
+        self.view = None;
+    }
+
+    /// Assigns view revisions to the battle updates in `commands`, replacing
+    /// each update with a [PatchBattleCommand] relative to the previous
+    /// update if patches are enabled.
+    pub fn track(&mut self, mut commands: CommandSequence) -> CommandSequence {
+        for command in commands.groups.iter_mut().flat_map(|group| group.commands.iter_mut()) {
+            if let Command::UpdateBattle(update) = command {
+                self.revision += 1;
+                update.view_revision = self.revision;
+                if let Some(patch) = self.patch(update) {
+                    *command = Command::PatchBattle(patch);
+                }
+            }
+        }
+        commands
+    }
+
+    /// Records `update` as the client's current battle view, returning a patch
+    /// to apply it instead if possible.
+    fn patch(&mut self, update: &UpdateBattleCommand) -> Option<PatchBattleCommand> {
+        let previous = self.view.replace(update.battle.clone());
+        let previous =
+            previous.filter(|previous| self.enabled && previous.id == update.battle.id)?;
+        let mut patch = diff(&previous, &update.battle);
+        patch.base_view_revision = update.view_revision - 1;
+        patch.view_revision = update.view_revision;
+        patch.update_sound = update.update_sound.clone();
+        Some(patch)
+    }
+}
+
+/// Returns a patch describing the changes from `old` to `new`.
+///
+/// View revisions are left unset.
+pub fn diff(old: &BattleView, new: &BattleView) -> PatchBattleCommand {
+    let old_cards = old.cards.iter().map(|card| (card.id, card)).collect::<BTreeMap<_, _>>();
+    let new_cards = new.cards.iter().map(|card| (card.id, card)).collect::<BTreeMap<_, _>>();
+    PatchBattleCommand {
+        base_view_revision: 0,
+        view_revision: 0,
+        user: (old.user != new.user).then(|| new.user.clone()),
+        enemy: (old.enemy != new.enemy).then(|| new.enemy.clone()),
+        cards: new
+            .cards
+            .iter()
+            .filter(|card| old_cards.get(&card.id) != Some(card))
+            .cloned()
+            .collect(),
+        removed_cards: old
+            .cards
+            .iter()
+            .map(|card| card.id)
+            .filter(|id| !new_cards.contains_key(id))
+            .collect(),
+        status_description: (old.status_description != new.status_description)
+            .then(|| new.status_description.clone()),
+        interface: (old.interface != new.interface).then(|| new.interface.clone()),
+        update_sound: None,
+    }
+}
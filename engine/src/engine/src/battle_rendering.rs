@@ -0,0 +1,114 @@
+use battle_data::battle_card::Zone;
+use battle_data::battle_data::{BattleData, BattlePrompt};
+use core_data::display_types::DisplayColor;
+use core_data::types::Seat;
+use display_data::battle_view::{BattleView, InterfaceView, PlayerView};
+use masonry::flex_enums::{FlexPosition, TextAlign, WhiteSpace};
+use masonry::flex_node::{FlexNode, NodeType, Text};
+use masonry::flex_style::{
+    BorderRadius, Dimension, DimensionGroup, DimensionUnit, FlexInsets, FlexStyle,
+};
+
+use crate::{card_rendering, legal_actions};
+
+/// Renders the visual state of a battle, as seen by the user in the `viewer`
+/// seat.
+///
+/// Seats are converted to players so that the viewer sees themselves as
+/// `PlayerName::User`. Information which is hidden from the viewer, such as
+/// the identities of cards in their opponent's hand, is omitted.
+///
+/// Cards are listed in sorting key order rather than in the order they were
+/// created, since decks are created in name order and the index of a hidden
+/// card would otherwise reveal its identity.
+pub fn battle_view(battle: &BattleData, viewer: Seat) -> BattleView {
+    let mut cards = battle.cards.values().collect::<Vec<_>>();
+    cards.sort_by_key(|card| card.sorting_key);
+    BattleView {
+        id: battle.id,
+        user: player_view(battle, viewer),
+        enemy: player_view(battle, viewer.opponent()),
+        cards: cards
+            .into_iter()
+            .map(|card| card_rendering::card_view(battle, card, viewer))
+            .collect(),
+        status_description: "Status".to_string(),
+        interface: interface_view(battle, viewer),
+    }
+}
+
+fn player_view(battle: &BattleData, player: Seat) -> PlayerView {
+    let data = battle.player(player);
+    PlayerView {
+        score: data.points,
+        can_act: false,
+        energy: data.energy,
+        produced_energy: data.produced_energy,
+        total_spark: battle
+            .cards_in_zone(player, Zone::Battlefield)
+            .iter()
+            .filter_map(|card| card.definition().spark)
+            .sum(),
+    }
+}
+
+fn interface_view(battle: &BattleData, viewer: Seat) -> InterfaceView {
+    if let Some(BattlePrompt::SelectTarget { source, .. }) = &battle.prompt {
+        let choosing = battle.card(*source).is_some_and(|card| card.owner == viewer);
+        InterfaceView {
+            screen_overlay: choosing.then(select_target_message),
+            primary_action_button: None,
+        }
+    } else {
+        InterfaceView {
+            screen_overlay: None,
+            primary_action_button: legal_actions::can_end_turn(battle, viewer)
+                .then(|| "End Turn".to_string()),
+        }
+    }
+}
+
+fn select_target_message() -> FlexNode {
+    let style = FlexStyle {
+        background_color: Some(DisplayColor { red: 0.0, green: 0.0, blue: 0.0, alpha: 0.95 }),
+        border_radius: Some(BorderRadius {
+            top_left: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+            top_right: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+            bottom_right: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+            bottom_left: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+        }),
+        padding: Some(DimensionGroup {
+            top: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+            right: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+            bottom: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+            left: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+        }),
+        color: Some(DisplayColor { red: 1.0, green: 1.0, blue: 1.0, alpha: 1.0 }),
+        font_size: Some(Dimension { unit: DimensionUnit::Pixels, value: 10.0 }),
+        min_height: Some(Dimension { unit: DimensionUnit::Pixels, value: 22.0 }),
+        white_space: Some(WhiteSpace::Normal),
+        text_align: Some(TextAlign::MiddleCenter),
+        ..Default::default()
+    };
+
+    let message = FlexNode {
+        node_type: Some(NodeType::Text(Text { label: "Choose an enemy character".into() })),
+        style: Some(style),
+        ..Default::default()
+    };
+
+    FlexNode {
+        style: Some(FlexStyle {
+            position: Some(FlexPosition::Absolute),
+            inset: Some(FlexInsets {
+                top: None,
+                right: Some(Dimension { unit: DimensionUnit::Pixels, value: 32.0 }),
+                bottom: Some(Dimension { unit: DimensionUnit::Pixels, value: 50.0 }),
+                left: Some(Dimension { unit: DimensionUnit::Pixels, value: 32.0 }),
+            }),
+            ..Default::default()
+        }),
+        children: vec![message],
+        ..Default::default()
+    }
+}
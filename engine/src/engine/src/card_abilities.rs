@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use ability_data::ability::Ability;
+use card_data::card_definitions;
+use card_data::card_name::CardName;
+use parser::ability_parser;
+
+static ABILITIES: LazyLock<HashMap<CardName, Vec<Ability>>> = LazyLock::new(|| {
+    card_definitions::all()
+        .map(|definition| {
+            let abilities = definition.ability_text.map(parse).unwrap_or_default();
+            (definition.name, abilities)
+        })
+        .collect()
+});
+
+/// Returns the parsed abilities of the card with the given name.
+///
+/// Abilities are parsed from each card's `ability_text` the first time this
+/// function is called.
+///
+/// Panics if any card's ability text cannot be parsed. Card definitions are
+/// fixed at compile time and every card's text is checked by the parser tests,
+/// so this indicates a card definition which was changed without running them.
+pub fn get(name: CardName) -> &'static [Ability] {
+    ABILITIES.get(&name).map(Vec::as_slice).unwrap_or_default()
+}
+
+fn parse(text: &str) -> Vec<Ability> {
+    ability_parser::parse(text)
+        .into_result()
+        .unwrap_or_else(|errors| panic!("Cannot parse ability text '{text}': {errors:?}"))
+}
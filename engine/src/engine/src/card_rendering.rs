@@ -0,0 +1,261 @@
+use std::hash::Hasher;
+
+use ability_data::keyword;
+use action_data::battle_action::{BattleAction, CardBrowserType};
+use action_data::user_action::UserAction;
+use battle_data::battle_card::{BattleCard, Zone};
+use battle_data::battle_data::{BattleData, BattlePrompt};
+use card_data::card_definition::{CardDefinition, CardType};
+use core_data::display_types::{DisplayColor, SpriteAddress, Url};
+use core_data::identifiers::CardId;
+use core_data::types::{CardFacing, PlayerName, Seat};
+use display_data::card_view::{
+    CardActions, CardEffects, CardFrame, CardPrefab, CardView, DisplayImage, RevealedCardStatus,
+    RevealedCardView,
+};
+use display_data::object_position::{ObjectPosition, Position};
+use masonry::flex_enums::WhiteSpace;
+use masonry::flex_node::{FlexNode, NodeType, Text};
+use masonry::flex_style::{BorderRadius, Dimension, DimensionGroup, DimensionUnit, FlexStyle};
+use siphasher::sip::SipHasher13;
+
+use crate::{card_abilities, legal_actions, queries};
+
+/// Second half of the key used to derive opaque identifiers for hidden cards,
+/// see [opaque_id]. The first half is the battle's secret seed.
+const OPAQUE_ID_KEY: u64 = 0x6472_6561_6d63_616c;
+
+/// Renders the visual state of a card in a battle, as seen by the user in the
+/// `viewer` seat.
+///
+/// Seats are converted to players so that the viewer sees themselves as
+/// [PlayerName::User].
+///
+/// Cards in zones which are hidden from the viewer are rendered face down
+/// with an opaque identifier in place of their real ID, so that the client
+/// cannot follow a hidden card between zones or learn the order of a deck.
+/// Opaque identifiers are stable while a card remains in the same position
+/// and change whenever it moves.
+pub fn card_view(battle: &BattleData, card: &BattleCard, viewer: Seat) -> CardView {
+    let revealed = is_revealed(battle, card, viewer);
+    CardView {
+        id: if revealed { card.id } else { opaque_id(battle, card, viewer) },
+        position: position(battle, card, viewer),
+        card_back: Url::new("".to_string()),
+        revealed: revealed.then(|| revealed_card_view(battle, card, viewer)),
+        revealed_to_opponents: card.zone.is_hidden()
+            && card.revealed_to.contains(&card.owner.opponent()),
+        card_facing: if revealed { CardFacing::FaceUp } else { CardFacing::FaceDown },
+        create_position: revealed.then(|| create_position(card, viewer)).flatten(),
+        destroy_position: None,
+        prefab: prefab(card.definition()),
+    }
+}
+
+/// Returns the owner and zone of cards shown by the card browser of the user
+/// in the `viewer` seat.
+pub fn browser_source(browser: CardBrowserType, viewer: Seat) -> (Seat, Zone) {
+    let (player, zone) = match browser {
+        CardBrowserType::UserDeck => (PlayerName::User, Zone::Deck),
+        CardBrowserType::EnemyDeck => (PlayerName::Enemy, Zone::Deck),
+        CardBrowserType::UserVoid => (PlayerName::User, Zone::Void),
+        CardBrowserType::EnemyVoid => (PlayerName::Enemy, Zone::Void),
+        CardBrowserType::UserStatus => (PlayerName::User, Zone::Status),
+        CardBrowserType::EnemyStatus => (PlayerName::Enemy, Zone::Status),
+    };
+    (Seat::of_player(player, viewer), zone)
+}
+
+/// Builds a tooltip-style text box, e.g. for describing keywords on a card.
+pub fn supplemental_info(text: impl Into<String>) -> FlexNode {
+    let style = FlexStyle {
+        background_color: Some(DisplayColor { red: 0.0, green: 0.0, blue: 0.0, alpha: 0.95 }),
+        border_radius: Some(BorderRadius {
+            top_left: Dimension { unit: DimensionUnit::Pixels, value: 2.0 },
+            top_right: Dimension { unit: DimensionUnit::Pixels, value: 2.0 },
+            bottom_right: Dimension { unit: DimensionUnit::Pixels, value: 2.0 },
+            bottom_left: Dimension { unit: DimensionUnit::Pixels, value: 2.0 },
+        }),
+        padding: Some(DimensionGroup {
+            top: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+            right: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+            bottom: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+            left: Dimension { unit: DimensionUnit::Pixels, value: 4.0 },
+        }),
+        color: Some(DisplayColor { red: 1.0, green: 1.0, blue: 1.0, alpha: 1.0 }),
+        font_size: Some(Dimension { unit: DimensionUnit::Pixels, value: 5.0 }),
+        white_space: Some(WhiteSpace::Normal),
+        ..Default::default()
+    };
+    FlexNode {
+        node_type: Some(NodeType::Text(Text { label: text.into() })),
+        style: Some(style),
+        ..Default::default()
+    }
+}
+
+/// Returns true if `card` is currently displayed in the `viewer`'s card
+/// browser.
+fn is_browsing(battle: &BattleData, card: &BattleCard, viewer: Seat) -> bool {
+    battle
+        .player(viewer)
+        .card_browser
+        .is_some_and(|browser| browser_source(browser, viewer) == (card.owner, card.zone))
+}
+
+fn is_revealed(battle: &BattleData, card: &BattleCard, viewer: Seat) -> bool {
+    card.revealed_to.contains(&viewer)
+        || match card.zone {
+            Zone::Deck => card.owner == viewer && is_browsing(battle, card, viewer),
+            Zone::Hand => card.owner == viewer,
+            Zone::Dreamwell => battle.turn.dreamwell_card == Some(card.id),
+            _ => true,
+        }
+}
+
+/// Returns an identifier to display for a card which is hidden from the
+/// `viewer`.
+///
+/// The identifier is a keyed hash of the card's current sorting key, using
+/// the battle's secret seed as the key, so it cannot be linked to the card's
+/// real ID and changes whenever the card moves. SipHash is used with explicit
+/// inputs so that identifiers do not change between builds. The high bit is
+/// always set so that it never collides with a real card ID.
+fn opaque_id(battle: &BattleData, card: &BattleCard, viewer: Seat) -> CardId {
+    let mut hasher = SipHasher13::new_with_keys(battle.hidden_card_seed, OPAQUE_ID_KEY);
+    hasher.write_u8(match viewer {
+        Seat::Host => 0,
+        Seat::Guest => 1,
+    });
+    hasher.write_u64(card.id.to_int());
+    hasher.write_u32(card.sorting_key);
+    CardId::from_int(hasher.finish() | (1 << 63))
+}
+
+/// Position at which to create a card which has just been revealed to the
+/// `viewer`, because its last move was out of a zone hidden from them.
+///
+/// Such cards are displayed with their real ID for the first time, e.g.
+/// after being drawn or played from an opponent's hand, so the client has
+/// not seen them before. Returns None for all other cards.
+fn create_position(card: &BattleCard, viewer: Seat) -> Option<ObjectPosition> {
+    let owner = card.owner.player_name(viewer);
+    let position = match card.previous_zone? {
+        Zone::Deck => Position::InDeck(owner),
+        Zone::Hand if card.owner != viewer => Position::InHand(owner),
+        _ => return None,
+    };
+    Some(ObjectPosition { position, sorting_key: card.sorting_key, sorting_sub_key: 0 })
+}
+
+fn position(battle: &BattleData, card: &BattleCard, viewer: Seat) -> ObjectPosition {
+    if is_browsing(battle, card, viewer) {
+        return ObjectPosition {
+            position: Position::Browser,
+            sorting_key: browser_sorting_key(battle, card),
+            sorting_sub_key: 0,
+        };
+    }
+
+    let owner = card.owner.player_name(viewer);
+    let position = match (card.zone, &battle.prompt) {
+        (Zone::Stack, Some(BattlePrompt::SelectTarget { source, target_controller }))
+            if *source == card.id =>
+        {
+            Position::SelectingTargets(target_controller.player_name(viewer))
+        }
+        (Zone::Deck, _) => Position::InDeck(owner),
+        (Zone::Hand, _) => Position::InHand(owner),
+        (Zone::Stack, _) => Position::OnStack,
+        (Zone::Battlefield, _) => Position::OnBattlefield(owner),
+        (Zone::Void, _) => Position::InVoid(owner),
+        (Zone::Banished, _) => Position::InBanished(owner),
+        (Zone::Status, _) => Position::InPlayerStatus(owner),
+        (Zone::Dreamwell, _) if battle.turn.dreamwell_card == Some(card.id) => {
+            Position::DreamwellActivation
+        }
+        (Zone::Dreamwell, _) => Position::InDreamwell(owner),
+    };
+    ObjectPosition { position, sorting_key: card.sorting_key, sorting_sub_key: 0 }
+}
+
+/// Sorting key for a card in the card browser.
+///
+/// Deck cards are displayed in name order so that browsing the deck does not
+/// reveal the order in which cards will be drawn.
+fn browser_sorting_key(battle: &BattleData, card: &BattleCard) -> u32 {
+    if card.zone != Zone::Deck {
+        return card.sorting_key;
+    }
+
+    let mut deck = battle.cards_in_zone(card.owner, Zone::Deck);
+    deck.sort_by_key(|c| (c.name, c.sorting_key));
+    deck.iter().position(|c| c.id == card.id).unwrap_or_default() as u32
+}
+
+fn revealed_card_view(battle: &BattleData, card: &BattleCard, viewer: Seat) -> RevealedCardView {
+    let definition = card.definition();
+    let can_play = legal_actions::can_play(battle, viewer, card);
+    let can_select = legal_actions::can_select(battle, viewer, card);
+    RevealedCardView {
+        image: DisplayImage { address: SpriteAddress::new(definition.image) },
+        name: definition.displayed_name.to_string(),
+        cost: queries::cost(battle, card),
+        spark: definition.spark,
+        card_type: card_type(definition),
+        rules_text: definition.rules_text.to_string(),
+        status: can_select.then_some(RevealedCardStatus::CanSelectNegative),
+        frame: match definition.card_type {
+            CardType::Character => CardFrame::Character,
+            CardType::Event => CardFrame::Event,
+            CardType::Enemy | CardType::Dreamsign | CardType::Dreamwell => CardFrame::Default,
+        },
+        supplemental_card_info: supplemental_card_info(card),
+        is_fast: queries::is_fast(battle, card),
+        actions: CardActions {
+            can_play,
+            on_click: can_select
+                .then_some(UserAction::BattleAction(BattleAction::SelectTarget(card.id))),
+            ..Default::default()
+        },
+        effects: CardEffects::default(),
+    }
+}
+
+/// Describes the keywords used by a card.
+///
+/// Reminder text is generated for each keyword in the card's parsed
+/// abilities, falling back to the card's hand-written
+/// [CardDefinition::supplemental_info] if none are found.
+fn supplemental_card_info(card: &BattleCard) -> Option<FlexNode> {
+    let keywords = keyword::keywords(card_abilities::get(card.name));
+    if keywords.is_empty() {
+        return card.definition().supplemental_info.map(supplemental_info);
+    }
+
+    let lines = keywords
+        .iter()
+        .map(|keyword| format!("<b>{}</b>: {}", keyword.name(), keyword.reminder_text()))
+        .collect::<Vec<_>>();
+    Some(supplemental_info(lines.join("\n")))
+}
+
+fn card_type(definition: &CardDefinition) -> String {
+    match (definition.subtype, definition.card_type) {
+        (Some(subtype), _) => subtype.to_string(),
+        (None, CardType::Character) => "Character".to_string(),
+        (None, CardType::Event) => "Event".to_string(),
+        (None, CardType::Enemy) => "Enemy".to_string(),
+        (None, CardType::Dreamsign) => "Dreamsign".to_string(),
+        (None, CardType::Dreamwell) => "Dreamwell".to_string(),
+    }
+}
+
+fn prefab(definition: &CardDefinition) -> CardPrefab {
+    match definition.card_type {
+        CardType::Character | CardType::Event => CardPrefab::Default,
+        CardType::Enemy => CardPrefab::Enemy,
+        CardType::Dreamsign => CardPrefab::Dreamsign,
+        CardType::Dreamwell => CardPrefab::Dreamwell,
+    }
+}
@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use ability_data::effect::{Effect, EffectWithOptions};
+use ability_data::quantity::Quantity;
+use ability_data::quantity_expression::QuantityExpression;
+use ability_data::standard_effect::StandardEffect;
+use battle_data::battle_card::Zone;
+use battle_data::battle_data::BattleData;
+use core_data::types::Seat;
+
+use crate::{mutations, queries};
+
+/// An effect which cannot yet be applied.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EffectError {
+    /// Modal effects require the player to choose modes, which is not yet
+    /// implemented.
+    ModalEffect,
+
+    /// An amount written as "X" is defined by a quantity which cannot yet be
+    /// evaluated.
+    UnsupportedQuantity(QuantityExpression),
+}
+
+impl Display for EffectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ModalEffect => write!(f, "Modal effects are not yet implemented"),
+            Self::UnsupportedQuantity(x) => write!(f, "Cannot evaluate X for quantity {x:?}"),
+        }
+    }
+}
+
+impl Error for EffectError {}
+
+/// Applies an effect controlled by the `controller` player.
+///
+/// Only effects which do not require a choice from the player are currently
+/// supported. Optional effects, conditional effects and unsupported effects are
+/// skipped. For effects which look at the enemy's hand and then discard a
+/// chosen card, only the enemy's hand is revealed.
+///
+/// Amounts written as "X" are evaluated when the effect is applied.
+///
+/// Returns an error for modal effects and for effects whose X cannot yet be
+/// evaluated. The battle may then have been partially modified, so callers
+/// should discard it.
+pub fn apply(
+    battle: &mut BattleData,
+    controller: Seat,
+    effect: &Effect,
+) -> Result<(), EffectError> {
+    match effect {
+        Effect::Effect(effect) => apply_standard(battle, controller, effect),
+        Effect::WithOptions(effect) => apply_with_options(battle, controller, effect),
+        Effect::List(effects) => {
+            for effect in effects {
+                apply_with_options(battle, controller, effect)?;
+            }
+            Ok(())
+        }
+        Effect::Modal(_) => Err(EffectError::ModalEffect),
+    }
+}
+
+fn apply_with_options(
+    battle: &mut BattleData,
+    controller: Seat,
+    effect: &EffectWithOptions,
+) -> Result<(), EffectError> {
+    if !effect.is_optional() && effect.condition.is_none() {
+        apply_standard(battle, controller, &effect.effect)?;
+    }
+    Ok(())
+}
+
+fn apply_standard(
+    battle: &mut BattleData,
+    controller: Seat,
+    effect: &StandardEffect,
+) -> Result<(), EffectError> {
+    match effect {
+        StandardEffect::DiscardCardFromEnemyHand { .. }
+        | StandardEffect::DiscardCardFromEnemyHandThenTheyDraw { .. } => {
+            mutations::reveal_cards(battle, controller.opponent(), Zone::Hand, controller);
+        }
+        StandardEffect::DoubleYourEnergy => {
+            let energy = battle.player(controller).energy;
+            mutations::gain_energy(battle, controller, energy);
+        }
+        StandardEffect::DrawCards { count } => {
+            let count = resolve(battle, controller, count)?;
+            mutations::draw_cards(battle, controller, count as usize);
+        }
+        StandardEffect::GainEnergy { gains } => {
+            let gains = resolve(battle, controller, gains)?;
+            mutations::gain_energy(battle, controller, gains);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Returns the value of an amount used by an effect controlled by
+/// `controller`, or an error if it is an X which cannot yet be evaluated.
+fn resolve<T: Copy + From<u32>>(
+    battle: &BattleData,
+    controller: Seat,
+    quantity: &Quantity<T>,
+) -> Result<T, EffectError> {
+    match quantity {
+        Quantity::Fixed(amount) => Ok(*amount),
+        Quantity::X(x) => queries::quantity(battle, controller, x)
+            .map(T::from)
+            .ok_or_else(|| EffectError::UnsupportedQuantity(x.clone())),
+    }
+}
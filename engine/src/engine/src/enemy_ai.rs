@@ -0,0 +1,58 @@
+use battle_data::battle_card::Zone;
+use battle_data::battle_data::BattleData;
+use card_data::card_definition::CardType;
+use core_data::display_types::Milliseconds;
+use core_data::numerics::Energy;
+use core_data::types::Seat;
+use display_data::command::{Command, CommandSequence, GameMessageType, UpdateBattleCommand};
+
+use crate::effects::EffectError;
+use crate::{battle_rendering, mutations, queries};
+
+/// Performs the next action for the enemy during their turn.
+///
+/// The enemy plays the first character in their hand which they can afford,
+/// and ends their turn once no more characters can be played. Returns commands
+/// to display the action, or None if it is not currently the enemy's turn.
+///
+/// Returns an error without modifying the battle if ending the turn applies
+/// an effect which cannot yet be applied, see [mutations::start_turn].
+pub fn take_action(battle: &mut BattleData) -> Result<Option<CommandSequence>, EffectError> {
+    if battle.turn.active_player != Seat::Guest {
+        return Ok(None);
+    }
+
+    let energy = battle.enemy.energy;
+    let playable = battle.cards_in_zone(Seat::Guest, Zone::Hand).into_iter().find_map(|card| {
+        let cost = queries::cost(battle, card)?;
+        (card.definition().card_type == CardType::Character && cost <= energy)
+            .then_some((card.id, cost))
+    });
+
+    Ok(Some(match playable {
+        Some((id, cost)) => {
+            battle.enemy.energy = Energy(energy.0 - cost.0);
+            battle.move_card(id, Zone::Battlefield);
+            // Pause after each action so the user can follow along
+            CommandSequence::sequential(vec![update(battle), Command::Wait(Milliseconds::new(500))])
+        }
+        None => {
+            let original = battle.clone();
+            if let Err(error) = mutations::end_turn(battle) {
+                *battle = original;
+                return Err(error);
+            }
+            CommandSequence::sequential(vec![
+                Command::DisplayGameMessage(GameMessageType::YourTurn),
+                update(battle),
+            ])
+        }
+    }))
+}
+
+fn update(battle: &BattleData) -> Command {
+    Command::UpdateBattle(UpdateBattleCommand::new(battle_rendering::battle_view(
+        battle,
+        Seat::Host,
+    )))
+}
@@ -0,0 +1,350 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use battle_data::action_log::{LogEntry, LoggedAction};
+use battle_data::battle_data::BattleData;
+use core_data::identifiers::{BattleId, UserId};
+use core_data::types::Seat;
+use display_data::command::{Command, CommandSequence, GameMessageType, ParallelCommandGroup};
+use display_data::request_data::{
+    ConnectRequest, ConnectResponse, Metadata, PerformActionRequest, PerformActionResponse,
+    PollRequest, PollResponse,
+};
+use uuid::Uuid;
+
+use crate::battle_patches::ViewTracker;
+use crate::request_error::RequestError;
+use crate::session_registry::SessionRegistry;
+use crate::{enemy_ai, test_data};
+
+/// Handles a request to connect to a battle.
+///
+/// If the request metadata has a `battle_id`, the user's battle with that ID
+/// is resumed, or an error is returned if they have no such battle.
+/// Otherwise, the battle the user is currently playing is resumed if it is
+/// against the requested opponent. If there is none, a battle which the
+/// requested opponent has started against this user is joined, or else a new
+/// battle is started. The response metadata contains the ID of the battle.
+///
+/// Any queued updates for the user are discarded, since the response contains
+/// the full current state of their battle. Subsequent battle updates are sent
+/// as patches to this state if requested.
+pub fn connect(
+    registry: &SessionRegistry,
+    request: &ConnectRequest,
+) -> Result<ConnectResponse, RequestError> {
+    connect_client(registry, request, ClientView::Session(request.metadata.user_id))
+}
+
+/// Equivalent to [connect] for a streaming connection, which tracks its own
+/// battle view in `view` rather than sharing the view tracked by the user's
+/// session. See [SessionRegistry::subscribe].
+pub fn connect_stream(
+    registry: &SessionRegistry,
+    request: &ConnectRequest,
+    view: &mut ViewTracker,
+) -> Result<ConnectResponse, RequestError> {
+    connect_client(registry, request, ClientView::Connection(view))
+}
+
+fn connect_client(
+    registry: &SessionRegistry,
+    request: &ConnectRequest,
+    mut view: ClientView,
+) -> Result<ConnectResponse, RequestError> {
+    registry.evict_idle_sessions();
+    let user_id = request.metadata.user_id;
+    if let Some(battle_id) = request.metadata.battle_id {
+        // Checked before resetting the view, so that connecting to an unknown
+        // battle does not create a session for the user.
+        registry.find_seat(user_id, battle_id)?;
+    }
+    view.reset(registry, Some(request.battle_patches))?;
+    let battle = match request.metadata.battle_id {
+        Some(battle_id) => registry
+            .find_battle(user_id, battle_id)?
+            .ok_or(RequestError::UnknownBattle(battle_id))?,
+        None => match registry.current_battle(user_id, request.opponent)? {
+            Some(battle) => battle,
+            None => {
+                let joined = match request.opponent {
+                    Some(opponent) => registry.join_battle(user_id, opponent)?,
+                    None => None,
+                };
+                match joined {
+                    Some(battle) => battle,
+                    None => registry.current_battle_or_insert(user_id, request.opponent, || {
+                        let mut battle = test_data::new_battle(BattleId(Uuid::new_v4()));
+                        battle.guest = request.opponent;
+                        battle
+                    })?,
+                }
+            }
+        },
+    };
+    let (_, seat) = registry.find_seat(user_id, battle.id)?;
+    Ok(ConnectResponse {
+        metadata: Metadata {
+            battle_id: Some(battle.id),
+            version: Some(battle.version),
+            ..request.metadata
+        },
+        commands: view.track(registry, test_data::connect(&battle, seat))?,
+        wire_format: request.wire_format,
+    })
+}
+
+/// Handles a request to perform a game action in the battle identified by the
+/// request metadata.
+///
+/// Returns an error if the action is not legal in the current state of the
+/// battle. If the request metadata specifies a battle version which does not
+/// match the current version, the action is not performed and the response
+/// instead contains a full snapshot of the battle.
+///
+/// If the action passes the turn to the AI enemy, the enemy's turn is played
+/// on a background thread and its commands are queued to be retrieved via
+/// [poll]. In a battle between two users, the battle state after the action is
+/// instead queued for the acting user's opponent.
+pub fn perform_action(
+    registry: &Arc<SessionRegistry>,
+    request: &PerformActionRequest,
+) -> Result<PerformActionResponse, RequestError> {
+    perform_client_action(registry, request, ClientView::Session(request.metadata.user_id))
+}
+
+/// Equivalent to [perform_action] for a streaming connection, which tracks its
+/// own battle view in `view`. See [connect_stream].
+pub fn perform_action_stream(
+    registry: &Arc<SessionRegistry>,
+    request: &PerformActionRequest,
+    view: &mut ViewTracker,
+) -> Result<PerformActionResponse, RequestError> {
+    perform_client_action(registry, request, ClientView::Connection(view))
+}
+
+fn perform_client_action(
+    registry: &Arc<SessionRegistry>,
+    request: &PerformActionRequest,
+    mut view: ClientView,
+) -> Result<PerformActionResponse, RequestError> {
+    registry.evict_idle_sessions();
+    let user_id = request.metadata.user_id;
+    let battle_id = request.metadata.battle_id.ok_or(RequestError::MissingBattleId)?;
+    let (host, seat) = registry.find_seat(user_id, battle_id)?;
+    let (result, version, opponent, opponent_update) =
+        registry.with_battle(user_id, Some(battle_id), |battle| {
+            let result = apply_action(battle, seat, request)?;
+            let opponent = match seat {
+                Seat::Host => battle.guest,
+                Seat::Guest => Some(host),
+            };
+            let opponent_update = match &result {
+                // Display-only actions change nothing the opponent can see
+                ActionResult::Performed { .. } if request.action.is_display_only() => None,
+                ActionResult::Performed { passed_turn, .. } => {
+                    opponent.map(|_| opponent_update(battle, seat.opponent(), *passed_turn))
+                }
+                ActionResult::Resync(_) => None,
+            };
+            Ok::<_, RequestError>((result, battle.version, opponent, opponent_update))
+        })??;
+
+    if let (Some(opponent), Some(commands)) = (opponent, opponent_update) {
+        registry.queue_commands(opponent, battle_id, version, commands)?;
+    }
+
+    let commands = match result {
+        ActionResult::Performed { commands, passed_turn } => {
+            // Battles without an opponent user are against the AI
+            if passed_turn && opponent.is_none() {
+                schedule_update(registry, user_id, battle_id, Duration::ZERO, take_enemy_action);
+            }
+            commands
+        }
+        ActionResult::Resync(commands) => {
+            view.reset(registry, None)?;
+            commands
+        }
+    };
+
+    Ok(PerformActionResponse {
+        metadata: Metadata { version: Some(version), ..request.metadata },
+        commands: view.track(registry, commands)?,
+    })
+}
+
+/// Handles a request for commands which were produced outside of a user
+/// request, such as enemy actions. See [schedule_update].
+///
+/// Returns an empty command sequence if no updates are available. If the
+/// request asks to resync, queued updates are discarded and a full snapshot of
+/// the battle is returned instead.
+pub fn poll(
+    registry: &SessionRegistry,
+    request: &PollRequest,
+) -> Result<PollResponse, RequestError> {
+    if request.resync {
+        return resync_client(
+            registry,
+            &request.metadata,
+            ClientView::Session(request.metadata.user_id),
+        );
+    }
+
+    let (commands, version) =
+        registry.take_updates(request.metadata.user_id, request.metadata.battle_id)?;
+    Ok(PollResponse { metadata: Metadata { version: Some(version), ..request.metadata }, commands })
+}
+
+/// Equivalent to a [poll] request with `resync` set for a streaming
+/// connection, which tracks its own battle view in `view`. See
+/// [connect_stream].
+pub fn resync_stream(
+    registry: &SessionRegistry,
+    metadata: &Metadata,
+    view: &mut ViewTracker,
+) -> Result<PollResponse, RequestError> {
+    resync_client(registry, metadata, ClientView::Connection(view))
+}
+
+fn resync_client(
+    registry: &SessionRegistry,
+    metadata: &Metadata,
+    mut view: ClientView,
+) -> Result<PollResponse, RequestError> {
+    let user_id = metadata.user_id;
+    let battle_id = metadata.battle_id.ok_or(RequestError::MissingBattleId)?;
+    let (_, seat) = registry.find_seat(user_id, battle_id)?;
+    view.reset(registry, None)?;
+    let battle =
+        registry.find_battle(user_id, battle_id)?.ok_or(RequestError::UnknownBattle(battle_id))?;
+    Ok(PollResponse {
+        metadata: Metadata { version: Some(battle.version), ..*metadata },
+        commands: view.track(registry, test_data::connect(&battle, seat))?,
+    })
+}
+
+/// Battle view of the client which a response is sent to, used to send battle
+/// updates as patches. See [ViewTracker].
+enum ClientView<'a> {
+    /// A client which receives updates via poll requests, whose view is
+    /// tracked by the session of the given user.
+    Session(UserId),
+
+    /// A streaming connection, which tracks its own view.
+    Connection(&'a mut ViewTracker),
+}
+
+impl ClientView<'_> {
+    /// See [SessionRegistry::reset_client].
+    fn reset(
+        &mut self,
+        registry: &SessionRegistry,
+        enabled: Option<bool>,
+    ) -> Result<(), RequestError> {
+        match self {
+            ClientView::Session(user_id) => registry.reset_client(*user_id, enabled),
+            ClientView::Connection(view) => {
+                view.reset(enabled);
+                Ok(())
+            }
+        }
+    }
+
+    fn track(
+        &mut self,
+        registry: &SessionRegistry,
+        commands: CommandSequence,
+    ) -> Result<CommandSequence, RequestError> {
+        match self {
+            ClientView::Session(user_id) => registry.track_commands(*user_id, commands),
+            ClientView::Connection(view) => Ok(view.track(commands)),
+        }
+    }
+}
+
+/// Result of applying a user action to a battle.
+enum ActionResult {
+    /// The action was performed. `passed_turn` is true if the action ended the
+    /// acting user's turn.
+    Performed { commands: CommandSequence, passed_turn: bool },
+
+    /// The action was made against a stale version of the battle and was not
+    /// performed. Contains commands to display the current state of the
+    /// battle.
+    Resync(CommandSequence),
+}
+
+/// Applies an action by the user in the `seat` player's seat to a battle.
+fn apply_action(
+    battle: &mut BattleData,
+    seat: Seat,
+    request: &PerformActionRequest,
+) -> Result<ActionResult, RequestError> {
+    if request.metadata.version.is_some_and(|version| version != battle.version) {
+        return Ok(ActionResult::Resync(test_data::connect(battle, seat)));
+    }
+
+    let turn_number = battle.turn.turn_number;
+    let own_turn = battle.turn.active_player == seat;
+    let commands = test_data::perform_action(battle, seat, request.action)?;
+    if !request.action.is_display_only() {
+        battle.version += 1;
+    }
+    battle
+        .action_log
+        .push(LogEntry { turn_number, action: LoggedAction::Player(seat, request.action) });
+    let passed_turn = own_turn && battle.turn.active_player == seat.opponent();
+    Ok(ActionResult::Performed { commands, passed_turn })
+}
+
+/// Returns commands to display the result of an action to the acting user's
+/// opponent, who occupies the `seat` player's seat.
+fn opponent_update(battle: &BattleData, seat: Seat, passed_turn: bool) -> CommandSequence {
+    let mut commands = test_data::connect(battle, seat);
+    if passed_turn {
+        commands.groups.insert(0, ParallelCommandGroup {
+            commands: vec![Command::DisplayGameMessage(GameMessageType::YourTurn)],
+        });
+    }
+    commands
+}
+
+/// Updates one of the user's battles on a background thread, outside of any
+/// user request.
+///
+/// After waiting for `delay`, `step` is called repeatedly with exclusive
+/// access to the battle until it returns None or an error. The commands it
+/// returns are
+/// queued to be delivered to the user via [poll]. Everything which changes a
+/// battle without a user request, such as the AI enemy's turn, timers and
+/// delayed triggers, is scheduled through this function.
+pub fn schedule_update(
+    registry: &Arc<SessionRegistry>,
+    user_id: UserId,
+    battle_id: BattleId,
+    delay: Duration,
+    mut step: impl FnMut(&mut BattleData) -> Result<Option<CommandSequence>, RequestError>
+        + Send
+        + 'static,
+) {
+    let registry = registry.clone();
+    thread::spawn(move || {
+        thread::sleep(delay);
+        while let Ok(true) = registry.update_battle(user_id, battle_id, &mut step) {}
+    });
+}
+
+/// Performs the next action for the AI enemy during their turn, recording it
+/// in the battle's action log.
+fn take_enemy_action(battle: &mut BattleData) -> Result<Option<CommandSequence>, RequestError> {
+    let turn_number = battle.turn.turn_number;
+    let Some(commands) = enemy_ai::take_action(battle)? else {
+        return Ok(None);
+    };
+    battle.version += 1;
+    battle.action_log.push(LogEntry { turn_number, action: LoggedAction::EnemyAi });
+    Ok(Some(commands))
+}
@@ -0,0 +1,60 @@
+use action_data::battle_action::BattleAction;
+use action_data::user_action::UserAction;
+use battle_data::battle_card::{BattleCard, Zone};
+use battle_data::battle_data::{BattleData, BattlePrompt};
+use core_data::numerics::Energy;
+use core_data::types::Seat;
+
+use crate::queries;
+
+/// Returns true if the user in the `player` seat can currently perform
+/// `action` in this battle.
+pub fn is_legal(battle: &BattleData, player: Seat, action: UserAction) -> bool {
+    match action {
+        UserAction::DebugAction(_) => true,
+        UserAction::BattleAction(action) => is_legal_battle_action(battle, player, action),
+    }
+}
+
+/// Returns true if `player` can currently play `card`.
+///
+/// Cards can be played from the player's hand during their turn, or during
+/// their opponent's turn if they are fast, while no prompt is active. The
+/// player must have enough energy to pay the card's current cost.
+pub fn can_play(battle: &BattleData, player: Seat, card: &BattleCard) -> bool {
+    card.zone == Zone::Hand
+        && card.owner == player
+        && battle.prompt.is_none()
+        && (battle.turn.active_player == player || queries::is_fast(battle, card))
+        && queries::cost(battle, card).unwrap_or(Energy(0)) <= battle.player(player).energy
+}
+
+/// Returns true if `player` can currently select `card` as the target of a
+/// prompt.
+pub fn can_select(battle: &BattleData, player: Seat, card: &BattleCard) -> bool {
+    matches!(
+        &battle.prompt,
+        Some(BattlePrompt::SelectTarget { source, target_controller })
+            if card.zone == Zone::Battlefield
+                && card.owner == *target_controller
+                && battle.card(*source).is_some_and(|source| source.owner == player)
+    )
+}
+
+/// Returns true if `player` can currently end their turn.
+pub fn can_end_turn(battle: &BattleData, player: Seat) -> bool {
+    battle.turn.active_player == player && battle.prompt.is_none()
+}
+
+fn is_legal_battle_action(battle: &BattleData, player: Seat, action: BattleAction) -> bool {
+    match action {
+        BattleAction::PlayCard(id) => {
+            battle.card(id).is_some_and(|card| can_play(battle, player, card))
+        }
+        BattleAction::SelectTarget(id) => {
+            battle.card(id).is_some_and(|card| can_select(battle, player, card))
+        }
+        BattleAction::BrowseCards(_) | BattleAction::CloseCardBrowser => true,
+        BattleAction::EndTurn => can_end_turn(battle, player),
+    }
+}
@@ -1 +1,18 @@
+pub mod battle_creation;
+pub mod battle_patches;
+pub mod battle_rendering;
+pub mod card_abilities;
+pub mod card_rendering;
+pub mod effects;
+pub mod enemy_ai;
+pub mod handle_request;
+pub mod legal_actions;
+pub mod mutations;
+pub mod queries;
+pub mod quest_creation;
+pub mod quest_mutations;
+pub mod request_error;
+pub mod session_registry;
+pub mod spectator;
 pub mod test_data;
+pub mod triggers;
@@ -0,0 +1,102 @@
+use ability_data::ability::Ability;
+use ability_data::trigger_event::TriggerEvent;
+use battle_data::battle_card::Zone;
+use battle_data::battle_data::BattleData;
+use battle_data::turn_data::TurnData;
+use card_data::card_definition::EnergyProduction;
+use core_data::identifiers::CardId;
+use core_data::numerics::Energy;
+use core_data::types::Seat;
+
+use crate::effects::EffectError;
+use crate::{card_abilities, effects, triggers};
+
+/// Moves the top card of the `player`'s deck to their hand.
+///
+/// Returns the ID of the drawn card, or None if the deck is empty.
+pub fn draw_card(battle: &mut BattleData, player: Seat) -> Option<CardId> {
+    let id = battle.top_of_deck(player)?;
+    battle.move_card(id, Zone::Hand);
+    Some(id)
+}
+
+/// Draws `count` cards for the `player`, returning the IDs of the cards which
+/// were drawn.
+pub fn draw_cards(battle: &mut BattleData, player: Seat, count: usize) -> Vec<CardId> {
+    (0..count).map_while(|_| draw_card(battle, player)).collect()
+}
+
+/// Adds energy to the `player`'s energy pool, firing "when you gain energy"
+/// triggers.
+pub fn gain_energy(battle: &mut BattleData, player: Seat, amount: Energy) {
+    if amount == Energy(0) {
+        return;
+    }
+    battle.player_mut(player).energy += amount;
+    triggers::fire(battle, player, |event| matches!(event, TriggerEvent::GainEnergy));
+}
+
+/// Starts a new turn for the `player`.
+///
+/// Any unspent energy is lost. The next card of the player's dreamwell is
+/// revealed, updating their energy production, and they gain energy equal to
+/// their production. The dreamwell card's own abilities are then applied,
+/// followed by any triggered abilities.
+///
+/// Returns an error if any of these abilities cannot be applied, see
+/// [effects::apply].
+pub fn start_turn(battle: &mut BattleData, player: Seat) -> Result<(), EffectError> {
+    battle.turn = TurnData {
+        active_player: player,
+        turn_number: battle.turn.turn_number + 1,
+        ..TurnData::default()
+    };
+    battle.player_mut(player).energy = Energy(0);
+    let dreamwell = reveal_dreamwell_card(battle, player);
+    let produced = battle.player(player).produced_energy;
+    gain_energy(battle, player, produced);
+    if let Some(name) = dreamwell.and_then(|id| battle.card(id)).map(|card| card.name) {
+        for ability in card_abilities::get(name) {
+            if let Ability::Event(effect) = ability {
+                effects::apply(battle, player, effect)?;
+            }
+        }
+    }
+    triggers::resolve_pending(battle)
+}
+
+/// Ends the current turn, starting the next turn for the other player. See
+/// [start_turn].
+pub fn end_turn(battle: &mut BattleData) -> Result<(), EffectError> {
+    let next = battle.turn.active_player.opponent();
+    start_turn(battle, next)
+}
+
+/// Reveals the cards in the `owner`'s `zone` to `player`, e.g. for an effect
+/// which lets a player look at their opponent's hand.
+///
+/// Cards remain revealed until they change zones.
+pub fn reveal_cards(battle: &mut BattleData, owner: Seat, zone: Zone, player: Seat) {
+    for card in battle.cards.values_mut().filter(|c| c.owner == owner && c.zone == zone) {
+        card.revealed_to.insert(player);
+    }
+}
+
+/// Reveals the next card of the `player`'s dreamwell and applies its energy
+/// production. The card is then moved to the end of the dreamwell.
+///
+/// Returns the ID of the revealed card, or None if the player has no
+/// dreamwell.
+fn reveal_dreamwell_card(battle: &mut BattleData, player: Seat) -> Option<CardId> {
+    let id = battle.next_dreamwell_card(player)?;
+    battle.move_card(id, Zone::Dreamwell);
+    battle.turn.dreamwell_card = Some(id);
+    let production = battle.card(id).and_then(|card| card.definition().energy_production);
+    let produced_energy = &mut battle.player_mut(player).produced_energy;
+    match production {
+        Some(EnergyProduction::Set(energy)) => *produced_energy = energy,
+        Some(EnergyProduction::Increase(energy)) => *produced_energy += energy,
+        None => {}
+    }
+    Some(id)
+}
@@ -0,0 +1,184 @@
+use ability_data::ability::Ability;
+use ability_data::predicate::{CardPredicate, CardZone, Controller, Operator};
+use ability_data::quantity_expression::QuantityExpression;
+use ability_data::static_ability::{StandardStaticAbility, StaticAbility};
+use ability_data::triggered_ability::TriggeredAbility;
+use battle_data::battle_card::{BattleCard, Zone};
+use battle_data::battle_data::BattleData;
+use card_data::card_definition::CardType;
+use core_data::identifiers::CardId;
+use core_data::numerics::Energy;
+use core_data::types::Seat;
+
+use crate::card_abilities;
+
+/// Returns the static abilities of cards controlled by `player` which modify
+/// the rules of the battle.
+///
+/// This includes abilities of characters on the battlefield and of cards in
+/// the player's status zone, such as dreamsigns. Abilities with conditions
+/// are not yet supported and are ignored.
+pub fn static_abilities(
+    battle: &BattleData,
+    player: Seat,
+) -> impl Iterator<Item = &'static StandardStaticAbility> + '_ {
+    [Zone::Battlefield, Zone::Status]
+        .into_iter()
+        .flat_map(move |zone| battle.cards_in_zone(player, zone))
+        .flat_map(|card| card_abilities::get(card.name))
+        .filter_map(|ability| match ability {
+            Ability::Static(StaticAbility::StaticAbility(ability)) => Some(ability),
+            _ => None,
+        })
+}
+
+/// Returns the triggered abilities of cards controlled by `player` which are
+/// able to trigger, along with the ID of their card and the index of the
+/// ability on that card.
+pub fn triggered_abilities(
+    battle: &BattleData,
+    player: Seat,
+) -> impl Iterator<Item = (CardId, usize, &'static TriggeredAbility)> + '_ {
+    [Zone::Battlefield, Zone::Status]
+        .into_iter()
+        .flat_map(move |zone| battle.cards_in_zone(player, zone))
+        .flat_map(|card| {
+            card_abilities::get(card.name).iter().enumerate().filter_map(|(index, ability)| {
+                match ability {
+                    Ability::Triggered(triggered) => Some((card.id, index, triggered)),
+                    _ => None,
+                }
+            })
+        })
+}
+
+/// Returns the current energy cost of a card, after applying cost
+/// modifications from static abilities.
+pub fn cost(battle: &BattleData, card: &BattleCard) -> Option<Energy> {
+    let mut cost = card.definition().cost?.0;
+    for ability in static_abilities(battle, card.owner) {
+        match ability {
+            StandardStaticAbility::YourCardsCostReduction { matching, reduction }
+                if matches(card, card.owner, matching) =>
+            {
+                cost = cost.saturating_sub(reduction.0);
+            }
+            StandardStaticAbility::YourCardsCostIncrease { matching, reduction }
+                if matches(card, card.owner, matching) =>
+            {
+                cost += reduction.0;
+            }
+            _ => {}
+        }
+    }
+    for ability in static_abilities(battle, card.owner.opponent()) {
+        if let StandardStaticAbility::EnemyCardsCostIncrease { matching, increase } = ability {
+            if matches(card, card.owner.opponent(), matching) {
+                cost += increase.0;
+            }
+        }
+    }
+    Some(Energy(cost))
+}
+
+/// Evaluates a quantity for `player`, e.g. the number of characters they
+/// control.
+///
+/// Returns None for quantities which are not yet supported, including those
+/// which count events earlier in the turn.
+pub fn quantity(battle: &BattleData, player: Seat, quantity: &QuantityExpression) -> Option<u32> {
+    let QuantityExpression::Matching(predicate) = quantity else {
+        return None;
+    };
+    let predicate = predicate.card_predicate()?;
+    let mut count = 0;
+    for owner in [player, player.opponent()] {
+        for zone in [Zone::Deck, Zone::Hand, Zone::Battlefield, Zone::Void] {
+            for card in battle.cards_in_zone(owner, zone) {
+                count += u32::from(evaluate(card, player, &predicate)?);
+            }
+        }
+    }
+    Some(count)
+}
+
+/// True if a card can currently be played during the opponent's turn.
+pub fn is_fast(battle: &BattleData, card: &BattleCard) -> bool {
+    let definition = card.definition();
+    definition.is_fast
+        || (card.zone == Zone::Hand
+            && definition.card_type == CardType::Character
+            && static_abilities(battle, card.owner)
+                .any(|a| matches!(a, StandardStaticAbility::CharactersInHandHaveFast)))
+}
+
+/// True if `card` matches the given predicate, evaluated for an ability
+/// controlled by `player`.
+///
+/// Predicates which are not yet supported never match, including when they
+/// are negated via [CardPredicate::Not].
+pub fn matches(card: &BattleCard, player: Seat, predicate: &CardPredicate) -> bool {
+    evaluate(card, player, predicate).unwrap_or(false)
+}
+
+/// Evaluates a predicate against `card` for an ability controlled by
+/// `player`, returning None if the predicate is not yet supported.
+fn evaluate(card: &BattleCard, player: Seat, predicate: &CardPredicate) -> Option<bool> {
+    let definition = card.definition();
+    match predicate {
+        CardPredicate::Card | CardPredicate::Dream => Some(true),
+        CardPredicate::Character => Some(definition.card_type == CardType::Character),
+        CardPredicate::Event => Some(definition.card_type == CardType::Event),
+        CardPredicate::CharacterType(character_type) => {
+            Some(definition.character_type == Some(*character_type))
+        }
+        CardPredicate::WithCost(cost, operator) => {
+            definition.cost.map_or(Some(false), |actual| compare(actual, *cost, operator))
+        }
+        CardPredicate::WithSpark(spark, operator) => {
+            definition.spark.map_or(Some(false), |actual| compare(actual, *spark, operator))
+        }
+        CardPredicate::ControlledBy(controller) => Some(match controller {
+            Controller::You => card.owner == player,
+            Controller::Enemy => card.owner == player.opponent(),
+        }),
+        CardPredicate::InZone(zone) => Some(
+            card.zone
+                == match zone {
+                    CardZone::Battlefield => Zone::Battlefield,
+                    CardZone::Hand => Zone::Hand,
+                    CardZone::Void => Zone::Void,
+                    CardZone::Deck => Zone::Deck,
+                },
+        ),
+        CardPredicate::And(predicates) => predicates
+            .iter()
+            .map(|p| evaluate(card, player, p))
+            .collect::<Option<Vec<_>>>()
+            .map(|results| results.into_iter().all(|result| result)),
+        CardPredicate::Or(predicates) => predicates
+            .iter()
+            .map(|p| evaluate(card, player, p))
+            .collect::<Option<Vec<_>>>()
+            .map(|results| results.into_iter().any(|result| result)),
+        CardPredicate::Not(predicate) => evaluate(card, player, predicate).map(|result| !result),
+        CardPredicate::CharacterWithCostComparedToControlled { .. }
+        | CardPredicate::CharacterWithCostComparedToAbandoned { .. }
+        | CardPredicate::CharacterWithSparkComparedToAbandoned { .. }
+        | CardPredicate::CharacterWithSparkComparedToAbandonedCountThisTurn { .. }
+        | CardPredicate::CharacterWithMaterializedAbility
+        | CardPredicate::Fast { .. }
+        | CardPredicate::CharacterWithMultiActivatedAbility => None,
+    }
+}
+
+/// Compares a card's printed stat to the value in a predicate. Returns None
+/// for operators relative to another card, which are not yet supported.
+fn compare<T: Ord>(actual: T, expected: T, operator: &Operator<T>) -> Option<bool> {
+    match operator {
+        Operator::Exactly => Some(actual == expected),
+        Operator::OrLess => Some(actual <= expected),
+        Operator::OrMore => Some(actual >= expected),
+        Operator::LowerBy(_) | Operator::HigherBy(_) => None,
+    }
+}
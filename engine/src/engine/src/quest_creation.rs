@@ -0,0 +1,129 @@
+use card_data::card_definition::{CardDefinition, CardType};
+use card_data::card_definitions;
+use card_data::card_name::CardName;
+use core_data::identifiers::QuestId;
+use core_data::numerics::Essence;
+use core_data::types::Rarity;
+use deck_data::deck::Deck;
+use quest_data::quest_data::QuestData;
+use quest_data::quest_map::{QuestMap, ShopOffer, Site, SiteId, SiteKind};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256StarStar;
+
+/// Amount of essence the player has at the start of a quest.
+pub const STARTING_ESSENCE: Essence = Essence(150);
+
+/// Number of cards offered at each draft site.
+pub const DRAFT_CHOICES: usize = 3;
+
+/// Number of cards offered at each shop site.
+pub const SHOP_OFFERS: usize = 4;
+
+/// Number of dreamsigns offered at each dreamsign reward site.
+pub const DREAMSIGN_CHOICES: usize = 2;
+
+/// Starts a new quest using `deck` as the player's starting deck.
+///
+/// The quest map is generated randomly, seeded from `seed`, so the same seed
+/// always produces the same map. The seed also determines the seeds of the
+/// quest's battles and must not be derived from the quest ID or anything else
+/// which is sent to clients.
+pub fn create(id: QuestId, seed: u64, deck: Deck) -> QuestData {
+    let mut rng = Xoshiro256StarStar::seed_from_u64(seed);
+    let map = generate_map(&mut rng);
+    QuestData {
+        id,
+        deck,
+        dreamsigns: vec![],
+        essence: STARTING_ESSENCE,
+        map,
+        current_site: None,
+        seed,
+        rng,
+    }
+}
+
+/// Generates a map made up of a series of layers. Every site in a layer
+/// connects to every site in the following layer, and the final layer is a
+/// single battle.
+fn generate_map(rng: &mut Xoshiro256StarStar) -> QuestMap {
+    let layers = [
+        vec![draft(rng)],
+        vec![battle(), battle()],
+        vec![shop(rng), dreamsign_reward(rng)],
+        vec![battle()],
+        vec![draft(rng), shop(rng)],
+        vec![battle()],
+    ];
+
+    let mut sites = vec![];
+    let mut next_id = 0;
+    for (index, layer) in layers.iter().enumerate() {
+        let next_layer_start = next_id + layer.len();
+        let next_layer_len = layers.get(index + 1).map_or(0, Vec::len);
+        for kind in layer {
+            sites.push(Site {
+                id: SiteId(next_id),
+                kind: kind.clone(),
+                connections: (next_layer_start..next_layer_start + next_layer_len)
+                    .map(SiteId)
+                    .collect(),
+                completed: false,
+            });
+            next_id += 1;
+        }
+    }
+
+    QuestMap { sites, start: SiteId(0) }
+}
+
+fn battle() -> SiteKind {
+    let deck = card_definitions::all()
+        .filter(|definition| definition.is_deck_card())
+        .map(|definition| definition.name)
+        .flat_map(|name| std::iter::repeat_n(name, 4))
+        .collect();
+    SiteKind::Battle { enemy: CardName::Korrak, deck }
+}
+
+fn draft(rng: &mut Xoshiro256StarStar) -> SiteKind {
+    SiteKind::Draft { choices: random_cards(rng, DRAFT_CHOICES, CardDefinition::is_deck_card) }
+}
+
+fn shop(rng: &mut Xoshiro256StarStar) -> SiteKind {
+    let offers = random_cards(rng, SHOP_OFFERS, CardDefinition::is_deck_card)
+        .into_iter()
+        .map(|card| ShopOffer { card, price: price(card_definitions::get(card)), sold: false })
+        .collect();
+    SiteKind::Shop { offers }
+}
+
+fn dreamsign_reward(rng: &mut Xoshiro256StarStar) -> SiteKind {
+    let choices = random_cards(rng, DREAMSIGN_CHOICES, |definition| {
+        definition.card_type == CardType::Dreamsign
+    });
+    SiteKind::DreamsignReward { choices }
+}
+
+/// Picks up to `count` distinct cards matching `predicate`.
+fn random_cards(
+    rng: &mut Xoshiro256StarStar,
+    count: usize,
+    predicate: impl Fn(&CardDefinition) -> bool,
+) -> Vec<CardName> {
+    let candidates = card_definitions::all()
+        .filter(|definition| predicate(definition))
+        .map(|definition| definition.name)
+        .collect::<Vec<_>>();
+    candidates.choose_multiple(rng, count).copied().collect()
+}
+
+fn price(definition: &CardDefinition) -> Essence {
+    match definition.rarity {
+        Rarity::Common => Essence(50),
+        Rarity::Uncommon => Essence(75),
+        Rarity::Rare => Essence(100),
+        Rarity::Legendary => Essence(150),
+    }
+}
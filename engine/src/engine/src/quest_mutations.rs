@@ -0,0 +1,199 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use battle_data::battle_data::BattleData;
+use card_data::card_definitions;
+use card_data::card_name::CardName;
+use core_data::identifiers::BattleId;
+use core_data::numerics::Essence;
+use deck_data::deck::Deck;
+use deck_data::deck_rules::{DeckRuleViolation, DeckRules};
+use quest_data::quest_data::QuestData;
+use quest_data::quest_map::{QuestMap, Site, SiteId, SiteKind};
+use rand::Rng;
+
+use crate::battle_creation;
+use crate::effects::EffectError;
+
+/// Amount of essence the player receives for winning a battle.
+pub const BATTLE_REWARD: Essence = Essence(100);
+
+/// Reasons a quest action could not be performed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum QuestError {
+    /// The site cannot be reached from the player's current position.
+    SiteNotAvailable(SiteId),
+
+    /// The player is not at a site which supports this action, or has already
+    /// completed it.
+    InvalidSiteAction,
+
+    /// The card is not offered at the current site.
+    CardNotOffered(CardName),
+
+    /// The player cannot afford to purchase a card.
+    NotEnoughEssence { price: Essence, available: Essence },
+
+    /// Adding the card would make the quest deck violate the deck rules.
+    DeckRule(DeckRuleViolation),
+
+    /// The battle could not be started, see [battle_creation::for_quest].
+    UnsupportedEffect(EffectError),
+}
+
+impl Display for QuestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SiteNotAvailable(site) => write!(f, "Site {} is not available", site.0),
+            Self::InvalidSiteAction => write!(f, "Action is not valid at the current site"),
+            Self::CardNotOffered(card) => {
+                write!(f, "{} is not offered here", card_definitions::get(*card).displayed_name)
+            }
+            Self::NotEnoughEssence { price, available } => {
+                write!(f, "Costs {price} essence, but only {available} is available")
+            }
+            Self::DeckRule(violation) => write!(f, "{violation}"),
+            Self::UnsupportedEffect(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for QuestError {}
+
+/// Moves the player to a new site on the quest map.
+pub fn visit_site(quest: &mut QuestData, site: SiteId) -> Result<(), QuestError> {
+    if !quest.available_sites().contains(&site) {
+        return Err(QuestError::SiteNotAvailable(site));
+    }
+    quest.current_site = Some(site);
+    Ok(())
+}
+
+/// Adds one of the cards offered by the current draft site to the quest deck,
+/// if the resulting deck satisfies `rules`.
+pub fn draft_pick(
+    quest: &mut QuestData,
+    card: CardName,
+    rules: &DeckRules,
+) -> Result<(), QuestError> {
+    let site = current_site_mut(&mut quest.map, quest.current_site)?;
+    let SiteKind::Draft { choices } = &site.kind else {
+        return Err(QuestError::InvalidSiteAction);
+    };
+    if !choices.contains(&card) {
+        return Err(QuestError::CardNotOffered(card));
+    }
+    check_deck_rules(&quest.deck, card, rules)?;
+    site.completed = true;
+    quest.deck.add(card, 1);
+    Ok(())
+}
+
+/// Gives the player one of the dreamsigns offered by the current dreamsign
+/// reward site.
+pub fn choose_dreamsign(quest: &mut QuestData, dreamsign: CardName) -> Result<(), QuestError> {
+    let site = current_site_mut(&mut quest.map, quest.current_site)?;
+    let SiteKind::DreamsignReward { choices } = &site.kind else {
+        return Err(QuestError::InvalidSiteAction);
+    };
+    if !choices.contains(&dreamsign) {
+        return Err(QuestError::CardNotOffered(dreamsign));
+    }
+    site.completed = true;
+    quest.dreamsigns.push(dreamsign);
+    Ok(())
+}
+
+/// Purchases a card from the current shop site, adding it to the quest deck,
+/// if the resulting deck satisfies `rules`.
+pub fn purchase(
+    quest: &mut QuestData,
+    card: CardName,
+    rules: &DeckRules,
+) -> Result<(), QuestError> {
+    let available = quest.essence;
+    let site = current_site_mut(&mut quest.map, quest.current_site)?;
+    let SiteKind::Shop { offers } = &mut site.kind else {
+        return Err(QuestError::InvalidSiteAction);
+    };
+    let offer = offers
+        .iter_mut()
+        .find(|offer| offer.card == card && !offer.sold)
+        .ok_or(QuestError::CardNotOffered(card))?;
+    if offer.price > available {
+        return Err(QuestError::NotEnoughEssence { price: offer.price, available });
+    }
+    check_deck_rules(&quest.deck, card, rules)?;
+    offer.sold = true;
+    let price = offer.price;
+    quest.essence -= price;
+    quest.deck.add(card, 1);
+    Ok(())
+}
+
+/// Finishes shopping at the current shop site.
+pub fn leave_shop(quest: &mut QuestData) -> Result<(), QuestError> {
+    let site = current_site_mut(&mut quest.map, quest.current_site)?;
+    if !matches!(site.kind, SiteKind::Shop { .. }) {
+        return Err(QuestError::InvalidSiteAction);
+    }
+    site.completed = true;
+    Ok(())
+}
+
+/// Starts the battle for the current battle site using the quest deck and
+/// dreamsigns.
+///
+/// The battle is seeded from the quest's random number generator.
+pub fn start_battle(quest: &mut QuestData, id: BattleId) -> Result<BattleData, QuestError> {
+    let Some(Site { kind: SiteKind::Battle { enemy, deck }, completed: false, .. }) = quest.site()
+    else {
+        return Err(QuestError::InvalidSiteAction);
+    };
+    let (enemy, deck) = (*enemy, deck.clone());
+    let seed = quest.rng.gen();
+    battle_creation::for_quest(id, seed, quest, enemy, &deck).map_err(QuestError::UnsupportedEffect)
+}
+
+/// Records that the player won the battle at the current battle site.
+pub fn win_battle(quest: &mut QuestData) -> Result<(), QuestError> {
+    let site = current_site_mut(&mut quest.map, quest.current_site)?;
+    if !matches!(site.kind, SiteKind::Battle { .. }) {
+        return Err(QuestError::InvalidSiteAction);
+    }
+    site.completed = true;
+    quest.essence += BATTLE_REWARD;
+    Ok(())
+}
+
+/// Checks that adding `card` to `deck` would not violate `rules`.
+///
+/// Quest decks grow over the course of the quest, so a deck which is still
+/// below the minimum size is allowed.
+fn check_deck_rules(deck: &Deck, card: CardName, rules: &DeckRules) -> Result<(), QuestError> {
+    let mut deck = deck.clone();
+    deck.add(card, 1);
+    match rules
+        .validate(&deck)
+        .into_iter()
+        .find(|violation| !matches!(violation, DeckRuleViolation::TooFewCards { .. }))
+    {
+        Some(violation) => Err(QuestError::DeckRule(violation)),
+        None => Ok(()),
+    }
+}
+
+/// Returns the current site if it has not yet been completed.
+///
+/// Takes the map rather than the whole quest so that the rest of the quest
+/// can be read while the site is borrowed.
+fn current_site_mut(
+    map: &mut QuestMap,
+    current_site: Option<SiteId>,
+) -> Result<&mut Site, QuestError> {
+    let id = current_site.ok_or(QuestError::InvalidSiteAction)?;
+    match map.site_mut(id) {
+        Some(site) if !site.completed => Ok(site),
+        _ => Err(QuestError::InvalidSiteAction),
+    }
+}
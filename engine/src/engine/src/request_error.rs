@@ -0,0 +1,82 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use action_data::user_action::UserAction;
+use core_data::identifiers::BattleId;
+use database::database::DatabaseError;
+use display_data::request_data::{ErrorResponse, ErrorType};
+
+use crate::effects::EffectError;
+
+/// Reasons a request could not be handled.
+#[derive(Debug)]
+pub enum RequestError {
+    /// The request is not valid for the connection it was sent on, e.g.
+    /// because it names a different user.
+    InvalidRequest(String),
+
+    /// The request metadata did not include a battle ID.
+    MissingBattleId,
+
+    /// The requesting user has no battle with this ID, e.g. because it was
+    /// replaced by a newer battle.
+    UnknownBattle(BattleId),
+
+    /// The action cannot be performed in the current state of the battle.
+    IllegalAction(UserAction),
+
+    /// The action has an effect which cannot yet be applied.
+    UnsupportedEffect(EffectError),
+
+    /// The user's save file could not be read or written.
+    Database(DatabaseError),
+}
+
+impl Display for RequestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidRequest(message) => write!(f, "{message}"),
+            Self::MissingBattleId => write!(f, "Request does not specify a battle ID"),
+            Self::UnknownBattle(id) => write!(f, "Unknown battle ID {}", id.0),
+            Self::IllegalAction(action) => write!(f, "Illegal action {action:?}"),
+            Self::UnsupportedEffect(error) => write!(f, "{error}"),
+            Self::Database(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for RequestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Database(error) => Some(error),
+            Self::UnsupportedEffect(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<DatabaseError> for RequestError {
+    fn from(error: DatabaseError) -> Self {
+        Self::Database(error)
+    }
+}
+
+impl From<EffectError> for RequestError {
+    fn from(error: EffectError) -> Self {
+        Self::UnsupportedEffect(error)
+    }
+}
+
+impl From<RequestError> for ErrorResponse {
+    fn from(error: RequestError) -> Self {
+        let error_type = match error {
+            RequestError::InvalidRequest(_) => ErrorType::InvalidRequest,
+            RequestError::MissingBattleId => ErrorType::MissingBattleId,
+            RequestError::UnknownBattle(_) => ErrorType::UnknownBattle,
+            RequestError::IllegalAction(_) => ErrorType::IllegalAction,
+            RequestError::UnsupportedEffect(_) => ErrorType::Internal,
+            RequestError::Database(_) => ErrorType::Internal,
+        };
+        ErrorResponse::new(error_type, error.to_string())
+    }
+}
@@ -0,0 +1,607 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use battle_data::battle_data::BattleData;
+use core_data::identifiers::{BattleId, UserId};
+use core_data::types::Seat;
+use database::database::Database;
+use database::save_file::{JoinedBattle, SaveFile};
+use display_data::command::CommandSequence;
+use tokio::sync::broadcast;
+
+use crate::battle_patches::ViewTracker;
+use crate::request_error::RequestError;
+
+/// Sessions which have not been accessed for this long are removed from
+/// memory. Their state remains in the database and is reloaded on the next
+/// request.
+pub const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Maximum number of updates buffered for each streaming connection which has
+/// not yet received them.
+const STREAM_CAPACITY: usize = 64;
+
+/// Maximum number of battle change notifications buffered for each observer
+/// which has not yet received them.
+const CHANGES_CAPACITY: usize = 256;
+
+/// Tracks the state of every connected user.
+///
+/// Each user has a session holding their save file, which is loaded from the
+/// database on first access and written back after every change. Sessions are
+/// locked individually, so requests from different users can be handled in
+/// parallel while requests for the same user are handled one at a time.
+///
+/// Sessions are only created for users who have a save file or who are
+/// connecting, so that requests naming arbitrary users do not fill the
+/// registry. A session whose lock was poisoned by a panic is discarded and
+/// reloaded from the database on next access.
+pub struct SessionRegistry {
+    database: Box<dyn Database>,
+    sessions: Mutex<HashMap<UserId, Arc<Mutex<Session>>>>,
+
+    /// Notifies observers whenever a battle is changed, see
+    /// [Self::watch_battles].
+    changes: broadcast::Sender<BattleId>,
+}
+
+/// Commands for one of a user's battles which were produced outside of a
+/// request by that user, such as enemy actions.
+#[derive(Debug, Clone)]
+pub struct BattleUpdate {
+    pub battle_id: BattleId,
+
+    /// Version of the battle after the update.
+    pub version: u64,
+
+    /// Commands to display the update. Each streaming connection converts
+    /// battle updates into patches against its own battle view, see
+    /// [ViewTracker::track].
+    pub commands: CommandSequence,
+}
+
+struct Session {
+    save: SaveFile,
+
+    /// Commands produced outside of a user request, waiting to be delivered
+    /// via a poll request, along with the battle they apply to.
+    ///
+    /// Commands are only queued here while the user has no streaming
+    /// connections, see [SessionRegistry::subscribe].
+    updates: VecDeque<(BattleId, CommandSequence)>,
+
+    /// Sends updates to the user's streaming connections.
+    stream: broadcast::Sender<BattleUpdate>,
+
+    /// Battle view most recently sent to the user via responses to requests
+    /// and poll requests. Streaming connections track their own views.
+    view: ViewTracker,
+
+    last_access: Instant,
+}
+
+impl SessionRegistry {
+    pub fn new(database: Box<dyn Database>) -> Self {
+        Self {
+            database,
+            sessions: Mutex::new(HashMap::new()),
+            changes: broadcast::channel(CHANGES_CAPACITY).0,
+        }
+    }
+
+    /// Returns the user's battle with the given ID, if it exists.
+    ///
+    /// This includes battles created by another user which this user has
+    /// joined as their opponent.
+    pub fn find_battle(
+        &self,
+        user_id: UserId,
+        battle_id: BattleId,
+    ) -> Result<Option<BattleData>, RequestError> {
+        let Some((host, _)) = self.locate_battle(user_id, battle_id)? else {
+            return Ok(None);
+        };
+        let battle = self.with_session(host, |session| {
+            Ok(session.save.battle.as_ref().filter(|battle| battle.id == battle_id).cloned())
+        })?;
+        Ok(battle.flatten())
+    }
+
+    /// Returns the user who created one of the user's battles, along with the
+    /// seat this user occupies in it.
+    pub fn find_seat(
+        &self,
+        user_id: UserId,
+        battle_id: BattleId,
+    ) -> Result<(UserId, Seat), RequestError> {
+        self.locate_battle(user_id, battle_id)?.ok_or(RequestError::UnknownBattle(battle_id))
+    }
+
+    /// Joins the current battle of the `host` user as their opponent, if they
+    /// started it against this user. Returns the joined battle.
+    pub fn join_battle(
+        &self,
+        user_id: UserId,
+        host: UserId,
+    ) -> Result<Option<BattleData>, RequestError> {
+        let battle = self.with_session(host, |session| {
+            Ok(session.save.battle.as_ref().filter(|battle| battle.guest == Some(user_id)).cloned())
+        })?;
+        let battle = battle.flatten();
+        if let Some(battle) = &battle {
+            self.with_new_session(user_id, |session| {
+                session.save.joined_battle = Some(JoinedBattle { host, battle_id: battle.id });
+                self.database.write_save(&session.save)?;
+                Ok(())
+            })?;
+        }
+        Ok(battle)
+    }
+
+    /// Returns the battle the user is currently playing, if any. This is
+    /// their own current battle or, if they have none, the battle they last
+    /// joined.
+    ///
+    /// If `opponent` is given, only a battle against that opponent is
+    /// returned, while None matches a battle against any opponent.
+    pub fn current_battle(
+        &self,
+        user_id: UserId,
+        opponent: Option<UserId>,
+    ) -> Result<Option<BattleData>, RequestError> {
+        let Some((battle, joined)) = self.with_session(user_id, |session| {
+            Ok((session.save.battle.clone(), session.save.joined_battle))
+        })?
+        else {
+            return Ok(None);
+        };
+        match (battle, joined) {
+            (Some(battle), _) => {
+                Ok(Some(battle).filter(|battle| opponent.is_none() || battle.guest == opponent))
+            }
+            (None, Some(joined)) if opponent.is_none() || opponent == Some(joined.host) => {
+                self.find_battle(user_id, joined.battle_id)
+            }
+            (None, _) => Ok(None),
+        }
+    }
+
+    /// Runs `function` with read-only access to one of the user's battles,
+    /// returning None if the user has no battle with this ID.
+    ///
+    /// Unlike other methods, this does not count as activity by the user. It
+    /// does not delay the eviction of any session, and save files for users
+    /// without a session in memory are read from the database without loading
+    /// a session. This allows battles to be observed, e.g. by spectators,
+    /// without keeping them in memory.
+    pub fn inspect_battle<T>(
+        &self,
+        user_id: UserId,
+        battle_id: BattleId,
+        function: impl FnOnce(&BattleData) -> T,
+    ) -> Result<Option<T>, RequestError> {
+        self.inspect_host_save(user_id, battle_id, |save| {
+            save.battle.as_ref().filter(|battle| battle.id == battle_id).map(function)
+        })
+    }
+
+    /// Runs `function` with read-only access to the initial state of one of
+    /// the user's battles, returning None if the user has no battle with this
+    /// ID or its initial state was not recorded.
+    ///
+    /// As with [Self::inspect_battle], this does not load any sessions.
+    pub fn inspect_battle_start<T>(
+        &self,
+        user_id: UserId,
+        battle_id: BattleId,
+        function: impl FnOnce(&BattleData) -> T,
+    ) -> Result<Option<T>, RequestError> {
+        self.inspect_host_save(user_id, battle_id, |save| {
+            save.battle_start.as_ref().filter(|battle| battle.id == battle_id).map(function)
+        })
+    }
+
+    /// Runs `function` on the current battle of every user who has one.
+    ///
+    /// As with [Self::inspect_battle], this does not load any sessions.
+    pub fn battles<T>(
+        &self,
+        function: impl Fn(UserId, &BattleData) -> T,
+    ) -> Result<Vec<T>, RequestError> {
+        let mut result = vec![];
+        for user_id in self.database.list_users()? {
+            let battle = self.inspect_save(user_id, |save| {
+                save.battle.as_ref().map(|battle| function(user_id, battle))
+            })?;
+            result.extend(battle.flatten());
+        }
+        Ok(result)
+    }
+
+    /// Returns a receiver which is sent the ID of each battle after it
+    /// changes, including when it is replaced by a new battle.
+    ///
+    /// This allows battles to be observed without loading sessions or polling,
+    /// see [Self::inspect_battle]. An observer which lags behind should assume
+    /// that any battle may have changed.
+    pub fn watch_battles(&self) -> broadcast::Receiver<BattleId> {
+        self.changes.subscribe()
+    }
+
+    /// Returns the user's own current battle if it is against `opponent`, or
+    /// otherwise makes the battle returned by `create` the user's current
+    /// battle, replacing any previous battle, including any battle they had
+    /// joined. A new battle is recorded as its own starting state so that it
+    /// can later be replayed.
+    ///
+    /// The check and the insertion happen while holding the user's session
+    /// lock, so concurrent requests cannot replace each other's new battles.
+    pub fn current_battle_or_insert(
+        &self,
+        user_id: UserId,
+        opponent: Option<UserId>,
+        create: impl FnOnce() -> BattleData,
+    ) -> Result<BattleData, RequestError> {
+        self.with_new_session(user_id, |session| {
+            if let Some(battle) = &session.save.battle {
+                if opponent.is_none() || battle.guest == opponent {
+                    return Ok(battle.clone());
+                }
+            }
+
+            let battle = create();
+            session.save.battle_start = Some(battle.clone());
+            let replaced = session.save.battle.replace(battle.clone());
+            session.save.joined_battle = None;
+            self.database.write_save(&session.save)?;
+            if let Some(replaced) = replaced {
+                self.notify(replaced.id);
+            }
+            Ok(battle)
+        })
+    }
+
+    /// Runs `function` with exclusive access to one of the user's battles,
+    /// saving the result.
+    pub fn with_battle<T>(
+        &self,
+        user_id: UserId,
+        battle_id: Option<BattleId>,
+        function: impl FnOnce(&mut BattleData) -> T,
+    ) -> Result<T, RequestError> {
+        let battle_id = battle_id.ok_or(RequestError::MissingBattleId)?;
+        let (host, _) = self.find_seat(user_id, battle_id)?;
+        self.with_session(host, |session| {
+            let battle = session
+                .save
+                .battle
+                .as_mut()
+                .filter(|battle| battle.id == battle_id)
+                .ok_or(RequestError::UnknownBattle(battle_id))?;
+            let result = function(battle);
+            self.database.write_save(&session.save)?;
+            self.notify(battle_id);
+            Ok(result)
+        })?
+        .ok_or(RequestError::UnknownBattle(battle_id))
+    }
+
+    /// Runs `function` with exclusive access to a battle created by the user,
+    /// saving the result. Commands returned by `function` are delivered to
+    /// the user as an update, see [Self::subscribe].
+    ///
+    /// Returns true if commands were queued. Errors returned by `function` are
+    /// returned without saving the battle.
+    pub fn update_battle(
+        &self,
+        user_id: UserId,
+        battle_id: BattleId,
+        function: impl FnOnce(&mut BattleData) -> Result<Option<CommandSequence>, RequestError>,
+    ) -> Result<bool, RequestError> {
+        self.with_session(user_id, |session| {
+            let battle = session
+                .save
+                .battle
+                .as_mut()
+                .filter(|battle| battle.id == battle_id)
+                .ok_or(RequestError::UnknownBattle(battle_id))?;
+            let Some(commands) = function(battle)? else {
+                return Ok(false);
+            };
+            let version = battle.version;
+            self.database.write_save(&session.save)?;
+            self.notify(battle_id);
+            session.deliver(BattleUpdate { battle_id, version, commands });
+            Ok(true)
+        })?
+        .ok_or(RequestError::UnknownBattle(battle_id))
+    }
+
+    /// Delivers commands for one of the user's battles to the user as an
+    /// update, e.g. to display an action taken by their opponent. `version` is
+    /// the version of the battle after the update.
+    ///
+    /// The update is sent to the user's streaming connections if they have
+    /// any, or else queued to be delivered on their next poll request.
+    pub fn queue_commands(
+        &self,
+        user_id: UserId,
+        battle_id: BattleId,
+        version: u64,
+        commands: CommandSequence,
+    ) -> Result<(), RequestError> {
+        self.with_session(user_id, |session| {
+            session.deliver(BattleUpdate { battle_id, version, commands });
+            Ok(())
+        })?
+        .ok_or(RequestError::UnknownBattle(battle_id))
+    }
+
+    /// Subscribes to updates for the user's battles, as an alternative to
+    /// poll requests.
+    ///
+    /// While the returned receiver exists, updates are sent to it instead of
+    /// being queued for poll requests. Each subscriber receives every update,
+    /// so a user can have several streaming connections at once.
+    pub fn subscribe(
+        &self,
+        user_id: UserId,
+    ) -> Result<broadcast::Receiver<BattleUpdate>, RequestError> {
+        self.with_new_session(user_id, |session| Ok(session.stream.subscribe()))
+    }
+
+    /// Removes and returns all queued commands for one of the user's battles,
+    /// combined into a single sequence in the order they were queued, along
+    /// with the current version of the battle.
+    pub fn take_updates(
+        &self,
+        user_id: UserId,
+        battle_id: Option<BattleId>,
+    ) -> Result<(CommandSequence, u64), RequestError> {
+        let battle_id = battle_id.ok_or(RequestError::MissingBattleId)?;
+        let (host, _) = self.find_seat(user_id, battle_id)?;
+        let take = |session: &mut Session| {
+            let mut result = CommandSequence::default();
+            for (id, commands) in std::mem::take(&mut session.updates) {
+                if id == battle_id {
+                    result.groups.extend(commands.groups);
+                }
+            }
+            result
+        };
+        let version = |session: &Session| {
+            session
+                .save
+                .battle
+                .as_ref()
+                .filter(|battle| battle.id == battle_id)
+                .map(|battle| battle.version)
+                .ok_or(RequestError::UnknownBattle(battle_id))
+        };
+
+        let unknown = || RequestError::UnknownBattle(battle_id);
+        if host == user_id {
+            self.with_session(user_id, |session| Ok((take(session), version(session)?)))?
+                .ok_or_else(unknown)
+        } else {
+            // The version is read first, so that commands queued in between
+            // are newer than the returned version rather than older.
+            let version =
+                self.with_session(host, |session| version(session))?.ok_or_else(unknown)?;
+            let commands =
+                self.with_session(user_id, |session| Ok(take(session)))?.ok_or_else(unknown)?;
+            Ok((commands, version))
+        }
+    }
+
+    /// Prepares commands to be sent to the user in response to a request,
+    /// converting battle updates into patches if the user has enabled them.
+    /// See [ViewTracker::track].
+    pub fn track_commands(
+        &self,
+        user_id: UserId,
+        commands: CommandSequence,
+    ) -> Result<CommandSequence, RequestError> {
+        self.with_new_session(user_id, |session| Ok(session.view.track(commands)))
+    }
+
+    /// Discards all queued commands for the user and forgets their current
+    /// battle view because they are about to receive a full snapshot of their
+    /// battle.
+    ///
+    /// Subsequent battle updates are sent as patches if `battle_patches` is
+    /// true, or if it is None and patches were previously enabled.
+    pub fn reset_client(
+        &self,
+        user_id: UserId,
+        battle_patches: Option<bool>,
+    ) -> Result<(), RequestError> {
+        self.with_new_session(user_id, |session| {
+            session.updates.clear();
+            session.view.reset(battle_patches);
+            Ok(())
+        })
+    }
+
+    /// Removes sessions which have been idle for longer than
+    /// [SESSION_IDLE_TIMEOUT] from memory, writing their save files first.
+    ///
+    /// Sessions poisoned by a panic are removed without being written, since
+    /// their state may be inconsistent. Their last saved state is reloaded on
+    /// the next request.
+    pub fn evict_idle_sessions(&self) {
+        lock(&self.sessions).retain(|_, session| {
+            // Sessions which are referenced by an in-progress request are
+            // never evicted. No new references can be created while the map
+            // is locked, so this session cannot be in use by anyone else.
+            if Arc::strong_count(session) > 1 {
+                return true;
+            }
+            if session.is_poisoned() {
+                return false;
+            }
+            let session = lock(session);
+            if !session.updates.is_empty()
+                || session.stream.receiver_count() > 0
+                || session.last_access.elapsed() < SESSION_IDLE_TIMEOUT
+            {
+                return true;
+            }
+            self.database.write_save(&session.save).is_err()
+        });
+    }
+
+    /// Returns the user whose save file contains one of the user's battles,
+    /// along with the seat this user occupies in it, or None if the user has
+    /// no battle with this ID.
+    fn locate_battle(
+        &self,
+        user_id: UserId,
+        battle_id: BattleId,
+    ) -> Result<Option<(UserId, Seat)>, RequestError> {
+        let seat = self.with_session(user_id, |session| {
+            if session.save.battle.as_ref().is_some_and(|battle| battle.id == battle_id) {
+                Ok(Some((user_id, Seat::Host)))
+            } else {
+                Ok(session
+                    .save
+                    .joined_battle
+                    .filter(|joined| joined.battle_id == battle_id)
+                    .map(|joined| (joined.host, Seat::Guest)))
+            }
+        })?;
+        Ok(seat.flatten())
+    }
+
+    /// Runs `function` on the save file of the user who created one of the
+    /// user's battles. See [Self::inspect_battle].
+    fn inspect_host_save<T>(
+        &self,
+        user_id: UserId,
+        battle_id: BattleId,
+        function: impl FnOnce(&SaveFile) -> Option<T>,
+    ) -> Result<Option<T>, RequestError> {
+        let host = self.inspect_save(user_id, |save| match &save.battle {
+            Some(battle) if battle.id == battle_id => Some(user_id),
+            _ => save
+                .joined_battle
+                .filter(|joined| joined.battle_id == battle_id)
+                .map(|joined| joined.host),
+        })?;
+        let Some(host) = host.flatten() else {
+            return Ok(None);
+        };
+        Ok(self.inspect_save(host, function)?.flatten())
+    }
+
+    /// Runs `function` on the user's save file, or returns None if they do not
+    /// have one. See [Self::inspect_battle].
+    fn inspect_save<T>(
+        &self,
+        user_id: UserId,
+        function: impl FnOnce(&SaveFile) -> T,
+    ) -> Result<Option<T>, RequestError> {
+        let session = lock(&self.sessions).get(&user_id).cloned();
+        match session {
+            Some(session) => Ok(Some(function(&lock(&session).save))),
+            None => Ok(self.database.fetch_save(user_id)?.as_ref().map(function)),
+        }
+    }
+
+    fn notify(&self, battle_id: BattleId) {
+        // Sending only fails if there are no receivers
+        let _ = self.changes.send(battle_id);
+    }
+
+    /// Runs `function` with exclusive access to the user's session, or
+    /// returns None without creating a session if the user has no save file.
+    fn with_session<T>(
+        &self,
+        user_id: UserId,
+        function: impl FnOnce(&mut Session) -> Result<T, RequestError>,
+    ) -> Result<Option<T>, RequestError> {
+        match self.session(user_id, false)? {
+            Some(session) => Ok(Some(Self::run(&session, function)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Runs `function` with exclusive access to the user's session, creating
+    /// a new save file for them if they do not have one.
+    fn with_new_session<T>(
+        &self,
+        user_id: UserId,
+        function: impl FnOnce(&mut Session) -> Result<T, RequestError>,
+    ) -> Result<T, RequestError> {
+        let session = self.session(user_id, true)?.expect("Session was not created");
+        Self::run(&session, function)
+    }
+
+    fn run<T>(
+        session: &Mutex<Session>,
+        function: impl FnOnce(&mut Session) -> Result<T, RequestError>,
+    ) -> Result<T, RequestError> {
+        let mut session = lock(session);
+        session.last_access = Instant::now();
+        function(&mut session)
+    }
+
+    /// Returns the user's session, loading it from the database if it is not
+    /// in memory. If the user has no save file, a new one is created if
+    /// `create` is true and otherwise None is returned.
+    ///
+    /// The database is read without holding the lock on the session map, so
+    /// loading one user's session does not block requests from other users.
+    fn session(
+        &self,
+        user_id: UserId,
+        create: bool,
+    ) -> Result<Option<Arc<Mutex<Session>>>, RequestError> {
+        {
+            let mut sessions = lock(&self.sessions);
+            match sessions.get(&user_id) {
+                Some(session) if session.is_poisoned() => {
+                    sessions.remove(&user_id);
+                }
+                Some(session) => return Ok(Some(session.clone())),
+                None => {}
+            }
+        }
+
+        let save = match self.database.fetch_save(user_id)? {
+            Some(save) => save,
+            None if create => SaveFile::new(user_id),
+            None => return Ok(None),
+        };
+        let session = Arc::new(Mutex::new(Session {
+            save,
+            updates: VecDeque::new(),
+            stream: broadcast::channel(STREAM_CAPACITY).0,
+            view: ViewTracker::default(),
+            last_access: Instant::now(),
+        }));
+
+        // Another request may have loaded the session in the meantime, in
+        // which case its session is used instead.
+        Ok(Some(lock(&self.sessions).entry(user_id).or_insert(session).clone()))
+    }
+}
+
+/// Locks `mutex`, recovering the guard if another thread panicked while
+/// holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Session {
+    fn deliver(&mut self, update: BattleUpdate) {
+        if self.stream.receiver_count() > 0 {
+            // Sending only fails if there are no receivers
+            let _ = self.stream.send(update);
+        } else {
+            let commands = self.view.track(update.commands);
+            self.updates.push_back((update.battle_id, commands));
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use battle_data::action_log::LoggedAction;
+use battle_data::battle_data::BattleData;
+use core_data::display_types::Milliseconds;
+use core_data::types::Seat;
+use display_data::command::{Command, CommandSequence, UpdateBattleCommand};
+use display_data::request_data::{
+    BattleSummary, ListBattlesResponse, ReplayRequest, ReplayResponse, SpectateRequest,
+    SpectateResponse,
+};
+
+use crate::request_error::RequestError;
+use crate::session_registry::SessionRegistry;
+use crate::{battle_rendering, enemy_ai, test_data};
+
+/// Pause between each action when replaying a turn.
+const REPLAY_STEP_DELAY: u32 = 500;
+
+/// Lists the current battle of every user.
+pub fn list_battles(registry: &SessionRegistry) -> Result<ListBattlesResponse, RequestError> {
+    let battles = registry.battles(|user_id, battle| BattleSummary {
+        user_id,
+        battle_id: battle.id,
+        version: battle.version,
+        turn_number: battle.turn.turn_number,
+        active_player: battle.turn.active_player,
+    })?;
+    Ok(ListBattlesResponse { battles })
+}
+
+/// Returns the current state of a battle as seen by the requested player.
+///
+/// Spectators cannot perform actions, and watching a battle does not affect
+/// the updates delivered to the user playing it.
+pub fn spectate(
+    registry: &SessionRegistry,
+    request: &SpectateRequest,
+) -> Result<SpectateResponse, RequestError> {
+    registry
+        .inspect_battle(request.user_id, request.battle_id, |battle| SpectateResponse {
+            version: battle.version,
+            commands: CommandSequence::from_command(update(battle, request.perspective)),
+        })?
+        .ok_or(RequestError::UnknownBattle(request.battle_id))
+}
+
+/// Follows a battle on behalf of a spectator.
+///
+/// The spectator keeps its own copy of the battle, which is brought up to date
+/// by applying new entries from the battle's action log. Only those entries
+/// are read from the battle when checking for updates.
+pub struct Spectator {
+    request: SpectateRequest,
+    battle: BattleData,
+
+    /// Number of entries from the battle's action log which have been applied
+    /// to [Self::battle].
+    log_position: usize,
+}
+
+impl Spectator {
+    /// Starts following a battle, returning the spectator along with the
+    /// current state of the battle.
+    pub fn new(
+        registry: &SessionRegistry,
+        request: SpectateRequest,
+    ) -> Result<(Self, SpectateResponse), RequestError> {
+        let battle = registry
+            .inspect_battle(request.user_id, request.battle_id, BattleData::clone)?
+            .ok_or(RequestError::UnknownBattle(request.battle_id))?;
+        let response = SpectateResponse {
+            version: battle.version,
+            commands: CommandSequence::from_command(update(&battle, request.perspective)),
+        };
+        let log_position = battle.action_log.len();
+        Ok((Self { request, battle, log_position }, response))
+    }
+
+    /// Returns the state of the battle after any actions taken since the last
+    /// update, or None if no actions have been taken.
+    pub fn update(
+        &mut self,
+        registry: &SessionRegistry,
+    ) -> Result<Option<SpectateResponse>, RequestError> {
+        let (version, entries) = registry
+            .inspect_battle(self.request.user_id, self.request.battle_id, |battle| {
+                let entries = battle.action_log.get(self.log_position..).unwrap_or_default();
+                (battle.version, entries.to_vec())
+            })?
+            .ok_or(RequestError::UnknownBattle(self.request.battle_id))?;
+        if entries.is_empty() {
+            return Ok(None);
+        }
+
+        self.log_position += entries.len();
+        for entry in entries {
+            apply(&mut self.battle, entry.action)?;
+        }
+        Ok(Some(SpectateResponse {
+            version,
+            commands: CommandSequence::from_command(update(&self.battle, self.request.perspective)),
+        }))
+    }
+}
+
+/// Replays one turn of a battle as seen by the requested player.
+///
+/// As with [spectate], replaying a battle does not load the session of the
+/// user playing it.
+///
+/// The battle is reconstructed by applying its action log to its starting
+/// state. The response displays the state of the battle at the start of the
+/// turn, followed by the state after each action taken during the turn.
+/// Requests for turns after the current turn replay the current turn.
+pub fn replay(
+    registry: &SessionRegistry,
+    request: &ReplayRequest,
+) -> Result<ReplayResponse, RequestError> {
+    let unknown = RequestError::UnknownBattle(request.battle_id);
+    let Some(current) =
+        registry.inspect_battle(request.user_id, request.battle_id, BattleData::clone)?
+    else {
+        return Err(unknown);
+    };
+    let Some(mut battle) =
+        registry.inspect_battle_start(request.user_id, request.battle_id, BattleData::clone)?
+    else {
+        return Err(unknown);
+    };
+
+    let last_turn_number = current.turn.turn_number;
+    let turn_number = request.turn_number.min(last_turn_number);
+    let mut log = current.action_log.iter().peekable();
+    while let Some(entry) = log.next_if(|entry| entry.turn_number < turn_number) {
+        apply(&mut battle, entry.action)?;
+    }
+
+    let mut commands = vec![update(&battle, request.perspective)];
+    for entry in log.take_while(|entry| entry.turn_number == turn_number) {
+        apply(&mut battle, entry.action)?;
+        commands.push(Command::Wait(Milliseconds::new(REPLAY_STEP_DELAY)));
+        commands.push(update(&battle, request.perspective));
+    }
+
+    Ok(ReplayResponse {
+        turn_number,
+        last_turn_number,
+        commands: CommandSequence::sequential(commands),
+    })
+}
+
+/// Applies a logged action to a battle.
+///
+/// Logged actions were legal when they were originally taken, and battles are
+/// deterministic, so replaying them from the same state should succeed. An
+/// error is returned if the replayed battle has diverged from the original.
+fn apply(battle: &mut BattleData, action: LoggedAction) -> Result<(), RequestError> {
+    match action {
+        LoggedAction::Player(player, action) => {
+            test_data::perform_action(battle, player, action)?;
+        }
+        LoggedAction::EnemyAi => {
+            enemy_ai::take_action(battle)?;
+        }
+    }
+    Ok(())
+}
+
+fn update(battle: &BattleData, perspective: Seat) -> Command {
+    Command::UpdateBattle(UpdateBattleCommand::new(battle_rendering::battle_view(
+        battle,
+        perspective,
+    )))
+}
@@ -0,0 +1,49 @@
+use ability_data::ability::Ability;
+use ability_data::trigger_event::TriggerEvent;
+use battle_data::battle_data::BattleData;
+use battle_data::turn_data::PendingTrigger;
+use core_data::types::Seat;
+
+use crate::effects::EffectError;
+use crate::{card_abilities, effects, queries};
+
+/// Adds all triggered abilities controlled by `player` whose trigger event
+/// matches `predicate` to the queue of pending triggers.
+///
+/// Abilities which can only trigger once per turn and have already done so
+/// are skipped.
+pub fn fire(battle: &mut BattleData, player: Seat, predicate: impl Fn(&TriggerEvent) -> bool) {
+    let triggered = queries::triggered_abilities(battle, player)
+        .filter(|(_, _, ability)| predicate(&ability.trigger))
+        .collect::<Vec<_>>();
+    for (card, ability_index, ability) in triggered {
+        let once_per_turn = ability.options.as_ref().is_some_and(|o| o.once_per_turn);
+        if once_per_turn && !battle.turn.triggered_this_turn.insert((card, ability_index)) {
+            continue;
+        }
+        battle.pending_triggers.push_back(PendingTrigger {
+            card,
+            ability_index,
+            controller: player,
+        });
+    }
+}
+
+/// Resolves pending triggered abilities in order until none remain.
+///
+/// Abilities which trigger during resolution are added to the end of the
+/// queue and resolved in turn. Stops with an error if an ability's effect
+/// cannot be applied, see [effects::apply].
+pub fn resolve_pending(battle: &mut BattleData) -> Result<(), EffectError> {
+    while let Some(trigger) = battle.pending_triggers.pop_front() {
+        let Some(card) = battle.card(trigger.card) else {
+            continue;
+        };
+        if let Some(Ability::Triggered(ability)) =
+            card_abilities::get(card.name).get(trigger.ability_index)
+        {
+            effects::apply(battle, trigger.controller, &ability.effect)?;
+        }
+    }
+    Ok(())
+}
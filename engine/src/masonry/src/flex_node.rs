@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::flex_style::FlexStyle;
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum ScrollBarVisibility {
     Auto,
@@ -12,7 +12,7 @@ pub enum ScrollBarVisibility {
     Hidden,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum TouchScrollBehavior {
     Unrestricted,
@@ -20,20 +20,20 @@ pub enum TouchScrollBehavior {
     Clamped,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum SliderDirection {
     Horizontal,
     Vertical,
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ScrollBar {
     pub style: Option<FlexStyle>,
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ScrollViewNode {
     pub elasticity: Option<f32>,
@@ -48,7 +48,7 @@ pub struct ScrollViewNode {
     pub mouse_wheel_scroll_size: Option<f32>,
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DraggableNode {
     pub drop_target_identifiers: Vec<String>,
@@ -61,7 +61,7 @@ pub struct DraggableNode {
     pub on_drag_detected: Option<UserAction>,
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TextFieldNode {
     pub global_identifier: Option<String>,
@@ -75,7 +75,7 @@ pub struct TextFieldNode {
     pub mask_character: Option<String>,
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SliderNode {
     pub initial_value: Option<f32>,
@@ -94,7 +94,7 @@ pub struct SliderNode {
     pub dragger_border_style: Option<FlexStyle>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum NodeType {
     Text(Text),
@@ -104,7 +104,7 @@ pub enum NodeType {
     SliderNode(Box<SliderNode>),
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EventHandlers {
     pub on_click: Option<UserAction>,
@@ -116,7 +116,7 @@ pub struct EventHandlers {
     pub on_field_changed: Option<UserAction>,
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FlexNode {
     pub name: Option<String>,
@@ -129,12 +129,12 @@ pub struct FlexNode {
     pub on_attach_style: Option<FlexStyle>,
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Text {
     pub label: String,
 }
 
-#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Flexbox {}
@@ -0,0 +1,285 @@
+use std::ops::Range;
+use std::slice;
+
+use ability_data::keyword;
+use chumsky::error::{Rich, RichPattern, RichReason};
+
+use crate::ability_parser;
+
+/// A parse error described in terms of the grammar of card rules text, for
+/// display to card designers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Location in the parsed text of the word which could not be parsed.
+    pub span: Range<usize>,
+
+    /// Summary of the error, for example "expected a card predicate after
+    /// 'Dissolve an'".
+    pub message: String,
+
+    /// Description of the text found at [Self::span].
+    pub label: String,
+
+    /// Sorted names of the grammar constructs which were expected at
+    /// [Self::span], or of individual tokens if no construct was expected.
+    pub expected: Vec<String>,
+
+    /// The known effect phrasing which most closely matches the clause that
+    /// failed to parse, if any is reasonably close.
+    pub suggestion: Option<String>,
+}
+
+/// Canonical phrasings of effects, used to suggest corrections for effect
+/// text which fails to parse.
+///
+/// Every kind of standard effect produced by the parser must be covered by at
+/// least one template, which is checked by the diagnostic tests.
+pub const EFFECT_TEMPLATES: &[&str] = &[
+    "abandon a character",
+    "abandon a character and gain $1 for each point of spark that character had",
+    "abandon it at end of turn",
+    "an event in your void gains {kw: reclaim} until end of turn",
+    "banish 2 cards from the enemy's void",
+    "banish another character you control, then materialize it",
+    "banish an enemy character",
+    "banish an enemy character until the start of your next main phase",
+    "banish an enemy character until this character leaves play",
+    "banish any number of enemy characters",
+    "banish the enemy's void",
+    "copy a character",
+    "copy the next event you play this turn",
+    "disable the activated abilities of an enemy character while this character is in play",
+    "discard 2 cards",
+    "discard a card",
+    "dissolve all characters",
+    "dissolve an enemy character",
+    "dissolve an enemy character with cost $2 or less",
+    "dissolve an enemy character with cost less than or equal to the number of cards in your void",
+    "double the amount of energy in your energy pool",
+    "draw 2 cards",
+    "draw a card",
+    "draw a card for each character you abandoned this turn",
+    "draw an event from your deck",
+    "each character you control gains +X spark, where X is the number of characters you control",
+    "each character you control gains +1 spark until your next main phase",
+    "each player abandons a character",
+    "each player discards a card",
+    "each player may shuffle their hand and void into their deck and then draw 5 cards",
+    "gain $1",
+    "gain $1 for each other character you control",
+    "gain 1 $point",
+    "gain 1 $point for each character you abandoned this turn",
+    "gain control of an enemy character",
+    "look at the enemy's hand. Choose a card from it. The enemy discards that card",
+    "look at the enemy's hand. You may choose a card from it. The enemy discards that card and then draws a card",
+    "materialize a character from your void",
+    "materialize a {kw: silent} copy of another character you control",
+    "materialize an enemy character",
+    "materialize it at end of turn",
+    "materialize two random characters with cost $3 or less from your deck",
+    "negate an enemy dream",
+    "put a character from your void on top of your deck",
+    "put an enemy character on top of the enemy's deck",
+    "put the top 3 cards of your deck into your void",
+    "return a character to hand",
+    "return all but one character you control to hand. Draw a card for each character returned",
+    "return an event from your void to your hand",
+    "spend all your remaining energy. Dissolve an enemy character with spark less than or equal to the energy spent this way",
+    "spend all your remaining energy. Draw X cards, then discard X cards, where X is the energy spent this way",
+    "take an extra turn after this one",
+    "the enemy gains $points equal to its spark",
+    "the enemy gains 2 $points",
+    "the enemy loses 1 $point",
+    "the spark of each character you control becomes 5",
+    "this character gains +1 spark",
+    "this character gains +1 spark for each character you abandoned this turn",
+    "this character gains +1 spark until your next main phase for each character you control",
+    "this character gains {kw: aegis} this turn",
+    "trigger the '$judgment' ability of each character you control",
+    "until end of turn, all cards in your void have {kw: reclaim}",
+    "until end of turn, whenever you gain energy, gain twice that much energy instead",
+    "until end of turn, whenever you play a character, draw a card",
+    "you lose 4 $points",
+    "you may return a character from your void to play",
+    "you win the game",
+    "{kw: discover} a character",
+    "{kw: discover} a character and materialize it",
+    "{kw: foresee} 2",
+    "{kw: kindle} 1",
+];
+
+/// Describes a parse error produced while parsing `text`.
+///
+/// The message names the grammar constructs which were expected instead of
+/// listing raw characters, and the clause containing the error is compared
+/// against [EFFECT_TEMPLATES] to suggest the closest known phrasing.
+pub fn diagnose(text: &str, error: &Rich<char>) -> Diagnostic {
+    let start = error.span().start.min(text.len());
+    let span = start..word_end(text, start);
+    let clause = clause_start(text, start);
+    let preceding = text[clause..start].trim();
+
+    let expected = expected(error);
+    let message = match error.reason() {
+        RichReason::Custom(message) => message.clone(),
+        _ if preceding.is_empty() => format!("expected {}", describe_expected(&expected)),
+        _ => format!("expected {} after '{preceding}'", describe_expected(&expected)),
+    };
+    let label = if span.is_empty() {
+        "found end of input".to_string()
+    } else {
+        format!("found '{}'", &text[span.clone()])
+    };
+
+    Diagnostic { span, message, label, expected, suggestion: suggestion(text, start) }
+}
+
+/// Returns an error for each `{reminder: ...}` in `text` which does not match
+/// the reminder text of the keywords in the ability it follows.
+///
+/// Reminder text may be wrapped in parentheses. The reminder text of several
+/// keywords is separated by spaces, in the order returned by
+/// [keyword::keywords].
+pub fn reminder_text_errors(text: &str) -> Vec<String> {
+    let Some(abilities) = ability_parser::parse_with_spans(text).into_output() else {
+        return vec![];
+    };
+    abilities
+        .iter()
+        .filter_map(|ability| {
+            let written = reminder_text(&text[ability.span.clone()])?;
+            let expected = keyword::keywords(slice::from_ref(&ability.ability))
+                .iter()
+                .map(|keyword| keyword.reminder_text())
+                .collect::<Vec<_>>()
+                .join(" ");
+            (written != expected)
+                .then(|| format!("reminder text '{written}' should be '{expected}'"))
+        })
+        .collect()
+}
+
+/// Returns the contents of the reminder text in `ability`, without
+/// surrounding parentheses.
+fn reminder_text(ability: &str) -> Option<&str> {
+    let start = ability.find("{reminder:")? + "{reminder:".len();
+    let end = start + ability[start..].find('}')?;
+    let written = ability[start..end].trim();
+    Some(written.strip_prefix('(').and_then(|w| w.strip_suffix(')')).unwrap_or(written))
+}
+
+/// Returns the patterns expected by `error`, preferring the names of grammar
+/// constructs to individual characters when any are available.
+fn expected(error: &Rich<char>) -> Vec<String> {
+    let labels = error
+        .expected()
+        .filter_map(|pattern| match pattern {
+            RichPattern::Label(label) => Some(label.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let mut patterns = if labels.is_empty() {
+        error.expected().map(|pattern| pattern.to_string()).collect()
+    } else {
+        labels
+    };
+    patterns.sort();
+    patterns.dedup();
+    patterns
+}
+
+/// Describes a list of expected patterns, e.g. "a cost or an effect".
+pub fn describe_expected(patterns: &[String]) -> String {
+    match patterns.split_last() {
+        None => "something else".to_string(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+    }
+}
+
+/// Returns the known effect phrasing with the smallest edit distance to the
+/// text around `position`, if that distance is less than half the phrasing's
+/// length. Case is ignored.
+///
+/// Each phrasing is compared against text which contains `position`, starts
+/// at a clause boundary within the same ability, and runs to the end of a
+/// sentence, spanning as many sentences as the phrasing itself. No suggestion
+/// is made if that text is already a known phrasing, since the error must then
+/// be elsewhere.
+fn suggestion(text: &str, position: usize) -> Option<String> {
+    let before = &text[..position];
+    let cost = before.rfind(':').map(|i| i + 1);
+    let paragraph = before.rfind("$br").map(|i| i + "$br".len());
+    let ability_start = cost.max(paragraph).unwrap_or(0);
+    let starts = text[ability_start..position]
+        .match_indices(['.', ','])
+        .map(|(i, _)| ability_start + i + 1)
+        .chain([ability_start])
+        .collect::<Vec<_>>();
+    EFFECT_TEMPLATES
+        .iter()
+        .filter_map(|template| {
+            let template_lowercase = template.to_lowercase();
+            let sentences = template.split(". ").count();
+            starts
+                .iter()
+                .map(|&start| (start, sentence_end(text, start, sentences)))
+                .filter(|&(_, end)| end >= position)
+                .map(|(start, end)| {
+                    let clause = text[start..end].trim().trim_end_matches('.').to_lowercase();
+                    edit_distance(&clause, &template_lowercase)
+                })
+                .min()
+                .map(|distance| (distance, template))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .filter(|(distance, template)| *distance > 0 && *distance * 2 < template.len())
+        .map(|(_, template)| template.to_string())
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Returns the start of the clause containing `position`, i.e. the position
+/// after the closest preceding sentence break, colon, comma or `$br`.
+fn clause_start(text: &str, position: usize) -> usize {
+    let before = &text[..position];
+    let punctuation = before.rfind(['.', ':', ',']).map(|i| i + 1);
+    let paragraph = before.rfind("$br").map(|i| i + "$br".len());
+    punctuation.max(paragraph).unwrap_or(0)
+}
+
+/// Returns the end of the `count`th sentence starting at `position`,
+/// including its period.
+fn sentence_end(text: &str, position: usize, count: usize) -> usize {
+    let mut end = position;
+    for _ in 0..count {
+        match text[end..].find('.') {
+            Some(i) => end += i + 1,
+            None => return text.len(),
+        }
+    }
+    end
+}
+
+/// Returns the end of the word starting at `position`. The word always
+/// includes the character at `position`.
+fn word_end(text: &str, position: usize) -> usize {
+    text[position..]
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| c.is_whitespace() || matches!(c, '.' | ',' | ':'))
+        .map_or(text.len(), |(i, _)| position + i)
+}
@@ -1,7 +1,8 @@
-use ability_data::ability::Ability;
+use ability_data::ability::{Ability, AbilityKind};
 use chumsky::prelude::*;
 
-use crate::parser_utils::{phrase, ErrorType};
+use crate::ability_spans::{SpanRecorder, SpannedAbility};
+use crate::parser_utils::{literal, phrase, ErrorType};
 use crate::{
     activated_ability_parser, effect_parser, static_ability_parser, triggered_ability_parser,
 };
@@ -9,28 +10,119 @@ use crate::{
 /// Takes a string containing card rules text and parses it into a
 /// Vec<[Ability]> data structure.
 ///
-/// The provided text must be all lowercase.
-pub fn parse(text: &str) -> ParseResult<Vec<Ability>, Rich<char>> {
+/// Keywords are matched without regard to case, so card text can be passed
+/// exactly as written. Error spans refer to positions in `text`.
+///
+/// Parsing recovers from errors at `$br` and sentence boundaries: the output
+/// contains every ability which could be parsed, and an error is reported for
+/// each sentence which could not.
+pub fn parse(text: &str) -> ParseResult<Vec<Ability>, Rich<'_, char>> {
     parser().parse(text)
 }
 
-fn parser<'a>() -> impl Parser<'a, &'a str, Vec<Ability>, ErrorType<'a>> {
-    let flavor_text = just("{flavor:").then(none_of("}").repeated()).then(just("}")).padded();
-    let reminder_text = just("{reminder:").then(none_of("}").repeated()).then(just("}")).padded();
+/// Parses card rules text like [parse], additionally recording the location
+/// in `text` of each ability and of the effects, costs, conditions and
+/// predicates within it.
+pub fn parse_with_spans(text: &str) -> ParseResult<Vec<SpannedAbility>, Rich<'_, char>> {
+    parser()
+        .map_with(|abilities, e| {
+            let text = e.slice();
+            e.state().annotate(text, abilities)
+        })
+        .parse_with_state(text, &mut SpanRecorder::enabled())
+}
 
+/// Returns the kind of ability written in the paragraph of `text` containing
+/// `position`.
+///
+/// The paragraph is parsed as each kind of ability in turn, and the kind whose
+/// parser gets furthest through the text is returned. This identifies the kind
+/// of ability a paragraph was intended to be even if it fails to parse.
+pub fn ability_kind(text: &str, position: usize) -> AbilityKind {
+    let start = text[..position].rfind("$br").map_or(0, |i| i + "$br".len());
+    let end = text[position..].find("$br").map_or(text.len(), |i| position + i);
+    let paragraph = &text[start..end];
+    [
+        (AbilityKind::Triggered, progress(triggered_ability_parser::parser().parse(paragraph))),
+        (AbilityKind::Activated, progress(activated_ability_parser::parser().parse(paragraph))),
+        (AbilityKind::Event, progress(effect_parser::effect().parse(paragraph))),
+        (AbilityKind::Static, progress(static_ability_parser::parser().parse(paragraph))),
+    ]
+    .into_iter()
+    .rev()
+    .max_by_key(|(_, progress)| *progress)
+    .map(|(kind, _)| kind)
+    .unwrap_or(AbilityKind::Event)
+}
+
+/// Returns how far parsing got before the first error, or [usize::MAX] if
+/// there were no errors.
+fn progress<T>(result: ParseResult<T, Rich<'_, char>>) -> usize {
+    result.errors().map(|error| error.span().start).max().unwrap_or(usize::MAX)
+}
+
+fn parser<'a>() -> impl Parser<'a, &'a str, Vec<Ability>, ErrorType<'a>> {
     let single_ability = choice((
         triggered_ability_parser::parser().map(Ability::Triggered),
         activated_ability_parser::parser().map(Ability::Activated),
         effect_parser::effect().map(Ability::Event),
         static_ability_parser::parser().then_ignore(phrase(".")).map(Ability::Static),
     ))
-    .then_ignore(reminder_text.or_not());
+    .then_ignore(reminder_text().or_not())
+    .map_with(|ability, e| {
+        let span = e.span().into_range();
+        if e.state().is_enabled() {
+            e.state().record_ability(span);
+        }
+        ability
+    })
+    .boxed();
 
-    single_ability
+    // If a paragraph fails to parse, fall back to parsing it one sentence at a
+    // time, skipping over sentences which cannot be parsed.
+    let paragraph_end =
+        choice((phrase("$br").ignored(), flavor_text().or_not().then(end()).ignored())).rewind();
+    let sentence = flavor_text().not().ignore_then(
+        single_ability.clone().map(Some).recover_with(via_parser(skip_sentence().to(None))),
+    );
+    let paragraph = choice((
+        single_ability.then_ignore(paragraph_end).map(|ability| vec![ability]),
+        sentence
+            .repeated()
+            .at_least(1)
+            .collect::<Vec<_>>()
+            .map(|abilities| abilities.into_iter().flatten().collect()),
+    ));
+
+    paragraph
         .separated_by(phrase("$br"))
         .at_least(1)
-        .collect()
-        .then_ignore(flavor_text.or_not())
+        .collect::<Vec<Vec<_>>>()
+        .map(|paragraphs| paragraphs.concat())
+        .then_ignore(flavor_text().or_not())
         .then_ignore(end())
         .boxed()
 }
+
+fn flavor_text<'a>() -> impl Parser<'a, &'a str, (), ErrorType<'a>> {
+    literal("{flavor:").then(none_of("}").repeated()).then(just("}")).padded().ignored().boxed()
+}
+
+fn reminder_text<'a>() -> impl Parser<'a, &'a str, (), ErrorType<'a>> {
+    literal("{reminder:").then(none_of("}").repeated()).then(just("}")).padded().ignored().boxed()
+}
+
+/// Skips the remainder of a sentence which could not be parsed, stopping after
+/// its period and any reminder text, or before the next `$br`.
+fn skip_sentence<'a>() -> impl Parser<'a, &'a str, (), ErrorType<'a>> {
+    let braced = just("{").then(none_of("}").repeated()).then(just("}")).ignored();
+    let other = phrase("$br").not().ignore_then(none_of(".")).ignored();
+    choice((braced, other))
+        .repeated()
+        .then(just(".").or_not())
+        .then(reminder_text().or_not())
+        .to_slice()
+        .filter(|skipped: &&str| !skipped.trim().is_empty())
+        .ignored()
+        .boxed()
+}
@@ -0,0 +1,240 @@
+use ability_data::ability::Ability;
+use ability_data::activated_ability::ActivatedAbility;
+use ability_data::cost::Cost;
+use ability_data::effect::Effect;
+use ability_data::keyword::Keyword;
+use ability_data::predicate::Predicate;
+use ability_data::static_ability::{StandardStaticAbility, StaticAbility};
+use ability_data::trigger_event::TriggerEvent;
+use ability_data::triggered_ability::TriggeredAbility;
+use core_data::numerics::Energy;
+
+use crate::cost_serializer::{serialize_cost, serialize_inflected_cost};
+use crate::effect_serializer::{
+    serialize_condition, serialize_effect, serialize_effect_clause, serialize_standard_effect,
+};
+use crate::predicate_serializer::{
+    serialize_card_predicate, serialize_card_predicate_plural, serialize_counted_predicate,
+    serialize_predicate, serialize_quantity_expression, serialize_your_action,
+};
+use crate::serializer_utils::{a_or_an, capitalize, ordinal_number};
+
+/// Takes a list of [Ability] data structures and serializes them into
+/// canonical card rules text, separating abilities with `$br`.
+///
+/// This is the inverse of [crate::ability_parser::parse]: parsing the result
+/// produces the same abilities.
+pub fn serialize(abilities: &[Ability]) -> String {
+    abilities.iter().map(serialize_ability).collect::<Vec<_>>().join(" $br ")
+}
+
+/// Serializes a single ability as a paragraph of card rules text.
+pub fn serialize_ability(ability: &Ability) -> String {
+    match ability {
+        Ability::Event(effect) => serialize_effect(effect),
+        Ability::Static(ability) => format!("{}.", capitalize(&serialize_static_ability(ability))),
+        Ability::Activated(ability) => serialize_activated_ability(ability),
+        Ability::Triggered(ability) => serialize_triggered_ability(ability),
+    }
+}
+
+/// Serializes an activated ability, for example "$activated $2: Draw a card."
+pub fn serialize_activated_ability(ability: &ActivatedAbility) -> String {
+    let options = ability.options.clone().unwrap_or_default();
+    let keyword = match (options.is_fast, options.is_multi) {
+        (false, false) => "$activated",
+        (true, false) => "$fastActivated",
+        (false, true) => "$multiActivated",
+        (true, true) => "$fastMultiActivated",
+    };
+    let immediate = if options.is_immediate { "$immediate " } else { "" };
+    let costs = ability.costs.iter().map(serialize_cost).collect::<Vec<_>>().join(", ");
+    let effect = serialize_effect(&ability.effect);
+    if costs.is_empty() {
+        format!("{immediate}{keyword}: {effect}")
+    } else {
+        format!("{immediate}{keyword} {}: {effect}", capitalize(&costs))
+    }
+}
+
+/// Serializes a triggered ability, for example "Whenever you play an event,
+/// copy it."
+pub fn serialize_triggered_ability(ability: &TriggeredAbility) -> String {
+    if let TriggerEvent::Keywords(_) = &ability.trigger {
+        return format!(
+            "{}: {}",
+            serialize_trigger_event(&ability.trigger),
+            serialize_effect(&ability.effect)
+        );
+    }
+
+    let once_per_turn = ability.options.as_ref().is_some_and(|options| options.once_per_turn);
+    let word = match &ability.trigger {
+        TriggerEvent::EndOfYourTurn => "at",
+        TriggerEvent::DrawAllCardsInCopyOfDeck => "when",
+        TriggerEvent::Abandon(Predicate::This)
+        | TriggerEvent::Banished(Predicate::This)
+        | TriggerEvent::Discard(Predicate::This)
+        | TriggerEvent::Dissolved(Predicate::This)
+        | TriggerEvent::Materialize(Predicate::This)
+        | TriggerEvent::Play(Predicate::This)
+        | TriggerEvent::PlayFromHand(Predicate::This) => "when",
+        _ if once_per_turn => "when",
+        _ => "whenever",
+    };
+    capitalize(&format!(
+        "{}{word} {}, {}",
+        if once_per_turn { "once per turn, " } else { "" },
+        serialize_trigger_event(&ability.trigger),
+        serialize_effect_clause(&ability.effect)
+    ))
+}
+
+/// Serializes the event which causes a triggered ability to trigger, for
+/// example "you materialize a character".
+pub fn serialize_trigger_event(event: &TriggerEvent) -> String {
+    match event {
+        TriggerEvent::Abandon(p) => format!("you abandon {}", serialize_your_action(p)),
+        TriggerEvent::Banished(p @ (Predicate::Your(_) | Predicate::Another(_))) => {
+            format!("you banish {}", serialize_your_action(p))
+        }
+        TriggerEvent::Banished(p) => format!("{} is banished", serialize_predicate(p)),
+        TriggerEvent::Discard(p) => format!("you discard {}", serialize_your_action(p)),
+        TriggerEvent::Dissolved(p) => format!("{} is dissolved", serialize_predicate(p)),
+        TriggerEvent::DrawAllCardsInCopyOfDeck => {
+            "you draw all of the cards in a copy of your deck".to_string()
+        }
+        TriggerEvent::EndOfYourTurn => "the end of your turn".to_string(),
+        TriggerEvent::GainEnergy => "you gain energy".to_string(),
+        TriggerEvent::Keywords(keywords) => keywords
+            .iter()
+            .map(|keyword| Keyword::from(keyword.clone()).ability_text())
+            .collect::<Vec<_>>()
+            .join(", "),
+        TriggerEvent::Materialize(p) => format!("you materialize {}", serialize_your_action(p)),
+        TriggerEvent::MaterializeNthThisTurn(p, n) => {
+            format!("you materialize your {} {} in a turn", ordinal_number(*n), match p {
+                Predicate::Your(p) => serialize_card_predicate(p),
+                _ => serialize_predicate(p),
+            })
+        }
+        TriggerEvent::Play(p) => format!("you play {}", serialize_your_action(p)),
+        TriggerEvent::PlayFromHand(p) => {
+            format!("you play {} from your hand", serialize_your_action(p))
+        }
+    }
+}
+
+/// Serializes a static ability without a trailing period, for example "events
+/// cost you $2 more".
+pub fn serialize_static_ability(ability: &StaticAbility) -> String {
+    match ability {
+        StaticAbility::StaticAbility(ability) => serialize_standard_static_ability(ability),
+        StaticAbility::WithOptions(options) => match &options.condition {
+            Some(condition) => format!(
+                "if {}, {}",
+                serialize_condition(condition),
+                serialize_standard_static_ability(&options.ability)
+            ),
+            None => serialize_standard_static_ability(&options.ability),
+        },
+    }
+}
+
+fn serialize_standard_static_ability(ability: &StandardStaticAbility) -> String {
+    match ability {
+        StandardStaticAbility::CardsInYourVoidHaveReclaim { .. } => {
+            "cards in your void have {kw: reclaim}".to_string()
+        }
+        StandardStaticAbility::CharactersInHandHaveFast => {
+            "characters in your hand have '$fast'".to_string()
+        }
+        StandardStaticAbility::CostReductionForEach { reduction, quantity } => format!(
+            "this character costs ${reduction} less to play for each {}",
+            serialize_quantity_expression(quantity, false)
+        ),
+        StandardStaticAbility::DisableEnemyMaterializedAbilities => {
+            "disable the \"$materialized\" abilities of enemy characters".to_string()
+        }
+        StandardStaticAbility::EnemyCardsCostIncrease { matching, increase } => format!(
+            "the enemy's {} cost ${increase} more",
+            serialize_card_predicate_plural(matching)
+        ),
+        StandardStaticAbility::HasAllCharacterTypes => {
+            "this character has all character types".to_string()
+        }
+        StandardStaticAbility::JudgmentTriggersWhenMaterialized { predicate } => format!(
+            "the '$judgment' ability of {} triggers when you materialize them",
+            serialize_counted_predicate(predicate, true)
+        ),
+        StandardStaticAbility::OncePerTurnPlayFromVoid { matching } => format!(
+            "once per turn, you may play {} from your void",
+            a_or_an(&serialize_card_predicate(matching))
+        ),
+        StandardStaticAbility::PlayForAlternateCost(alternate) => {
+            if alternate.additional_cost == Cost::NoCost && alternate.if_you_do.is_none() {
+                format!("this character costs ${}", alternate.energy_cost)
+            } else {
+                format!(
+                    "you may play this character for ${}{}",
+                    alternate.energy_cost,
+                    additional_cost(&alternate.additional_cost, &alternate.if_you_do)
+                )
+            }
+        }
+        StandardStaticAbility::PlayFromVoid(play) => format!(
+            "you may play this character from your void{}{}",
+            play.energy_cost.map(|Energy(cost)| format!(" for ${cost}")).unwrap_or_default(),
+            additional_cost(&play.additional_cost, &play.if_you_do)
+        ),
+        StandardStaticAbility::PlayOnlyFromVoid => {
+            "you may only play this character from your void".to_string()
+        }
+        StandardStaticAbility::Reclaim { cost: Some(cost) } => {
+            format!("{{kw: reclaim}} {}", serialize_cost(cost))
+        }
+        StandardStaticAbility::Reclaim { cost: None } => "{kw: reclaim}".to_string(),
+        StandardStaticAbility::SparkBonusYourCharacters { matching, added_spark } => format!(
+            "{} you control have +{added_spark} spark",
+            serialize_card_predicate_plural(matching)
+        ),
+        StandardStaticAbility::SparkBonusOtherCharacters { matching, added_spark } => format!(
+            "other {} you control have +{added_spark} spark",
+            serialize_card_predicate_plural(matching)
+        ),
+        StandardStaticAbility::SparkEqualToPredicateCount { predicate } => format!(
+            "this character's spark is equal to the number of {}",
+            serialize_counted_predicate(predicate, true)
+        ),
+        StandardStaticAbility::YouMayLookAtTopCardOfYourDeck => {
+            "you may look at the top card of your deck".to_string()
+        }
+        StandardStaticAbility::YouMayPlayFromTopOfDeck { matching } => format!(
+            "you may play {} from the top of your deck",
+            serialize_card_predicate_plural(matching)
+        ),
+        StandardStaticAbility::YourCardsCostIncrease { matching, reduction } => {
+            format!("{} cost you ${reduction} more", serialize_card_predicate_plural(matching))
+        }
+        StandardStaticAbility::YourCardsCostReduction { matching, reduction } => {
+            format!("{} cost you ${reduction} less", serialize_card_predicate_plural(matching))
+        }
+    }
+}
+
+/// Writes the additional cost and follow-up effect of playing a card via a
+/// static ability, e.g. " by abandoning a character. If you do, draw a card".
+fn additional_cost(cost: &Cost, if_you_do: &Option<Effect>) -> String {
+    let mut result = String::new();
+    if *cost != Cost::NoCost {
+        result.push_str(&format!(" by {}", serialize_inflected_cost(cost)));
+    }
+    if let Some(effect) = if_you_do {
+        let effect = match effect {
+            Effect::Effect(effect) => serialize_standard_effect(effect),
+            effect => serialize_effect_clause(effect).trim_end_matches('.').to_string(),
+        };
+        result.push_str(&format!(". If you do, {effect}"));
+    }
+    result
+}
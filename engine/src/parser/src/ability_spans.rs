@@ -0,0 +1,133 @@
+use std::cmp::Reverse;
+use std::ops::Range;
+
+use ability_data::ability::Ability;
+use ability_data::condition::Condition;
+use ability_data::cost::Cost;
+use ability_data::predicate::{CardPredicate, Predicate};
+use ability_data::standard_effect::StandardEffect;
+use chumsky::input::{Checkpoint, Cursor, Input};
+use chumsky::inspector::Inspector;
+use serde::Serialize;
+
+/// An [Ability] along with the location in the source text of the ability
+/// and of the effects, costs, conditions and predicates within it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpannedAbility {
+    pub ability: Ability,
+
+    /// Byte range of the ability's text, including its trailing period.
+    pub span: Range<usize>,
+
+    /// Parts of this ability in the order they appear in the text.
+    ///
+    /// Parts which are implied rather than written, such as the default
+    /// quantity of a "materialize a {kw: silent} copy" effect, are omitted.
+    pub nodes: Vec<SpannedNode>,
+}
+
+/// A part of an ability along with the byte range of its text.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpannedNode {
+    pub node: AstNode,
+    pub span: Range<usize>,
+}
+
+/// Parts of an ability which can have source spans.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AstNode {
+    Effect(StandardEffect),
+    Cost(Cost),
+    Condition(Condition),
+    Predicate(Predicate),
+    CardPredicate(CardPredicate),
+}
+
+/// Parser state which records the spans of parsed abilities and their parts.
+///
+/// Spans are recorded as each part is parsed. When the parser backtracks,
+/// spans recorded by the alternative which failed are discarded, so the
+/// recorded spans are exactly those of the parts of the final abilities.
+#[derive(Debug, Default)]
+pub struct SpanRecorder {
+    enabled: bool,
+    abilities: Vec<Range<usize>>,
+    nodes: Vec<(AstNode, Range<usize>)>,
+}
+
+impl SpanRecorder {
+    pub fn enabled() -> Self {
+        Self { enabled: true, ..Self::default() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn record_ability(&mut self, span: Range<usize>) {
+        self.abilities.push(span);
+    }
+
+    pub fn record(&mut self, node: AstNode, span: Range<usize>) {
+        self.nodes.push((node, span));
+    }
+
+    /// Attaches recorded spans to the abilities parsed from `text`.
+    ///
+    /// Abilities are recorded in the order they are parsed, and each node
+    /// belongs to the ability whose span contains it.
+    pub fn annotate(&self, text: &str, abilities: Vec<Ability>) -> Vec<SpannedAbility> {
+        abilities
+            .into_iter()
+            .zip(&self.abilities)
+            .map(|(ability, span)| {
+                let mut nodes = self
+                    .nodes
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, node))| node.start >= span.start && node.end <= span.end)
+                    .collect::<Vec<_>>();
+
+                // Parts are recorded after the parts within them, so a part
+                // with the same span as another part contains it.
+                nodes.sort_by_key(|(i, (_, node))| (node.start, Reverse(node.end), Reverse(*i)));
+                let nodes = nodes
+                    .into_iter()
+                    .map(|(_, (node, node_span))| SpannedNode {
+                        node: node.clone(),
+                        span: trim(text, node_span.clone()),
+                    })
+                    .collect();
+                SpannedAbility { ability, span: trim(text, span.clone()), nodes }
+            })
+            .collect()
+    }
+}
+
+impl<'a, I: Input<'a>> Inspector<'a, I> for SpanRecorder {
+    type Checkpoint = (usize, usize);
+
+    fn on_token(&mut self, _: &I::Token) {}
+
+    fn on_save<'parse>(&self, _: &Cursor<'a, 'parse, I>) -> Self::Checkpoint {
+        (self.abilities.len(), self.nodes.len())
+    }
+
+    fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'a, 'parse, I, Self::Checkpoint>) {
+        let (abilities, nodes) = *marker.inspector();
+        self.abilities.truncate(abilities);
+        self.nodes.truncate(nodes);
+    }
+}
+
+/// Removes leading and trailing whitespace from a span, which is included by
+/// padded parsers.
+fn trim(text: &str, span: Range<usize>) -> Range<usize> {
+    let slice = &text[span.clone()];
+    let start = span.start + (slice.len() - slice.trim_start().len());
+    let end = span.end - (slice.len() - slice.trim_end().len());
+    start..end.max(start)
+}
@@ -1,25 +1,38 @@
 use ability_data::predicate::{CardPredicate, Operator};
-use chumsky::prelude::choice;
+use chumsky::prelude::*;
 use chumsky::Parser;
 use core_data::character_type::CharacterType;
 use core_data::numerics::{Energy, Spark};
 
-use crate::parser_utils::{a_or_an, numeric, phrase, ErrorType};
+use crate::ability_spans::AstNode;
+use crate::parser_utils::{a_or_an, numeric, phrase, spanned, ErrorType};
 
 pub fn parser<'a>() -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
-    choice((
-        character_with_cost_compared_to_controlled(),
-        character_with_cost_compared_to_abandoned(),
-        character_with_spark_compared_to_abandoned_this_turn(),
-        character_with_spark_compared_to_abandoned(),
-        fast_card(),
-        card_with_cost(),
-        non_recursive_predicate(),
-    ))
-    .boxed()
+    // Constructed once and shared, since building the predicate parser is a
+    // significant part of the cost of building the ability parser.
+    let base = non_recursive_predicate();
+    let predicate = choice((
+        character_with_cost_compared_to_controlled(base.clone()),
+        character_with_cost_compared_to_abandoned(base.clone()),
+        character_with_spark_compared_to_abandoned_this_turn(base.clone()),
+        character_with_spark_compared_to_abandoned(base.clone()),
+        fast_card(base.clone()),
+        with_filters(base.clone()),
+        base,
+    ));
+    let predicates = predicate.separated_by(phrase("or")).at_least(1).collect::<Vec<_>>().map(
+        |mut predicates| {
+            if predicates.len() == 1 {
+                predicates.remove(0)
+            } else {
+                CardPredicate::Or(predicates)
+            }
+        },
+    );
+    spanned(predicates, AstNode::CardPredicate).labelled("a card predicate")
 }
 
-fn non_recursive_predicate<'a>() -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
+fn non_recursive_predicate<'a>() -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> + Clone {
     choice((
         character_with_spark(),
         character_with_materialized_ability(),
@@ -28,10 +41,16 @@ fn non_recursive_predicate<'a>() -> impl Parser<'a, &'a str, CardPredicate, Erro
         phrase("character that is not")
             .ignore_then(a_or_an())
             .ignore_then(character_type())
-            .map(CardPredicate::NotCharacterType),
-        phrase("characters that are not")
-            .ignore_then(character_type())
-            .map(CardPredicate::NotCharacterType),
+            .map(not_character_type),
+        phrase("characters that are not").ignore_then(character_type()).map(not_character_type),
+        non_card_type(),
+        card_type(),
+    ))
+    .boxed()
+}
+
+fn card_type<'a>() -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
+    choice((
         choice((phrase("cards"), phrase("card"))).to(CardPredicate::Card),
         character().to(CardPredicate::Character),
         choice((phrase("events"), phrase("event"))).to(CardPredicate::Event),
@@ -40,24 +59,67 @@ fn non_recursive_predicate<'a>() -> impl Parser<'a, &'a str, CardPredicate, Erro
     .boxed()
 }
 
-fn card_with_cost<'a>() -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
-    non_recursive_predicate()
-        .then(numeric("with cost $", Energy, ""))
-        .then(
-            choice((
-                phrase("or less").to(Operator::OrLess),
-                phrase("or more").to(Operator::OrMore),
-            ))
-            .or_not(),
-        )
-        .map(|((target, cost), operator)| CardPredicate::CardWithCost {
-            target: Box::new(target),
-            cost_operator: operator.unwrap_or(Operator::Exactly),
-            cost,
+/// Characters which are not of the given type, e.g. "character that is not a
+/// {cardtype: warrior}".
+fn not_character_type(character_type: CharacterType) -> CardPredicate {
+    CardPredicate::And(vec![
+        CardPredicate::Character,
+        CardPredicate::Not(Box::new(CardPredicate::CharacterType(character_type))),
+    ])
+}
+
+/// Parses a card type excluding another type, e.g. "non-{cardtype: warrior}
+/// character".
+fn non_card_type<'a>() -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
+    phrase("non-")
+        .ignore_then(choice((character_type().map(CardPredicate::CharacterType), card_type())))
+        .then(card_type())
+        .map(|(excluded, card_type)| {
+            CardPredicate::And(vec![card_type, CardPredicate::Not(Box::new(excluded))])
         })
         .boxed()
 }
 
+/// Parses a card predicate followed by filters on its cost or spark, e.g.
+/// "event with cost $2 or less and spark 1 or more".
+///
+/// Produces a [CardPredicate::And] of the base predicate and each filter.
+fn with_filters<'a>(
+    base: impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> + Clone + 'a,
+) -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
+    base.then_ignore(phrase("with"))
+        .then(filter().separated_by(phrase("and")).at_least(1).collect::<Vec<_>>())
+        .map(|(target, mut filters)| {
+            let mut predicates = match target {
+                CardPredicate::And(predicates) => predicates,
+                target => vec![target],
+            };
+            predicates.append(&mut filters);
+            CardPredicate::And(predicates)
+        })
+        .boxed()
+}
+
+fn filter<'a>() -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
+    choice((
+        numeric("cost $", Energy, "")
+            .then(operator())
+            .map(|(cost, operator)| CardPredicate::WithCost(cost, operator)),
+        numeric("spark", Spark, "")
+            .then(operator())
+            .map(|(spark, operator)| CardPredicate::WithSpark(spark, operator)),
+    ))
+    .boxed()
+}
+
+/// Parses an optional "or less" or "or more" suffix.
+fn operator<'a, T: Clone + 'a>() -> impl Parser<'a, &'a str, Operator<T>, ErrorType<'a>> {
+    choice((phrase("or less").to(Operator::OrLess), phrase("or more").to(Operator::OrMore)))
+        .or_not()
+        .map(|operator| operator.unwrap_or(Operator::Exactly))
+        .boxed()
+}
+
 fn character_with_spark<'a>() -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
     character()
         .ignore_then(numeric("with spark", Spark, ""))
@@ -65,24 +127,26 @@ fn character_with_spark<'a>() -> impl Parser<'a, &'a str, CardPredicate, ErrorTy
             phrase("or less").to(Operator::OrLess),
             phrase("or more").to(Operator::OrMore),
         )))
-        .map(|(spark, operator)| CardPredicate::CharacterWithSpark(spark, operator))
+        .map(|(spark, operator)| {
+            CardPredicate::And(vec![
+                CardPredicate::Character,
+                CardPredicate::WithSpark(spark, operator),
+            ])
+        })
         .boxed()
 }
 
 fn character_with_cost_compared_to_controlled<'a>(
+    base: impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> + Clone + 'a,
 ) -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
-    non_recursive_predicate()
+    base.clone()
         .then_ignore(phrase("with cost"))
         .then(choice((
             phrase("less than or equal to").to(Operator::OrLess),
             phrase("equal to").to(Operator::Exactly),
             phrase("greater than or equal to").to(Operator::OrMore),
         )))
-        .then(
-            phrase("the number of")
-                .ignore_then(non_recursive_predicate())
-                .then_ignore(phrase("you control")),
-        )
+        .then(phrase("the number of").ignore_then(base.clone()).then_ignore(phrase("you control")))
         .map(|((target, cost_operator), count_matching)| {
             CardPredicate::CharacterWithCostComparedToControlled {
                 target: Box::new(target),
@@ -94,9 +158,9 @@ fn character_with_cost_compared_to_controlled<'a>(
 }
 
 fn character_with_cost_compared_to_abandoned<'a>(
+    base: impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> + Clone + 'a,
 ) -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
-    non_recursive_predicate()
-        .then_ignore(phrase("with cost"))
+    base.then_ignore(phrase("with cost"))
         .then(choice((
             numeric("$", Energy, "higher than").map(Operator::HigherBy),
             numeric("$", Energy, "lower than").map(Operator::LowerBy),
@@ -140,9 +204,11 @@ fn character_type<'a>() -> impl Parser<'a, &'a str, CharacterType, ErrorType<'a>
         .boxed()
 }
 
-fn fast_card<'a>() -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
+fn fast_card<'a>(
+    base: impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> + Clone + 'a,
+) -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
     phrase("'$fast'")
-        .ignore_then(non_recursive_predicate())
+        .ignore_then(base)
         .map(|target| CardPredicate::Fast { target: Box::new(target) })
         .boxed()
 }
@@ -152,9 +218,9 @@ fn character<'a>() -> impl Parser<'a, &'a str, &'a str, ErrorType<'a>> {
 }
 
 fn character_with_spark_compared_to_abandoned_this_turn<'a>(
+    base: impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> + Clone + 'a,
 ) -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
-    non_recursive_predicate()
-        .then_ignore(phrase("with spark x"))
+    base.then_ignore(phrase("with spark x"))
         .then(choice((
             phrase("or less").to(Operator::OrLess),
             phrase("or more").to(Operator::OrMore),
@@ -170,9 +236,9 @@ fn character_with_spark_compared_to_abandoned_this_turn<'a>(
 }
 
 fn character_with_spark_compared_to_abandoned<'a>(
+    base: impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> + Clone + 'a,
 ) -> impl Parser<'a, &'a str, CardPredicate, ErrorType<'a>> {
-    non_recursive_predicate()
-        .then_ignore(phrase("with spark"))
+    base.then_ignore(phrase("with spark"))
         .then(choice((
             phrase("less than or equal to").to(Operator::OrLess),
             phrase("equal to").to(Operator::Exactly),
@@ -15,5 +15,6 @@ pub fn parser<'a>() -> impl Parser<'a, &'a str, CollectionExpression, ErrorType<
         phrase("each other").to(CollectionExpression::EachOther),
         phrase("each").to(CollectionExpression::All),
     ))
+    .labelled("a number of cards")
     .boxed()
 }
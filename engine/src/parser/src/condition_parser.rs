@@ -3,11 +3,12 @@ use ability_data::predicate::Predicate;
 use chumsky::prelude::*;
 use chumsky::Parser;
 
+use crate::ability_spans::AstNode;
 use crate::card_predicate_parser;
-use crate::parser_utils::{a_or_an, count, number, numeric, phrase, ErrorType};
+use crate::parser_utils::{a_or_an, count, number, numeric, phrase, spanned, ErrorType};
 
 pub fn parser<'a>() -> impl Parser<'a, &'a str, Condition, ErrorType<'a>> {
-    choice((
+    let conditions = choice((
         phrase("you control")
             .ignore_then(choice((phrase("another").to(1), numeric("", count, "other"))))
             .then(card_predicate_parser::parser())
@@ -24,7 +25,7 @@ pub fn parser<'a>() -> impl Parser<'a, &'a str, Condition, ErrorType<'a>> {
             }),
         numeric("you have", count, "or more cards in your void")
             .map(|count| Condition::CardsInVoidCount { count }),
-        phrase("a")
+        a_or_an()
             .ignore_then(card_predicate_parser::parser())
             .then_ignore(phrase("you controlled dissolved this turn"))
             .map(|predicate| Condition::DissolvedThisTurn {
@@ -43,5 +44,6 @@ pub fn parser<'a>() -> impl Parser<'a, &'a str, Condition, ErrorType<'a>> {
                 .map(|count| Condition::CardsDrawnThisTurn { count }),
         )),
         phrase("this character is in your void").to(Condition::ThisCharacterIsInYourVoid),
-    ))
+    ));
+    spanned(conditions, AstNode::Condition).labelled("a condition")
 }
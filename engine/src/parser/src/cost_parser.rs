@@ -4,15 +4,17 @@ use chumsky::prelude::*;
 use chumsky::Parser;
 use core_data::numerics::Energy;
 
-use crate::parser_utils::{count, number, numeric, phrase, ErrorType};
+use crate::ability_spans::AstNode;
+use crate::parser_utils::{a_or_an, count, number, numeric, phrase, spanned, ErrorType};
 use crate::{card_predicate_parser, collection_expression_parser, determiner_parser};
 
 pub fn parser<'a>() -> impl Parser<'a, &'a str, Cost, ErrorType<'a>> {
-    choice((numeric("$", Energy, "").map(Cost::Energy), standard_cost())).boxed()
+    choice((spanned(numeric("$", Energy, "").map(Cost::Energy), AstNode::Cost), standard_cost()))
+        .labelled("a cost")
 }
 
 pub fn standard_cost<'a>() -> impl Parser<'a, &'a str, Cost, ErrorType<'a>> {
-    choice((
+    let costs = choice((
         numeric("pay $", Energy, "").map(Cost::Energy),
         phrase("banish a card from your void").to(Cost::BanishCardsFromYourVoid(1)),
         numeric("banish", count, "cards from your void").map(Cost::BanishCardsFromYourVoid),
@@ -26,42 +28,47 @@ pub fn standard_cost<'a>() -> impl Parser<'a, &'a str, Cost, ErrorType<'a>> {
             .map(|p| Cost::AbandonCharacters(p, 1)),
         abandon_characters_count(),
         phrase("discard your hand").to(Cost::DiscardHand),
-        phrase("discard a")
+        phrase("discard")
+            .ignore_then(a_or_an())
             .ignore_then(card_predicate_parser::parser())
             .map(|predicate| Cost::DiscardCards(predicate, 1)),
         phrase("discard")
             .ignore_then(number(count))
             .then(card_predicate_parser::parser())
             .map(|(count, predicate)| Cost::DiscardCards(predicate, count)),
-    ))
-    .boxed()
+    ));
+    spanned(costs, AstNode::Cost).labelled("a cost")
 }
 
 /// Alternate phrasing for costs, which are written in static abilities, for
 /// example "You may play this event for $0 by abandoning a character".
 pub fn inflected_additional_cost<'a>() -> impl Parser<'a, &'a str, Cost, ErrorType<'a>> {
-    choice((
+    let costs = choice((
         phrase("banishing another card from your void").to(Cost::BanishCardsFromYourVoid(1)),
         phrase("banishing all other cards from your void").to(Cost::BanishAllCardsFromYourVoid),
         phrase("banishing all cards from your void").to(Cost::BanishAllCardsFromYourVoid),
         phrase("abandoning a dreamscape").to(Cost::AbandonDreamscapes(1)),
         numeric("abandoning", count, "dreamscapes").map(Cost::AbandonDreamscapes),
-        choice((phrase("abandoning a").to(1), numeric("abandoning", count, "")))
-            .then(card_predicate_parser::parser())
-            .map(|(n, predicate)| Cost::AbandonCharacters(Predicate::Your(predicate), n)),
+        choice((
+            phrase("abandoning").ignore_then(a_or_an()).to(1),
+            numeric("abandoning", count, ""),
+        ))
+        .then(card_predicate_parser::parser())
+        .map(|(n, predicate)| Cost::AbandonCharacters(Predicate::Your(predicate), n)),
         phrase("banishing")
             .ignore_then(determiner_parser::your_action())
             .then_ignore(phrase("from your hand"))
             .map(Cost::BanishFromHand),
-        phrase("discarding a")
+        phrase("discarding")
+            .ignore_then(a_or_an())
             .ignore_then(card_predicate_parser::parser())
             .map(|predicate| Cost::DiscardCards(predicate, 1)),
         phrase("discarding")
             .ignore_then(number(count))
             .then(card_predicate_parser::parser())
             .map(|(count, predicate)| Cost::DiscardCards(predicate, count)),
-    ))
-    .boxed()
+    ));
+    spanned(costs, AstNode::Cost).labelled("a cost")
 }
 
 fn abandon_characters_count<'a>() -> impl Parser<'a, &'a str, Cost, ErrorType<'a>> {
@@ -0,0 +1,85 @@
+use ability_data::cost::Cost;
+use ability_data::predicate::Predicate;
+
+use crate::predicate_serializer::{
+    is_plural, serialize_card_predicate, serialize_card_predicate_plural,
+    serialize_collection_expression, serialize_your_action, serialize_your_action_counted,
+};
+use crate::serializer_utils::a_or_an;
+
+/// Serializes a cost as written before the colon of an activated ability, for
+/// example "$2" or "abandon another character".
+pub fn serialize_cost(cost: &Cost) -> String {
+    match cost {
+        Cost::Energy(energy) => format!("${energy}"),
+        _ => serialize_standard_cost(cost),
+    }
+}
+
+/// Serializes a cost as written within an effect, for example "pay $1" in
+/// "you may pay $1 to draw a card".
+pub fn serialize_standard_cost(cost: &Cost) -> String {
+    match cost {
+        Cost::Energy(energy) => format!("pay ${energy}"),
+        Cost::BanishCardsFromYourVoid(1) => "banish a card from your void".to_string(),
+        Cost::BanishCardsFromYourVoid(n) => format!("banish {n} cards from your void"),
+        Cost::BanishCardsFromEnemyVoid(1) => "banish a card from the enemy's void".to_string(),
+        Cost::BanishCardsFromEnemyVoid(n) => format!("banish {n} cards from the enemy's void"),
+        Cost::BanishAllCardsFromYourVoid => "banish all cards from your void".to_string(),
+        Cost::BanishFromHand(predicate) => {
+            format!("banish {} from your hand", serialize_your_action(predicate))
+        }
+        Cost::AbandonACharacterOrDiscardACard => {
+            "abandon a character or discard a card".to_string()
+        }
+        Cost::AbandonDreamscapes(1) => "abandon a dreamscape".to_string(),
+        Cost::AbandonDreamscapes(n) => format!("abandon {n} dreamscapes"),
+        Cost::AbandonCharacters(predicate, 1) => {
+            format!("abandon {}", serialize_your_action(predicate))
+        }
+        Cost::AbandonCharacters(predicate, n) => {
+            format!("abandon {n} {}", serialize_your_action_counted(predicate, true))
+        }
+        Cost::AbandonCharactersCount { target, count } => format!(
+            "abandon {} {}",
+            serialize_collection_expression(count),
+            serialize_your_action_counted(target, is_plural(count))
+        ),
+        Cost::DiscardHand => "discard your hand".to_string(),
+        Cost::DiscardCards(predicate, 1) => {
+            format!("discard {}", a_or_an(&serialize_card_predicate(predicate)))
+        }
+        Cost::DiscardCards(predicate, n) => {
+            format!("discard {n} {}", serialize_card_predicate_plural(predicate))
+        }
+        Cost::NoCost => String::new(),
+    }
+}
+
+/// Serializes a cost in the inflected form used by static abilities, for
+/// example "abandoning a character" in "you may play this event for $0 by
+/// abandoning a character".
+pub fn serialize_inflected_cost(cost: &Cost) -> String {
+    match cost {
+        Cost::BanishCardsFromYourVoid(1) => "banishing another card from your void".to_string(),
+        Cost::BanishAllCardsFromYourVoid => "banishing all cards from your void".to_string(),
+        Cost::AbandonDreamscapes(1) => "abandoning a dreamscape".to_string(),
+        Cost::AbandonDreamscapes(n) => format!("abandoning {n} dreamscapes"),
+        Cost::AbandonCharacters(Predicate::Your(predicate), 1) => {
+            format!("abandoning {}", a_or_an(&serialize_card_predicate(predicate)))
+        }
+        Cost::AbandonCharacters(Predicate::Your(predicate), n) => {
+            format!("abandoning {n} {}", serialize_card_predicate_plural(predicate))
+        }
+        Cost::BanishFromHand(predicate) => {
+            format!("banishing {} from your hand", serialize_your_action(predicate))
+        }
+        Cost::DiscardCards(predicate, 1) => {
+            format!("discarding {}", a_or_an(&serialize_card_predicate(predicate)))
+        }
+        Cost::DiscardCards(predicate, n) => {
+            format!("discarding {n} {}", serialize_card_predicate_plural(predicate))
+        }
+        _ => serialize_standard_cost(cost),
+    }
+}
@@ -2,13 +2,14 @@ use ability_data::predicate::Predicate;
 use chumsky::prelude::*;
 use chumsky::Parser;
 
+use crate::ability_spans::AstNode;
 use crate::card_predicate_parser;
-use crate::parser_utils::{a_or_an, phrase, ErrorType};
+use crate::parser_utils::{a_or_an, phrase, spanned, ErrorType};
 
 /// Parser for expressions describing the target selected for an effect, for
 /// example in "Dissolve an enemy character".
 pub fn target_parser<'a>() -> impl Parser<'a, &'a str, Predicate, ErrorType<'a>> {
-    choice((
+    let predicates = choice((
         phrase("this character").to(Predicate::This),
         phrase("this event").to(Predicate::This),
         phrase("this card").to(Predicate::This),
@@ -36,8 +37,8 @@ pub fn target_parser<'a>() -> impl Parser<'a, &'a str, Predicate, ErrorType<'a>>
         phrase("an enemy").ignore_then(card_predicate_parser::parser()).map(Predicate::Enemy),
         phrase("another").ignore_then(card_predicate_parser::parser()).map(Predicate::AnyOther),
         a_or_an().ignore_then(card_predicate_parser::parser()).map(Predicate::Any),
-    ))
-    .boxed()
+    ));
+    spanned(predicates, AstNode::Predicate).labelled("a target")
 }
 
 /// Parser for 'for each' expressions which count entities matching a predicate
@@ -48,7 +49,7 @@ pub fn for_each_parser<'a>() -> impl Parser<'a, &'a str, Predicate, ErrorType<'a
 /// Parser for expressions describing multiple matching objects, such as in
 /// "banish two [enemy warriors]"
 pub fn counted_parser<'a>() -> impl Parser<'a, &'a str, Predicate, ErrorType<'a>> {
-    choice((
+    let predicates = choice((
         phrase("other")
             .ignore_then(card_predicate_parser::parser())
             .then_ignore(phrase("you control"))
@@ -62,30 +63,30 @@ pub fn counted_parser<'a>() -> impl Parser<'a, &'a str, Predicate, ErrorType<'a>
             .then_ignore(phrase("in the enemy's void"))
             .map(Predicate::EnemyVoid),
         card_predicate_parser::parser().map(Predicate::Any),
-    ))
-    .boxed()
+    ));
+    spanned(predicates, AstNode::Predicate).labelled("a target")
 }
 
 /// Parser for expressions describing multiple matching objects, when the
 /// controler is already implicitly specified, for example in 'Abandon any
 /// number of characters.'
 pub fn your_action_counted_parser<'a>() -> impl Parser<'a, &'a str, Predicate, ErrorType<'a>> {
-    choice((
+    let predicates = choice((
         phrase("other").ignore_then(card_predicate_parser::parser()).map(Predicate::Another),
         card_predicate_parser::parser().map(Predicate::Your),
-    ))
-    .boxed()
+    ));
+    spanned(predicates, AstNode::Predicate).labelled("a target")
 }
 
 /// Parser for expressions where the controller has already been described as
 /// the acting party, for example in "Whenever you materialize <predicate>".
 pub fn your_action<'a>() -> impl Parser<'a, &'a str, Predicate, ErrorType<'a>> {
-    choice((
+    let predicates = choice((
         phrase("this character").to(Predicate::This),
         phrase("this event").to(Predicate::This),
         phrase("another").ignore_then(card_predicate_parser::parser()).map(Predicate::Another),
         phrase("an").ignore_then(card_predicate_parser::parser()).map(Predicate::Your),
         phrase("a").ignore_then(card_predicate_parser::parser()).map(Predicate::Your),
-    ))
-    .boxed()
+    ));
+    spanned(predicates, AstNode::Predicate).labelled("a target")
 }
@@ -1,28 +1,105 @@
 use ability_data::cost::Cost;
-use ability_data::effect::{Effect, EffectWithOptions};
+use ability_data::effect::{Effect, EffectWithOptions, ModalEffect, ModalEffectMode};
 use chumsky::prelude::*;
 use chumsky::Parser;
 
-use crate::parser_utils::{phrase, ErrorType};
-use crate::{condition_parser, cost_parser, standard_effect_parser};
+use crate::ability_spans::AstNode;
+use crate::parser_utils::{literal, phrase, text_number, ErrorType};
+use crate::{condition_parser, cost_parser, standard_effect_parser, x_effect_parser};
 
 pub fn effect<'a>() -> impl Parser<'a, &'a str, Effect, ErrorType<'a>> {
-    single_effect()
-        .repeated()
-        .at_least(1)
-        .collect::<Vec<_>>()
-        .map(|effects| match effects.as_slice() {
-            [effect] => effect.clone().to_effect(),
-            effects => Effect::List(effects.to_vec()),
+    let effects = effect_list();
+    choice((modal_effect(effects.clone()), effects)).boxed()
+}
+
+/// Parses a modal effect, e.g. "Choose one: $br • $1: Draw a card. $br • $3:
+/// Dissolve an enemy character."
+///
+/// Modes are validated via [ModalEffect::validate], reporting an error for
+/// effects which do not offer a meaningful choice.
+fn modal_effect<'a>(
+    effects: impl Parser<'a, &'a str, Effect, ErrorType<'a>> + 'a,
+) -> impl Parser<'a, &'a str, Effect, ErrorType<'a>> {
+    let mode = phrase("$br")
+        .or_not()
+        .ignore_then(phrase("•"))
+        .ignore_then(cost_parser::parser().then_ignore(phrase(":")).or_not())
+        .then(effects)
+        .map(|(cost, effect)| ModalEffectMode { cost, effect });
+    phrase("choose")
+        .ignore_then(text_number())
+        .then_ignore(phrase(":"))
+        .then(mode.repeated().at_least(1).collect::<Vec<_>>())
+        .validate(|(choose, modes), e, emitter| {
+            let modal = ModalEffect { choose, modes };
+            if let Err(error) = modal.validate() {
+                emitter.emit(Rich::custom(e.span(), error));
+            }
+            Effect::Modal(modal)
         })
         .boxed()
 }
 
-fn single_effect<'a>() -> impl Parser<'a, &'a str, EffectWithOptions, ErrorType<'a>> {
+fn effect_list<'a>() -> impl Parser<'a, &'a str, Effect, ErrorType<'a>> + Clone {
+    choice((
+        x_sentence().map(|effects| (effects, false)),
+        single_effect().map(|(effect, then)| (vec![effect], then)),
+    ))
+    .repeated()
+    .at_least(1)
+    .collect::<Vec<_>>()
+    .map(|sentences| {
+        let mut effects = vec![];
+        let mut then = false;
+        for (sentence, continues) in sentences {
+            for (i, mut effect) in sentence.into_iter().enumerate() {
+                effect.then = i == 0 && then;
+                effects.push(effect);
+            }
+            then = continues;
+        }
+        match effects.as_slice() {
+            [effect] => effect.clone().to_effect(),
+            effects => Effect::List(effects.to_vec()),
+        }
+    })
+    .boxed()
+}
+
+/// Parses a single effect, returning true if it is followed by "then" and
+/// another effect in the same sentence.
+fn single_effect<'a>() -> impl Parser<'a, &'a str, (EffectWithOptions, bool), ErrorType<'a>> {
     conditional_effect()
         .or(optional_effect())
         .or(standard_effect_parser::parser().map(EffectWithOptions::new))
-        .then_ignore(choice((just("."), phrase(", then"), phrase("and then"))))
+        .then(choice((just(".").to(false), phrase(", then").to(true), phrase("and then").to(true))))
+        .boxed()
+}
+
+/// Parses a sentence of effects whose amounts are written as "X", followed by
+/// a clause defining X, for example "Draw X cards, then discard X cards,
+/// where X is the energy spent this way."
+fn x_sentence<'a>() -> impl Parser<'a, &'a str, Vec<EffectWithOptions>, ErrorType<'a>> {
+    x_effect_parser::parser()
+        .map_with(|effect, e| (effect, e.span().into_range()))
+        .separated_by(choice((phrase(", then"), phrase("and then"), phrase("then"))))
+        .at_least(1)
+        .collect::<Vec<_>>()
+        .then_ignore(phrase(","))
+        .then(x_effect_parser::where_x_is())
+        .then_ignore(just("."))
+        .map_with(|(effects, x), e| {
+            effects
+                .into_iter()
+                .map(|(effect, span)| {
+                    let effect = effect.bind(x.clone());
+                    if e.state().is_enabled() {
+                        e.state().record(AstNode::Effect(effect.clone()), span);
+                    }
+                    EffectWithOptions::new(effect)
+                })
+                .collect()
+        })
         .boxed()
 }
 
@@ -30,7 +107,7 @@ fn optional_effect<'a>() -> impl Parser<'a, &'a str, EffectWithOptions, ErrorTyp
     phrase("you may")
         .ignore_then(
             cost_parser::parser()
-                .then_ignore(just("to"))
+                .then_ignore(literal("to"))
                 .or_not()
                 .then(standard_effect_parser::parser()),
         )
@@ -38,6 +115,7 @@ fn optional_effect<'a>() -> impl Parser<'a, &'a str, EffectWithOptions, ErrorTyp
             effect: game_effect,
             optional: maybe_cost.or(Some(Cost::NoCost)),
             condition: None,
+            then: false,
         })
         .boxed()
 }
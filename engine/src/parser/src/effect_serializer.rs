@@ -0,0 +1,495 @@
+use std::fmt::Display;
+
+use ability_data::collection_expression::CollectionExpression;
+use ability_data::condition::Condition;
+use ability_data::cost::Cost;
+use ability_data::effect::{Effect, EffectWithOptions, ModalEffect};
+use ability_data::predicate::{CardPredicate, Predicate};
+use ability_data::quantity::Quantity;
+use ability_data::quantity_expression::QuantityExpression;
+use ability_data::standard_effect::StandardEffect;
+use core_data::numerics::Points;
+
+use crate::ability_serializer;
+use crate::cost_serializer::{serialize_cost, serialize_standard_cost};
+use crate::predicate_serializer::{
+    is_plural, serialize_card_predicate, serialize_card_predicate_plural,
+    serialize_collection_expression, serialize_counted_predicate, serialize_predicate,
+    serialize_quantity_expression, serialize_your_action,
+};
+use crate::serializer_utils::{a_or_an, a_or_count_cards, capitalize, text_number};
+
+/// Serializes an effect as one or more complete sentences, for example "Draw a
+/// card. Discard a card."
+pub fn serialize_effect(effect: &Effect) -> String {
+    sentences(effect, true)
+}
+
+/// Serializes an effect which follows an introductory clause, leaving its
+/// first sentence uncapitalized, for example "draw a card." in "whenever you
+/// play a character, draw a card."
+pub fn serialize_effect_clause(effect: &Effect) -> String {
+    sentences(effect, false)
+}
+
+/// Serializes a single effect without a trailing period, for example "draw a
+/// card".
+pub fn serialize_standard_effect(effect: &StandardEffect) -> String {
+    match effect {
+        StandardEffect::AbandonAndGainEnergyForSpark { target, energy_per_spark } => format!(
+            "abandon {} and gain ${energy_per_spark} for each point of spark that character had",
+            serialize_your_action(target)
+        ),
+        StandardEffect::AbandonAtEndOfTurn { target } => {
+            format!("abandon {} at end of turn", serialize_predicate(target))
+        }
+        StandardEffect::BanishCardsFromEnemyVoid { count } => {
+            format!("banish {} from the enemy's void", quantity_cards(count))
+        }
+        StandardEffect::BanishEnemyVoid => "banish the enemy's void".to_string(),
+        StandardEffect::BanishCharacter { target } => {
+            format!("banish {}", serialize_predicate(target))
+        }
+        StandardEffect::BanishCharacterUntilLeavesPlay { target, until_leaves } => format!(
+            "banish {} until {} leaves play",
+            serialize_predicate(target),
+            serialize_predicate(until_leaves)
+        ),
+        StandardEffect::BanishUntilNextMain { target } => format!(
+            "banish {} until the start of your next main phase",
+            serialize_predicate(target)
+        ),
+        StandardEffect::BanishCollection { target, count } => {
+            format!("banish {}", counted_collection(count, target))
+        }
+        StandardEffect::CardsInVoidGainReclaimThisTurn { count, predicate } => format!(
+            "until end of turn, {} {} in your void have {{kw: reclaim}}",
+            serialize_collection_expression(count),
+            if is_plural(count) {
+                serialize_card_predicate_plural(predicate)
+            } else {
+                serialize_card_predicate(predicate)
+            }
+        ),
+        StandardEffect::Copy { target } => format!("copy {}", serialize_predicate(target)),
+        StandardEffect::CopyNextPlayed { matching, times } => {
+            let times = match times {
+                Some(2) => " twice".to_string(),
+                Some(n) => format!(" {} times", text_number(*n)),
+                None => String::new(),
+            };
+            format!("copy the next {} you play this turn{times}", your_card_predicate(matching))
+        }
+        StandardEffect::CreateTriggerUntilEndOfTurn { trigger } => format!(
+            "until end of turn, whenever {}, {}",
+            ability_serializer::serialize_trigger_event(&trigger.trigger),
+            match &trigger.effect {
+                Effect::Effect(effect) => serialize_standard_effect(effect),
+                effect => serialize_effect_clause(effect).trim_end_matches('.').to_string(),
+            }
+        ),
+        StandardEffect::DisableActivatedAbilitiesWhileInPlay { target } => format!(
+            "disable the activated abilities of {} while this character is in play",
+            serialize_predicate(target)
+        ),
+        StandardEffect::DiscardCardFromEnemyHand { predicate } => format!(
+            "look at the enemy's hand. Choose {} from it. The enemy discards that card",
+            a_or_an(&serialize_card_predicate(predicate))
+        ),
+        StandardEffect::DiscardCardFromEnemyHandThenTheyDraw { predicate } => format!(
+            "look at the enemy's hand. You may choose {} from it. The enemy discards that card \
+             and then draws a card",
+            a_or_an(&serialize_card_predicate(predicate))
+        ),
+        StandardEffect::DiscardCards { count } => format!("discard {}", quantity_cards(count)),
+        StandardEffect::Discover { predicate } => {
+            format!("{{kw: discover}} {}", a_or_an(&serialize_card_predicate(predicate)))
+        }
+        StandardEffect::DiscoverAndThenMaterialize { predicate } => format!(
+            "{{kw: discover}} {} and materialize it",
+            a_or_an(&serialize_card_predicate(predicate))
+        ),
+        StandardEffect::DissolveCharacter { target } => {
+            format!("dissolve {}", serialize_predicate(target))
+        }
+        StandardEffect::DissolveCharactersCount { target, count } => {
+            format!("dissolve {}", counted_collection(count, target))
+        }
+        StandardEffect::DissolveCharactersQuantity { target, quantity } => format!(
+            "dissolve {} with cost less than or equal to the number of {}",
+            serialize_predicate(target),
+            serialize_quantity_expression(quantity, true)
+        ),
+        StandardEffect::DoubleYourEnergy => {
+            "double the amount of energy in your energy pool".to_string()
+        }
+        StandardEffect::DrawCards { count } => format!("draw {}", quantity_cards(count)),
+        StandardEffect::DrawMatchingCard { predicate } => {
+            format!("draw {} from your deck", a_or_an(&serialize_card_predicate(predicate)))
+        }
+        StandardEffect::DrawCardsForEach { count, for_each } => format!(
+            "draw {} for each {}",
+            a_or_count_cards(*count),
+            serialize_quantity_expression(for_each, false)
+        ),
+        StandardEffect::EachMatchingGainsSpark { each, gains } => format!(
+            "each {} you control gains +{} spark",
+            serialize_card_predicate(each),
+            amount(gains)
+        ),
+        StandardEffect::EachMatchingGainsSparkUntilNextMain { each, gains } => format!(
+            "each {} you control gains +{} spark until your next main phase",
+            serialize_card_predicate(each),
+            amount(gains)
+        ),
+        StandardEffect::EachPlayerAbandonsCharacters { matching, count } => {
+            format!("each player abandons {}", counted_cards(count, matching))
+        }
+        StandardEffect::EachPlayerDiscardCards { count } => {
+            format!("each player discards {}", quantity_cards(count))
+        }
+        StandardEffect::EnemyGainsPoints { count } => {
+            format!("the enemy gains {}", points(count))
+        }
+        StandardEffect::EnemyGainsPointsEqualToItsSpark => {
+            "the enemy gains $points equal to its spark".to_string()
+        }
+        StandardEffect::EnemyLosesPoints { count } => {
+            format!("the enemy loses {}", points(count))
+        }
+        StandardEffect::Foresee { count } => format!("{{kw: foresee}} {}", amount(count)),
+        StandardEffect::GainControl { target } => {
+            format!("gain control of {}", serialize_predicate(target))
+        }
+        StandardEffect::GainEnergy { gains } => format!("gain ${}", amount(gains)),
+        StandardEffect::GainEnergyForEach { gains, for_each } => {
+            format!("gain ${gains} for each {}", serialize_counted_predicate(for_each, false))
+        }
+        StandardEffect::GainPoints { gains } => format!("gain {}", points(gains)),
+        StandardEffect::GainPointsForEach { gain, for_count } => format!(
+            "gain {} for each {}",
+            points(&Quantity::Fixed(*gain)),
+            serialize_quantity_expression(for_count, false)
+        ),
+        StandardEffect::GainsAegisThisTurn { target } => {
+            format!("{} gains {{kw: aegis}} this turn", serialize_predicate(target))
+        }
+        StandardEffect::GainsReclaimUntilEndOfTurn { target, cost } => format!(
+            "{} gains {{kw: reclaim}}{} until end of turn",
+            serialize_predicate(target),
+            cost.map(|cost| format!(" ${cost}")).unwrap_or_default()
+        ),
+        StandardEffect::GainsSpark { target, gains } => {
+            format!("{} gains +{} spark", serialize_predicate(target), amount(gains))
+        }
+        StandardEffect::GainsSparkForQuantity { target, gains, for_quantity } => format!(
+            "{} gains +{gains} spark for each {}",
+            serialize_predicate(target),
+            serialize_quantity_expression(for_quantity, false)
+        ),
+        StandardEffect::GainsSparkUntilYourNextMainForEach { target, gains, for_each } => format!(
+            "{} gains +{gains} spark until your next main phase for each {} you control",
+            serialize_predicate(target),
+            your_card_predicate(for_each)
+        ),
+        StandardEffect::GainTwiceThatMuchEnergyInstead => {
+            "gain twice that much energy instead".to_string()
+        }
+        StandardEffect::Kindle { amount: kindle } => format!("{{kw: kindle}} {}", amount(kindle)),
+        StandardEffect::LosePoints { loses } => format!("you lose {}", points(loses)),
+        StandardEffect::MaterializeCharacter { target } => {
+            format!("materialize {}", serialize_predicate(target))
+        }
+        StandardEffect::MaterializeCharacterAtEndOfTurn { target } => {
+            format!("materialize {} at end of turn", serialize_predicate(target))
+        }
+        StandardEffect::MaterializeCharacterFromVoid { target } => {
+            format!("materialize {} from your void", a_or_an(&serialize_card_predicate(target)))
+        }
+        StandardEffect::MaterializeRandomFromDeck { count: Quantity::Fixed(1), predicate } => {
+            format!("materialize a random {} from your deck", serialize_card_predicate(predicate))
+        }
+        StandardEffect::MaterializeRandomFromDeck { count, predicate } => format!(
+            "materialize {} random {} from your deck",
+            quantity_number(count),
+            serialize_card_predicate_plural(predicate)
+        ),
+        StandardEffect::MaterializeSilentCopy { target, count, quantity } => {
+            let copies = if *count == Quantity::Fixed(1) {
+                "a {kw: silent} copy".to_string()
+            } else {
+                format!("{} {{kw: silent}} copies", quantity_number(count))
+            };
+            let for_each = if *quantity == QuantityExpression::Matching(target.clone()) {
+                String::new()
+            } else {
+                format!(" for each {}", serialize_quantity_expression(quantity, false))
+            };
+            format!("materialize {copies} of {}{for_each}", serialize_predicate(target))
+        }
+        StandardEffect::Negate { target } => format!("negate {}", serialize_predicate(target)),
+        StandardEffect::PayCost { cost } => serialize_standard_cost(cost),
+        StandardEffect::PutCardsFromYourDeckIntoVoid { count } => {
+            format!("put the top {} cards of your deck into your void", amount(count))
+        }
+        StandardEffect::PutCardsFromVoidOnTopOfDeck { count, matching } => {
+            format!("put {} from your void on top of your deck", counted_cards(count, matching))
+        }
+        StandardEffect::PutOnTopOfEnemyDeck { target } => {
+            format!("put {} on top of the enemy's deck", serialize_predicate(target))
+        }
+        StandardEffect::ReturnCharactersToHandDrawCardForEach { count } => format!(
+            "return {} character you control to hand. Draw a card for each character returned",
+            serialize_collection_expression(count)
+        ),
+        StandardEffect::ReturnFromYourVoidToHand { target } => {
+            format!("return {} from your void to your hand", serialize_your_action(target))
+        }
+        StandardEffect::ReturnFromYourVoidToPlay { target } => {
+            format!("return {} from your void to play", serialize_your_action(target))
+        }
+        StandardEffect::ReturnToHand { target } => {
+            format!("return {} to hand", serialize_predicate(target))
+        }
+        StandardEffect::ShuffleHandAndDeckAndDraw { count } => format!(
+            "each player may shuffle their hand and void into their deck and then draw {} cards",
+            amount(count)
+        ),
+        StandardEffect::SparkBecomes { collection: CollectionExpression::All, matching, spark } => {
+            format!(
+                "the spark of each {} you control becomes {}",
+                serialize_card_predicate(matching),
+                amount(spark)
+            )
+        }
+        StandardEffect::SparkBecomes { collection, matching, spark } => format!(
+            "the spark of {} {} you control becomes {}",
+            serialize_collection_expression(collection),
+            if is_plural(collection) {
+                serialize_card_predicate_plural(matching)
+            } else {
+                serialize_card_predicate(matching)
+            },
+            amount(spark)
+        ),
+        StandardEffect::SpendAllEnergy => "spend all your remaining energy".to_string(),
+        StandardEffect::SpendAllEnergyDissolveEnemy => "spend all your remaining energy. Dissolve \
+                                                        an enemy character with spark less than \
+                                                        or equal to the energy spent this way"
+            .to_string(),
+        StandardEffect::TakeExtraTurn => "take an extra turn after this one".to_string(),
+        StandardEffect::ThenMaterializeIt => "materialize it".to_string(),
+        StandardEffect::TriggerJudgmentAbility {
+            matching,
+            collection: CollectionExpression::All,
+        } => format!(
+            "trigger the '$judgment' ability of each {}",
+            serialize_counted_predicate(matching, false)
+        ),
+        StandardEffect::TriggerJudgmentAbility { matching, collection } => {
+            format!(
+                "trigger the '$judgment' ability of {}",
+                counted_collection(collection, matching)
+            )
+        }
+        StandardEffect::YouWinTheGame => "you win the game".to_string(),
+    }
+}
+
+/// Serializes a condition, for example "you control 2 other {cardtype:
+/// warriors}" in "if you control 2 other {cardtype: warriors}, gain $1".
+pub fn serialize_condition(condition: &Condition) -> String {
+    match condition {
+        Condition::CardsDiscardedThisTurn { count: 1 } => {
+            "you have discarded a card this turn".to_string()
+        }
+        Condition::CardsDiscardedThisTurn { count } => {
+            format!("you have discarded {count} cards this turn")
+        }
+        Condition::CardsDrawnThisTurn { count: 1 } => "you have drawn a card this turn".to_string(),
+        Condition::CardsDrawnThisTurn { count } => {
+            format!("you have drawn {count} or more cards this turn")
+        }
+        Condition::CardsInVoidCount { count } => {
+            format!("you have {count} or more cards in your void")
+        }
+        Condition::DissolvedThisTurn { predicate } => format!(
+            "{} you controlled dissolved this turn",
+            a_or_an(&your_card_predicate(predicate))
+        ),
+        Condition::PredicateCount { count: 1, predicate: Predicate::Another(p) } => {
+            format!("you control another {}", serialize_card_predicate(p))
+        }
+        Condition::PredicateCount { count, predicate: Predicate::Another(p) } => {
+            format!("you control {count} other {}", serialize_card_predicate_plural(p))
+        }
+        Condition::PredicateCount { count: 1, predicate } => {
+            format!("you control {}", a_or_an(&your_card_predicate(predicate)))
+        }
+        Condition::PredicateCount { count, predicate } => format!(
+            "you control {count} {}",
+            serialize_card_predicate_plural(&your_card_predicate_of(predicate))
+        ),
+        Condition::ThisCharacterIsInYourVoid => "this character is in your void".to_string(),
+    }
+}
+
+/// Serializes each effect as a sentence. Consecutive effects whose amounts are
+/// the same "X" are written as a single sentence followed by the definition of
+/// X, for example "Draw X cards, then discard X cards, where X is the energy
+/// spent this way."
+fn sentences(effect: &Effect, capitalize_first: bool) -> String {
+    let effects = match effect {
+        Effect::Effect(effect) => {
+            vec![(serialize_standard_effect(effect), effect.x_quantity(), false)]
+        }
+        Effect::WithOptions(options) => {
+            vec![(with_options(options), options_x_quantity(options), false)]
+        }
+        Effect::List(list) => list
+            .iter()
+            .map(|options| (with_options(options), options_x_quantity(options), options.then))
+            .collect(),
+        Effect::Modal(modal) => return modal_effect(modal, capitalize_first),
+    };
+
+    let mut result = String::new();
+    for (i, chunk) in effects.chunk_by(|(_, a, _), (_, b, _)| a.is_some() && a == b).enumerate() {
+        let texts = chunk.iter().map(|(effect, _, _)| effect.as_str()).collect::<Vec<_>>();
+        let text = match chunk[0].1 {
+            Some(x) => format!("{}, {}", texts.join(", then "), where_x_is(x)),
+            None => texts.join(""),
+        };
+        if i == 0 {
+            result = if capitalize_first { capitalize(&text) } else { text };
+        } else if chunk[0].2 {
+            result.push_str(&format!(", then {text}"));
+        } else {
+            result.push_str(&format!(". {}", capitalize(&text)));
+        }
+    }
+    result.push('.');
+    result
+}
+
+/// Serializes a modal effect with each mode on its own line, e.g. "Choose
+/// one: $br • $1: Draw a card. $br • $3: Dissolve an enemy character."
+fn modal_effect(modal: &ModalEffect, capitalize_first: bool) -> String {
+    let choose = format!("choose {}:", text_number(modal.choose));
+    let mut result = if capitalize_first { capitalize(&choose) } else { choose };
+    for mode in &modal.modes {
+        result.push_str(" $br • ");
+        if let Some(cost) = &mode.cost {
+            result.push_str(&format!("{}: ", serialize_cost(cost)));
+        }
+        result.push_str(&serialize_effect(&mode.effect));
+    }
+    result
+}
+
+fn with_options(options: &EffectWithOptions) -> String {
+    let effect = serialize_standard_effect(&options.effect);
+    let effect = match &options.optional {
+        Some(Cost::NoCost) => format!("you may {effect}"),
+        Some(cost) => format!("you may {} to {effect}", serialize_standard_cost(cost)),
+        None => effect,
+    };
+    match &options.condition {
+        Some(condition) => format!("if {}, {effect}", serialize_condition(condition)),
+        None => effect,
+    }
+}
+
+/// Returns the definition of X used by an effect without options or a
+/// condition, which can share a sentence with other effects using X.
+fn options_x_quantity(options: &EffectWithOptions) -> Option<&QuantityExpression> {
+    if options.optional.is_none() && options.condition.is_none() {
+        options.effect.x_quantity()
+    } else {
+        None
+    }
+}
+
+/// Writes the clause defining X, e.g. "where X is the number of characters
+/// you control".
+fn where_x_is(x: &QuantityExpression) -> String {
+    match x {
+        QuantityExpression::EnergySpentThisWay => {
+            "where X is the energy spent this way".to_string()
+        }
+        x => format!("where X is the number of {}", serialize_quantity_expression(x, true)),
+    }
+}
+
+/// Writes "a card", a number of cards, or "X cards".
+fn quantity_cards(count: &Quantity<u32>) -> String {
+    match count {
+        Quantity::Fixed(count) => a_or_count_cards(*count),
+        Quantity::X(_) => "X cards".to_string(),
+    }
+}
+
+/// Writes a fixed number as a word, e.g. "two", or "X".
+fn quantity_number(quantity: &Quantity<u32>) -> String {
+    match quantity {
+        Quantity::Fixed(number) => text_number(*number),
+        Quantity::X(_) => "X".to_string(),
+    }
+}
+
+/// Writes a fixed amount as a number, or "X".
+fn amount<T: Display>(quantity: &Quantity<T>) -> String {
+    match quantity {
+        Quantity::Fixed(amount) => amount.to_string(),
+        Quantity::X(_) => "X".to_string(),
+    }
+}
+
+/// Writes a collection of objects matching a predicate, e.g. "up to two
+/// enemy characters".
+fn counted_collection(count: &CollectionExpression, target: &Predicate) -> String {
+    format!(
+        "{} {}",
+        serialize_collection_expression(count),
+        serialize_counted_predicate(target, is_plural(count))
+    )
+}
+
+/// Writes "a" or a number of cards matching a predicate, e.g. "two
+/// characters" or "X characters".
+fn counted_cards(count: &Quantity<u32>, matching: &CardPredicate) -> String {
+    if *count == Quantity::Fixed(1) {
+        a_or_an(&serialize_card_predicate(matching))
+    } else {
+        format!("{} {}", quantity_number(count), serialize_card_predicate_plural(matching))
+    }
+}
+
+/// Writes the card predicate of a predicate which is implicitly controlled by
+/// the owner, e.g. "event" in "copy the next event you play this turn".
+fn your_card_predicate(predicate: &Predicate) -> String {
+    serialize_card_predicate(&your_card_predicate_of(predicate))
+}
+
+fn your_card_predicate_of(predicate: &Predicate) -> CardPredicate {
+    match predicate {
+        Predicate::Your(p)
+        | Predicate::Another(p)
+        | Predicate::Any(p)
+        | Predicate::AnyOther(p)
+        | Predicate::Enemy(p)
+        | Predicate::YourVoid(p)
+        | Predicate::EnemyVoid(p) => p.clone(),
+        Predicate::This | Predicate::It | Predicate::Them | Predicate::That => {
+            CardPredicate::Character
+        }
+    }
+}
+
+/// Writes a number of points, e.g. "1 $point", "2 $points" or "X $points".
+fn points(points: &Quantity<Points>) -> String {
+    match points {
+        Quantity::Fixed(Points(1)) => "1 $point".to_string(),
+        points => format!("{} $points", amount(points)),
+    }
+}
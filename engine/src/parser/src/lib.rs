@@ -1,4 +1,11 @@
+pub mod ability_diagnostics;
 pub mod ability_parser;
+pub mod ability_serializer;
+pub mod ability_spans;
+pub mod cost_serializer;
+pub mod effect_serializer;
+pub mod predicate_serializer;
+pub mod serializer_utils;
 
 mod activated_ability_parser;
 mod card_predicate_parser;
@@ -13,3 +20,4 @@ mod standard_effect_parser;
 mod static_ability_parser;
 mod trigger_event_parser;
 mod triggered_ability_parser;
+mod x_effect_parser;
@@ -1,9 +1,58 @@
+use chumsky::error::LabelError;
+use chumsky::input::InputRef;
 use chumsky::prelude::*;
+use chumsky::DefaultExpected;
 
-pub type ErrorType<'a> = extra::Err<Rich<'a, char>>;
+use crate::ability_spans::{AstNode, SpanRecorder};
 
+/// Parser configuration: rich errors, with a [SpanRecorder] as state.
+pub type ErrorType<'a> = extra::Full<Rich<'a, char>, SpanRecorder, ()>;
+
+/// Parses `text` surrounded by optional whitespace, ignoring case.
 pub fn phrase<'a>(text: &'static str) -> impl Parser<'a, &'a str, &'a str, ErrorType<'a>> {
-    just(text).padded().boxed()
+    literal(text).padded().boxed()
+}
+
+/// Parses exactly `text`, ignoring case, and returns the matched slice of the
+/// input.
+///
+/// Card text is written in sentence case with camel case keywords like
+/// "$fastActivated", so all literal matching goes through this function
+/// instead of [just].
+pub fn literal<'a>(text: &'static str) -> impl Parser<'a, &'a str, &'a str, ErrorType<'a>> + Clone {
+    custom(move |inp: &mut InputRef<'a, '_, &'a str, ErrorType<'a>>| {
+        let before = inp.cursor();
+        for expected in text.chars() {
+            match inp.next() {
+                Some(found) if found.eq_ignore_ascii_case(&expected) => {}
+                found => {
+                    return Err(LabelError::<&str, _>::expected_found(
+                        [DefaultExpected::Token(expected.into())],
+                        found.map(Into::into),
+                        inp.span_since(&before),
+                    ));
+                }
+            }
+        }
+        Ok(inp.slice_since(&before..))
+    })
+}
+
+/// Records the source span of the output of `parser` when parsing with
+/// spans, via [crate::ability_parser::parse_with_spans].
+pub fn spanned<'a, T: Clone + 'a>(
+    parser: impl Parser<'a, &'a str, T, ErrorType<'a>> + 'a,
+    node: impl Fn(T) -> AstNode + 'a,
+) -> impl Parser<'a, &'a str, T, ErrorType<'a>> {
+    parser
+        .map_with(move |value: T, e| {
+            let span = e.span().into_range();
+            if e.state().is_enabled() {
+                e.state().record(node(value.clone()), span);
+            }
+            value
+        })
+        .boxed()
 }
 
 /// Standard identity function with a different name for readability
@@ -45,9 +94,9 @@ pub fn a_or_an<'a>() -> impl Parser<'a, &'a str, &'a str, ErrorType<'a>> {
     choice((phrase("an"), phrase("a"))).boxed()
 }
 
-/// Parses "a" or a number followed by a word
+/// Parses "a", "an" or a number
 pub fn a_or_count<'a>() -> impl Parser<'a, &'a str, u32, ErrorType<'a>> {
-    choice((phrase("a").to(1), number(count), text_number()))
+    choice((a_or_an().to(1), number(count), text_number()))
 }
 
 pub fn card_or_cards<'a>() -> impl Parser<'a, &'a str, &'a str, ErrorType<'a>> {
@@ -86,6 +135,9 @@ pub fn ordinal_number<'a>() -> impl Parser<'a, &'a str, u32, ErrorType<'a>> {
         phrase("eighth").to(8),
         phrase("ninth").to(9),
         numeric("", count, "th"),
+        numeric("", count, "st"),
+        numeric("", count, "nd"),
+        numeric("", count, "rd"),
     ))
     .boxed()
 }
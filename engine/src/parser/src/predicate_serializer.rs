@@ -0,0 +1,296 @@
+use ability_data::collection_expression::CollectionExpression;
+use ability_data::predicate::{CardPredicate, CardZone, Controller, Operator, Predicate};
+use ability_data::quantity_expression::QuantityExpression;
+use core_data::character_type::CharacterType;
+
+use crate::serializer_utils::{a_or_an, text_number};
+
+/// Serializes the target selected for an effect, for example "an enemy
+/// character".
+pub fn serialize_predicate(predicate: &Predicate) -> String {
+    match predicate {
+        Predicate::This => "this character".to_string(),
+        Predicate::That => "that character".to_string(),
+        Predicate::It => "it".to_string(),
+        Predicate::Them => "them".to_string(),
+        Predicate::Another(p) => format!("another {} you control", serialize_card_predicate(p)),
+        Predicate::Your(p) => format!("{} you control", a_or_an(&serialize_card_predicate(p))),
+        Predicate::YourVoid(p) => format!("{} in your void", a_or_an(&serialize_card_predicate(p))),
+        Predicate::EnemyVoid(p) => {
+            format!("{} in the enemy's void", a_or_an(&serialize_card_predicate(p)))
+        }
+        Predicate::Enemy(p) => format!("an enemy {}", serialize_card_predicate(p)),
+        Predicate::AnyOther(p) => format!("another {}", serialize_card_predicate(p)),
+        Predicate::Any(p) => a_or_an(&serialize_card_predicate(p)),
+    }
+}
+
+/// Serializes a predicate describing multiple matching objects, for example
+/// "enemy characters" in "banish two enemy characters".
+pub fn serialize_counted_predicate(predicate: &Predicate, plural: bool) -> String {
+    let card_predicate = |p| card_predicate(p, plural);
+    match predicate {
+        Predicate::Another(p) => format!("other {} you control", card_predicate(p)),
+        Predicate::Enemy(p) => format!("enemy {}", card_predicate(p)),
+        Predicate::Your(p) => format!("{} you control", card_predicate(p)),
+        Predicate::YourVoid(p) => format!("{} in your void", card_predicate(p)),
+        Predicate::EnemyVoid(p) => format!("{} in the enemy's void", card_predicate(p)),
+        Predicate::Any(p) => card_predicate(p),
+        _ => serialize_predicate(predicate),
+    }
+}
+
+/// Serializes a predicate where the controller has already been described as
+/// the acting party, for example "a character" in "whenever you materialize a
+/// character".
+pub fn serialize_your_action(predicate: &Predicate) -> String {
+    match predicate {
+        Predicate::This => "this character".to_string(),
+        Predicate::Another(p) => format!("another {}", serialize_card_predicate(p)),
+        Predicate::Your(p) => a_or_an(&serialize_card_predicate(p)),
+        _ => serialize_predicate(predicate),
+    }
+}
+
+/// Serializes a predicate describing multiple matching objects when the
+/// controller has already been described as the acting party, for example
+/// "characters" in "abandon any number of characters".
+pub fn serialize_your_action_counted(predicate: &Predicate, plural: bool) -> String {
+    match predicate {
+        Predicate::Another(p) => format!("other {}", card_predicate(p, plural)),
+        Predicate::Your(p) => card_predicate(p, plural),
+        _ => serialize_counted_predicate(predicate, plural),
+    }
+}
+
+/// Serializes a card predicate in its singular form, for example "character
+/// with cost $2 or less".
+pub fn serialize_card_predicate(predicate: &CardPredicate) -> String {
+    card_predicate(predicate, false)
+}
+
+/// Serializes a card predicate in its plural form, for example "characters
+/// with cost $2 or less".
+pub fn serialize_card_predicate_plural(predicate: &CardPredicate) -> String {
+    card_predicate(predicate, true)
+}
+
+/// Serializes a collection expression, for example "up to two".
+pub fn serialize_collection_expression(collection: &CollectionExpression) -> String {
+    match collection {
+        CollectionExpression::All => "all".to_string(),
+        CollectionExpression::EachOther => "each other".to_string(),
+        CollectionExpression::AnyNumberOf => "any number of".to_string(),
+        CollectionExpression::AllButOne => "all but one".to_string(),
+        CollectionExpression::UpTo(n) => format!("up to {}", text_number(*n)),
+        CollectionExpression::Exactly(n) => text_number(*n),
+        CollectionExpression::OrMore(n) => format!("{} or more", text_number(*n)),
+    }
+}
+
+/// Returns true if objects described by a collection expression should be
+/// written in their plural form.
+pub fn is_plural(collection: &CollectionExpression) -> bool {
+    !matches!(
+        collection,
+        CollectionExpression::EachOther
+            | CollectionExpression::AllButOne
+            | CollectionExpression::Exactly(1)
+            | CollectionExpression::UpTo(1)
+    )
+}
+
+/// Serializes a quantity, for example "card you have drawn this turn" in "gain
+/// $1 for each card you have drawn this turn".
+pub fn serialize_quantity_expression(quantity: &QuantityExpression, plural: bool) -> String {
+    match quantity {
+        QuantityExpression::DiscardedThisTurn(p) => {
+            format!("{} you have discarded this turn", card_predicate(p, plural))
+        }
+        QuantityExpression::CardsDrawnThisTurn(p) => {
+            format!("{} you have drawn this turn", card_predicate(p, plural))
+        }
+        QuantityExpression::PlayedThisTurn(p) => {
+            format!("{} you have played this turn", card_predicate(p, plural))
+        }
+        QuantityExpression::AbandonedThisTurn(p) => {
+            format!("{} you abandoned this turn", card_predicate(p, plural))
+        }
+        QuantityExpression::DissolvedThisTurn(p) => {
+            format!("{} which dissolved this turn", card_predicate(p, plural))
+        }
+        QuantityExpression::AbandonedThisWay(p) => {
+            format!("{} abandoned", card_predicate(p, plural))
+        }
+        QuantityExpression::EnergySpentThisWay => "energy spent this way".to_string(),
+        QuantityExpression::Matching(p) => serialize_counted_predicate(p, plural),
+    }
+}
+
+fn card_predicate(predicate: &CardPredicate, plural: bool) -> String {
+    let noun = |singular: &str| if plural { format!("{singular}s") } else { singular.to_string() };
+    match predicate {
+        CardPredicate::Card => noun("card"),
+        CardPredicate::Character => noun("character"),
+        CardPredicate::Event => noun("event"),
+        CardPredicate::Dream => noun("dream"),
+        CardPredicate::CharacterType(character_type) => character_type_text(character_type, plural),
+        CardPredicate::CharacterWithCostComparedToControlled {
+            target,
+            cost_operator,
+            count_matching,
+        } => format!(
+            "{} with cost {} the number of {} you control",
+            card_predicate(target, plural),
+            comparison(cost_operator),
+            card_predicate(count_matching, true)
+        ),
+        CardPredicate::CharacterWithCostComparedToAbandoned { target, cost_operator } => {
+            let comparison = match cost_operator {
+                Operator::HigherBy(energy) => format!("${energy} higher than"),
+                Operator::LowerBy(energy) => format!("${energy} lower than"),
+                Operator::OrMore => "greater than".to_string(),
+                Operator::OrLess => "less than".to_string(),
+                Operator::Exactly => "equal to".to_string(),
+            };
+            format!(
+                "{} with cost {comparison} the abandoned character",
+                card_predicate(target, plural)
+            )
+        }
+        CardPredicate::CharacterWithSparkComparedToAbandoned { target, spark_operator } => format!(
+            "{} with spark {} the abandoned character's spark",
+            card_predicate(target, plural),
+            comparison(spark_operator)
+        ),
+        CardPredicate::CharacterWithSparkComparedToAbandonedCountThisTurn {
+            target,
+            spark_operator,
+        } => format!(
+            "{} with spark X{}, where X is the number of characters you have abandoned this turn",
+            card_predicate(target, plural),
+            operator_suffix(spark_operator)
+        ),
+        CardPredicate::CharacterWithMaterializedAbility => {
+            format!("{} with a $materialized ability", noun("character"))
+        }
+        CardPredicate::CharacterWithMultiActivatedAbility => {
+            format!("{} with a $multiActivated ability", noun("character"))
+        }
+        CardPredicate::Fast { target } => format!("'$fast' {}", card_predicate(target, plural)),
+        CardPredicate::And(predicates) => all_of(predicates, plural),
+        CardPredicate::Or(predicates) => predicates
+            .iter()
+            .map(|predicate| card_predicate(predicate, plural))
+            .collect::<Vec<_>>()
+            .join(" or "),
+        CardPredicate::Not(predicate) => {
+            format!("non-{} {}", card_predicate(predicate, false), noun("card"))
+        }
+        CardPredicate::WithCost(..) => format!("{} with {}", noun("card"), filter(predicate)),
+        CardPredicate::WithSpark(..) => format!("{} with {}", noun("character"), filter(predicate)),
+        CardPredicate::ControlledBy(..) | CardPredicate::InZone(..) => {
+            all_of(std::slice::from_ref(predicate), plural)
+        }
+    }
+}
+
+/// Writes the predicates of a [CardPredicate::And] as exclusions, followed by
+/// card types, stat filters and then location, e.g. "non-{cardtype: warrior}
+/// characters with cost $2 and spark 1 or more in your void".
+fn all_of(predicates: &[CardPredicate], plural: bool) -> String {
+    let mut words = vec![];
+    let mut types = vec![];
+    let mut filters = vec![];
+    let mut controller = None;
+    let mut zone = None;
+    for predicate in predicates {
+        match predicate {
+            CardPredicate::ControlledBy(c) => controller = Some(*c),
+            CardPredicate::InZone(z) => zone = Some(*z),
+            CardPredicate::Not(excluded) => {
+                words.push(format!("non-{}", card_predicate(excluded, false)))
+            }
+            CardPredicate::WithCost(..) | CardPredicate::WithSpark(..) => {
+                filters.push(filter(predicate))
+            }
+            _ => types.push(card_predicate(predicate, plural)),
+        }
+    }
+    if types.is_empty() {
+        types.push(card_predicate(&CardPredicate::Card, plural));
+    }
+    words.extend(types);
+
+    let mut text = words.join(" ");
+    if !filters.is_empty() {
+        text = format!("{text} with {}", filters.join(" and "));
+    }
+    match location(controller, zone) {
+        Some(location) => format!("{text} {location}"),
+        None => text,
+    }
+}
+
+/// Writes the controller and zone of a [CardPredicate::And], e.g. "in your
+/// void" or "the enemy controls".
+fn location(controller: Option<Controller>, zone: Option<CardZone>) -> Option<String> {
+    let zone_name = |zone| match zone {
+        CardZone::Battlefield => "battlefield",
+        CardZone::Hand => "hand",
+        CardZone::Void => "void",
+        CardZone::Deck => "deck",
+    };
+    Some(match (controller, zone) {
+        (None, None) => return None,
+        (Some(Controller::You), None | Some(CardZone::Battlefield)) => "you control".to_string(),
+        (Some(Controller::Enemy), None | Some(CardZone::Battlefield)) => {
+            "the enemy controls".to_string()
+        }
+        (Some(Controller::You), Some(zone)) => format!("in your {}", zone_name(zone)),
+        (Some(Controller::Enemy), Some(zone)) => format!("in the enemy's {}", zone_name(zone)),
+        (None, Some(zone)) => format!("in a {}", zone_name(zone)),
+    })
+}
+
+/// Writes a stat filter without its "with" prefix, e.g. "cost $2 or less".
+fn filter(predicate: &CardPredicate) -> String {
+    match predicate {
+        CardPredicate::WithCost(cost, operator) => {
+            format!("cost ${cost}{}", operator_suffix(operator))
+        }
+        CardPredicate::WithSpark(spark, operator) => {
+            format!("spark {spark}{}", operator_suffix(operator))
+        }
+        _ => card_predicate(predicate, false),
+    }
+}
+
+fn character_type_text(character_type: &CharacterType, plural: bool) -> String {
+    let name = match character_type {
+        CharacterType::Warrior => "warrior",
+        CharacterType::Survivor => "survivor",
+        CharacterType::SpiritAnimal => "spirit animal",
+    };
+    format!("{{cardtype: {name}{}}}", if plural { "s" } else { "" })
+}
+
+/// Suffix for an operator following a number, e.g. " or less" in "with cost
+/// $2 or less".
+fn operator_suffix<T>(operator: &Operator<T>) -> &'static str {
+    match operator {
+        Operator::OrLess | Operator::LowerBy(_) => " or less",
+        Operator::OrMore | Operator::HigherBy(_) => " or more",
+        Operator::Exactly => "",
+    }
+}
+
+/// Comparison phrase for an operator preceding a quantity, e.g. "less than or
+/// equal to" in "with cost less than or equal to the number of...".
+fn comparison<T>(operator: &Operator<T>) -> &'static str {
+    match operator {
+        Operator::OrLess | Operator::LowerBy(_) => "less than or equal to",
+        Operator::Exactly => "equal to",
+        Operator::OrMore | Operator::HigherBy(_) => "greater than or equal to",
+    }
+}
@@ -27,5 +27,6 @@ pub fn parser<'a>() -> impl Parser<'a, &'a str, QuantityExpression, ErrorType<'a
             .map(QuantityExpression::AbandonedThisWay),
         determiner_parser::counted_parser().map(QuantityExpression::Matching),
     ))
+    .labelled("a quantity")
     .boxed()
 }
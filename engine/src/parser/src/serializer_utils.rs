@@ -0,0 +1,80 @@
+/// Prefixes `text` with "a" or "an" as appropriate.
+///
+/// Text starting with a directive such as "{cardtype: ancient}" is checked
+/// using the word it displays rather than the directive name.
+pub fn a_or_an(text: &str) -> String {
+    let word = text
+        .strip_prefix('{')
+        .and_then(|directive| directive.split_once(':'))
+        .map_or(text, |(_, word)| word.trim_start());
+    if word.starts_with(['a', 'e', 'i', 'o', 'u', 'A', 'E', 'I', 'O', 'U']) {
+        format!("an {text}")
+    } else {
+        format!("a {text}")
+    }
+}
+
+/// Writes a number as text (e.g. "two") if it is less than ten, otherwise as
+/// a numeral.
+pub fn text_number(n: u32) -> String {
+    match n {
+        1 => "one".to_string(),
+        2 => "two".to_string(),
+        3 => "three".to_string(),
+        4 => "four".to_string(),
+        5 => "five".to_string(),
+        6 => "six".to_string(),
+        7 => "seven".to_string(),
+        8 => "eight".to_string(),
+        9 => "nine".to_string(),
+        _ => n.to_string(),
+    }
+}
+
+/// Writes an ordinal number as text (e.g. "second") if it is less than ten,
+/// otherwise as a numeral with an ordinal suffix (e.g. "21st").
+pub fn ordinal_number(n: u32) -> String {
+    match n {
+        1 => "first".to_string(),
+        2 => "second".to_string(),
+        3 => "third".to_string(),
+        4 => "fourth".to_string(),
+        5 => "fifth".to_string(),
+        6 => "sixth".to_string(),
+        7 => "seventh".to_string(),
+        8 => "eighth".to_string(),
+        9 => "ninth".to_string(),
+        _ => {
+            let suffix = match (n % 10, n % 100) {
+                (_, 11..=13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            };
+            format!("{n}{suffix}")
+        }
+    }
+}
+
+/// Writes "a card" or a number of cards, e.g. "2 cards".
+pub fn a_or_count_cards(count: u32) -> String {
+    if count == 1 {
+        "a card".to_string()
+    } else {
+        format!("{count} cards")
+    }
+}
+
+/// Capitalizes the first letter of a sentence.
+///
+/// Sentences starting with a keyword, such as "{kw: discover}", have the
+/// keyword capitalized instead.
+pub fn capitalize(text: &str) -> String {
+    let start = if text.starts_with("{kw: ") { "{kw: ".len() } else { 0 };
+    let mut chars = text[start..].chars();
+    match chars.next() {
+        Some(first) => format!("{}{}{}", &text[..start], first.to_uppercase(), chars.as_str()),
+        None => text.to_string(),
+    }
+}
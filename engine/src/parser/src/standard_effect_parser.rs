@@ -1,6 +1,7 @@
 use ability_data::collection_expression::CollectionExpression;
 use ability_data::effect::Effect;
 use ability_data::predicate::Predicate;
+use ability_data::quantity::Quantity;
 use ability_data::quantity_expression::QuantityExpression;
 use ability_data::standard_effect::StandardEffect;
 use ability_data::triggered_ability::{TriggeredAbility, TriggeredAbilityOptions};
@@ -8,9 +9,10 @@ use chumsky::prelude::*;
 use chumsky::Parser;
 use core_data::numerics::{Energy, Points, Spark};
 
+use crate::ability_spans::AstNode;
 use crate::parser_utils::{
-    a_or_an, a_or_count, card_or_cards, count, number_of_times, numeric, phrase, text_number,
-    ErrorType,
+    a_or_an, a_or_count, card_or_cards, count, literal, number_of_times, numeric, phrase, spanned,
+    text_number, ErrorType,
 };
 use crate::{
     card_predicate_parser, collection_expression_parser, cost_parser, determiner_parser,
@@ -19,7 +21,8 @@ use crate::{
 
 /// Parses all standard game effects
 pub fn parser<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
-    choice((non_recursive_effects(), create_trigger_until_end_of_turn())).boxed()
+    spanned(choice((non_recursive_effects(), create_trigger_until_end_of_turn())), AstNode::Effect)
+        .labelled("an effect")
 }
 
 /// Parses all standard game effects that do not recursively invoke effect
@@ -47,8 +50,8 @@ fn card_effects<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>>
         return_all_but_one_character_draw_card_for_each(),
         put_on_top_of_deck(),
         put_cards_from_void_on_top_of_deck(),
-        spend_all_energy_draw_and_discard(),
         spend_all_energy_dissolve_enemy(),
+        spend_all_energy(),
         materialize_character_from_void(),
         materialize_character_at_end_of_turn(),
         materialize_character(),
@@ -70,7 +73,6 @@ fn spark_effects<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>
         gain_spark_until_next_main_for_each(),
         gain_spark(),
         abandon_and_gain_energy_for_spark(),
-        each_matching_gains_spark_for_each(),
         each_matching_gains_spark_until_next_main(),
         kindle(),
         spark_becomes(),
@@ -130,17 +132,21 @@ fn game_state_effects<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorTyp
 fn draw_cards<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
     phrase("draw")
         .ignore_then(choice((phrase("a card").to(1), numeric("", count, "cards"))))
-        .map(|count| StandardEffect::DrawCards { count })
+        .map(|count| StandardEffect::DrawCards { count: Quantity::Fixed(count) })
 }
 
 fn gain_spark<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
-    determiner_parser::target_parser()
-        .then(numeric("gains +", Spark, "spark"))
-        .map(|(predicate, spark)| StandardEffect::GainsSpark { target: predicate, gains: spark })
+    determiner_parser::target_parser().then(numeric("gains +", Spark, "spark")).map(
+        |(predicate, spark)| StandardEffect::GainsSpark {
+            target: predicate,
+            gains: Quantity::Fixed(spark),
+        },
+    )
 }
 
 fn gain_energy<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
-    numeric("gain $", Energy, "").map(|energy| StandardEffect::GainEnergy { gains: energy })
+    numeric("gain $", Energy, "")
+        .map(|energy| StandardEffect::GainEnergy { gains: Quantity::Fixed(energy) })
 }
 
 fn gain_spark_until_next_main_for_each<'a>(
@@ -169,7 +175,8 @@ fn gains_aegis_this_turn<'a>() -> impl Parser<'a, &'a str, StandardEffect, Error
 }
 
 fn draw_matching_card<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
-    phrase("draw a")
+    phrase("draw")
+        .ignore_then(a_or_an())
         .ignore_then(card_predicate_parser::parser())
         .then_ignore(phrase("from your deck"))
         .map(|card_predicate| StandardEffect::DrawMatchingCard { predicate: card_predicate })
@@ -179,7 +186,7 @@ fn banish_card_from_enemy_void<'a>() -> impl Parser<'a, &'a str, StandardEffect,
     phrase("banish")
         .ignore_then(choice((phrase("a card").to(1), numeric("", count, "cards"))))
         .then_ignore(phrase("from the enemy's void"))
-        .map(|count| StandardEffect::BanishCardsFromEnemyVoid { count })
+        .map(|count| StandardEffect::BanishCardsFromEnemyVoid { count: Quantity::Fixed(count) })
 }
 
 fn disable_activated_abilities<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
@@ -235,7 +242,7 @@ fn discover<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
 
 fn discover_and_then_materialize<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
     phrase("{kw: discover}")
-        .ignore_then(choice((phrase("a"), phrase("an"))))
+        .ignore_then(a_or_an())
         .ignore_then(card_predicate_parser::parser())
         .then_ignore(phrase("and materialize it"))
         .map(|predicate| StandardEffect::DiscoverAndThenMaterialize { predicate })
@@ -250,7 +257,10 @@ fn materialize_random_characters<'a>() -> impl Parser<'a, &'a str, StandardEffec
         )))
         .then(card_predicate_parser::parser())
         .then_ignore(phrase("from your deck"))
-        .map(|(count, predicate)| StandardEffect::MaterializeRandomFromDeck { count, predicate })
+        .map(|(count, predicate)| StandardEffect::MaterializeRandomFromDeck {
+            count: Quantity::Fixed(count),
+            predicate,
+        })
         .boxed()
 }
 
@@ -281,7 +291,9 @@ fn gains_reclaim_until_end_of_turn<'a>() -> impl Parser<'a, &'a str, StandardEff
 }
 
 fn kindle<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
-    numeric("{kw: kindle}", Spark, "").map(|amount| StandardEffect::Kindle { amount }).boxed()
+    numeric("{kw: kindle}", Spark, "")
+        .map(|amount| StandardEffect::Kindle { amount: Quantity::Fixed(amount) })
+        .boxed()
 }
 
 fn negate<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
@@ -318,13 +330,6 @@ fn abandon_at_end_of_turn<'a>() -> impl Parser<'a, &'a str, StandardEffect, Erro
         .boxed()
 }
 
-fn spend_all_energy_draw_and_discard<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>>
-{
-    phrase("spend all your remaining energy. draw x cards then discard x cards, where x is the energy spent this way")
-        .to(StandardEffect::SpendAllEnergyDrawAndDiscard)
-        .boxed()
-}
-
 fn spend_all_energy_dissolve_enemy<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>>
 {
     phrase("spend all your remaining energy. dissolve an enemy character with spark less than or equal to the energy spent this way")
@@ -332,6 +337,10 @@ fn spend_all_energy_dissolve_enemy<'a>() -> impl Parser<'a, &'a str, StandardEff
         .boxed()
 }
 
+fn spend_all_energy<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
+    phrase("spend all your remaining energy").to(StandardEffect::SpendAllEnergy).boxed()
+}
+
 fn put_on_top_of_deck<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
     phrase("put")
         .ignore_then(determiner_parser::target_parser())
@@ -346,21 +355,9 @@ fn each_matching_gains_spark_until_next_main<'a>(
         .ignore_then(card_predicate_parser::parser())
         .then_ignore(phrase("you control gains +"))
         .then(numeric("", Spark, "spark until your next main phase"))
-        .map(|(each, gains)| StandardEffect::EachMatchingGainsSparkUntilNextMain { each, gains })
-        .boxed()
-}
-
-fn each_matching_gains_spark_for_each<'a>(
-) -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
-    phrase("each")
-        .ignore_then(card_predicate_parser::parser())
-        .then_ignore(phrase("you control gains +x spark, where x is the number of"))
-        .then(card_predicate_parser::parser())
-        .then_ignore(phrase("you control"))
-        .map(|(matching, for_each)| StandardEffect::EachMatchingGainsSparkForEach {
-            each: matching,
-            gains: Spark(1),
-            for_each,
+        .map(|(each, gains)| StandardEffect::EachMatchingGainsSparkUntilNextMain {
+            each,
+            gains: Quantity::Fixed(gains),
         })
         .boxed()
 }
@@ -417,18 +414,20 @@ fn materialize_character_at_end_of_turn<'a>(
 
 fn gain_points<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
     numeric("gain", Points, "$point")
-        .then_ignore(just("s").or_not())
-        .map(|points| StandardEffect::GainPoints { gains: points })
+        .then_ignore(literal("s").or_not())
+        .map(|points| StandardEffect::GainPoints { gains: Quantity::Fixed(points) })
 }
 
 fn foresee<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
-    numeric("{kw: foresee}", count, "").map(|count| StandardEffect::Foresee { count }).boxed()
+    numeric("{kw: foresee}", count, "")
+        .map(|count| StandardEffect::Foresee { count: Quantity::Fixed(count) })
+        .boxed()
 }
 
 fn lose_points<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
     numeric("you lose", Points, "$point")
-        .then_ignore(just("s").or_not())
-        .map(|points| StandardEffect::LosePoints { loses: points })
+        .then_ignore(literal("s").or_not())
+        .map(|points| StandardEffect::LosePoints { loses: Quantity::Fixed(points) })
 }
 
 fn dissolve_characters_count<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
@@ -441,9 +440,9 @@ fn dissolve_characters_count<'a>() -> impl Parser<'a, &'a str, StandardEffect, E
 
 fn enemy_gains_points<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
     phrase("the enemy gains")
-        .ignore_then(numeric("", count, "$point"))
-        .then_ignore(just("s").or_not())
-        .map(|count| StandardEffect::EnemyGainsPoints { count })
+        .ignore_then(numeric("", Points, "$point"))
+        .then_ignore(literal("s").or_not())
+        .map(|points| StandardEffect::EnemyGainsPoints { count: Quantity::Fixed(points) })
         .boxed()
 }
 
@@ -456,9 +455,9 @@ fn enemy_gains_points_equal_to_its_spark<'a>(
 
 fn enemy_loses_points<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
     phrase("the enemy loses")
-        .ignore_then(numeric("", count, "$point"))
-        .then_ignore(just("s").or_not())
-        .map(|count| StandardEffect::EnemyLosesPoints { count })
+        .ignore_then(numeric("", Points, "$point"))
+        .then_ignore(literal("s").or_not())
+        .map(|points| StandardEffect::EnemyLosesPoints { count: Quantity::Fixed(points) })
         .boxed()
 }
 
@@ -484,7 +483,7 @@ fn return_to_hand<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a
 fn gain_points_for_each<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
     phrase("gain")
         .ignore_then(numeric("", Points, "$point"))
-        .then_ignore(just("s").or_not())
+        .then_ignore(literal("s").or_not())
         .then_ignore(phrase("for each"))
         .then(quantity_expression_parser::parser())
         .map(|(gain, for_count)| StandardEffect::GainPointsForEach { gain, for_count })
@@ -564,7 +563,7 @@ fn gain_twice_that_much_energy_instead<'a>(
 fn shuffle_hand_and_deck_and_draw<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
     phrase("each player may shuffle their hand and void into their deck and then draw")
         .ignore_then(numeric("", count, "cards"))
-        .map(|count| StandardEffect::ShuffleHandAndDeckAndDraw { count })
+        .map(|count| StandardEffect::ShuffleHandAndDeckAndDraw { count: Quantity::Fixed(count) })
         .boxed()
 }
 
@@ -579,7 +578,7 @@ fn dissolve_characters_quantity<'a>() -> impl Parser<'a, &'a str, StandardEffect
 
 fn put_cards_from_deck_into_void<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>> {
     numeric("put the top", count, "cards of your deck into your void")
-        .map(|count| StandardEffect::PutCardsFromYourDeckIntoVoid { count })
+        .map(|count| StandardEffect::PutCardsFromYourDeckIntoVoid { count: Quantity::Fixed(count) })
         .boxed()
 }
 
@@ -610,7 +609,7 @@ fn spark_becomes<'a>() -> impl Parser<'a, &'a str, StandardEffect, ErrorType<'a>
         .map(|((collection, matching), spark)| StandardEffect::SparkBecomes {
             collection: collection.unwrap_or(CollectionExpression::All),
             matching,
-            spark,
+            spark: Quantity::Fixed(spark),
         })
         .boxed()
 }
@@ -641,7 +640,7 @@ fn each_player_discard_cards<'a>() -> impl Parser<'a, &'a str, StandardEffect, E
     phrase("each player discards")
         .ignore_then(a_or_count())
         .then_ignore(card_or_cards())
-        .map(|count| StandardEffect::EachPlayerDiscardCards { count })
+        .map(|count| StandardEffect::EachPlayerDiscardCards { count: Quantity::Fixed(count) })
         .boxed()
 }
 
@@ -651,7 +650,10 @@ fn put_cards_from_void_on_top_of_deck<'a>(
         .ignore_then(a_or_count())
         .then(card_predicate_parser::parser())
         .then_ignore(phrase("from your void on top of your deck"))
-        .map(|(count, matching)| StandardEffect::PutCardsFromVoidOnTopOfDeck { count, matching })
+        .map(|(count, matching)| StandardEffect::PutCardsFromVoidOnTopOfDeck {
+            count: Quantity::Fixed(count),
+            matching,
+        })
         .boxed()
 }
 
@@ -669,7 +671,7 @@ fn materialize_silent_copy<'a>() -> impl Parser<'a, &'a str, StandardEffect, Err
                 let target_clone = target.clone();
                 StandardEffect::MaterializeSilentCopy {
                     target,
-                    count,
+                    count: Quantity::Fixed(count),
                     quantity: quantity.unwrap_or(QuantityExpression::Matching(target_clone)),
                 }
             }),
@@ -682,6 +684,9 @@ fn each_player_abandons_characters<'a>() -> impl Parser<'a, &'a str, StandardEff
     phrase("each player abandons")
         .ignore_then(a_or_count())
         .then(card_predicate_parser::parser())
-        .map(|(count, matching)| StandardEffect::EachPlayerAbandonsCharacters { matching, count })
+        .map(|(count, matching)| StandardEffect::EachPlayerAbandonsCharacters {
+            matching,
+            count: Quantity::Fixed(count),
+        })
         .boxed()
 }
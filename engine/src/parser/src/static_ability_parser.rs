@@ -8,7 +8,7 @@ use chumsky::prelude::*;
 use chumsky::Parser;
 use core_data::numerics::{Energy, Spark};
 
-use crate::parser_utils::{numeric, phrase, this, ErrorType};
+use crate::parser_utils::{a_or_an, numeric, phrase, this, ErrorType};
 use crate::{
     card_predicate_parser, condition_parser, cost_parser, determiner_parser,
     quantity_expression_parser, standard_effect_parser,
@@ -59,7 +59,8 @@ fn standard<'a>() -> impl Parser<'a, &'a str, StandardStaticAbility, ErrorType<'
 
 fn once_per_turn_play_from_void<'a>(
 ) -> impl Parser<'a, &'a str, StandardStaticAbility, ErrorType<'a>> {
-    phrase("once per turn, you may play a")
+    phrase("once per turn, you may play")
+        .ignore_then(a_or_an())
         .ignore_then(card_predicate_parser::parser())
         .then_ignore(phrase("from your void"))
         .map(|matching| StandardStaticAbility::OncePerTurnPlayFromVoid { matching })
@@ -20,6 +20,7 @@ pub fn event_parser<'a>() -> impl Parser<'a, &'a str, TriggerEvent, ErrorType<'a
         dissolved(),
         abandon(),
     ))
+    .labelled("a trigger event")
     .boxed()
 }
 
@@ -0,0 +1,178 @@
+use ability_data::collection_expression::CollectionExpression;
+use ability_data::predicate::{CardPredicate, Predicate};
+use ability_data::quantity::Quantity;
+use ability_data::quantity_expression::QuantityExpression;
+use ability_data::standard_effect::StandardEffect;
+use chumsky::prelude::*;
+use chumsky::Parser;
+
+use crate::parser_utils::{phrase, ErrorType};
+use crate::{card_predicate_parser, determiner_parser, quantity_expression_parser};
+
+/// An effect whose amount is written as "X", before the "where X is ..."
+/// clause which defines X has been parsed.
+#[derive(Debug, Clone)]
+pub enum XEffect {
+    BanishCardsFromEnemyVoid,
+    DiscardCards,
+    DrawCards,
+    EachMatchingGainsSpark(CardPredicate),
+    EachMatchingGainsSparkUntilNextMain(CardPredicate),
+    EachPlayerAbandonsCharacters(CardPredicate),
+    EachPlayerDiscardCards,
+    EnemyGainsPoints,
+    EnemyLosesPoints,
+    Foresee,
+    GainEnergy,
+    GainPoints,
+    GainsSpark(Predicate),
+    Kindle,
+    LosePoints,
+    MaterializeRandomFromDeck(CardPredicate),
+    MaterializeSilentCopy(Predicate),
+    PutCardsFromYourDeckIntoVoid,
+    PutCardsFromVoidOnTopOfDeck(CardPredicate),
+    ShuffleHandAndDeckAndDraw,
+    SparkBecomes(CardPredicate),
+}
+
+impl XEffect {
+    /// Returns this effect with X defined as `x`.
+    pub fn bind(self, x: QuantityExpression) -> StandardEffect {
+        match self {
+            XEffect::BanishCardsFromEnemyVoid => {
+                StandardEffect::BanishCardsFromEnemyVoid { count: Quantity::X(x) }
+            }
+            XEffect::DiscardCards => StandardEffect::DiscardCards { count: Quantity::X(x) },
+            XEffect::DrawCards => StandardEffect::DrawCards { count: Quantity::X(x) },
+            XEffect::EachMatchingGainsSpark(each) => {
+                StandardEffect::EachMatchingGainsSpark { each, gains: Quantity::X(x) }
+            }
+            XEffect::EachMatchingGainsSparkUntilNextMain(each) => {
+                StandardEffect::EachMatchingGainsSparkUntilNextMain { each, gains: Quantity::X(x) }
+            }
+            XEffect::EachPlayerAbandonsCharacters(matching) => {
+                StandardEffect::EachPlayerAbandonsCharacters { matching, count: Quantity::X(x) }
+            }
+            XEffect::EachPlayerDiscardCards => {
+                StandardEffect::EachPlayerDiscardCards { count: Quantity::X(x) }
+            }
+            XEffect::EnemyGainsPoints => StandardEffect::EnemyGainsPoints { count: Quantity::X(x) },
+            XEffect::EnemyLosesPoints => StandardEffect::EnemyLosesPoints { count: Quantity::X(x) },
+            XEffect::Foresee => StandardEffect::Foresee { count: Quantity::X(x) },
+            XEffect::GainEnergy => StandardEffect::GainEnergy { gains: Quantity::X(x) },
+            XEffect::GainPoints => StandardEffect::GainPoints { gains: Quantity::X(x) },
+            XEffect::GainsSpark(target) => {
+                StandardEffect::GainsSpark { target, gains: Quantity::X(x) }
+            }
+            XEffect::Kindle => StandardEffect::Kindle { amount: Quantity::X(x) },
+            XEffect::LosePoints => StandardEffect::LosePoints { loses: Quantity::X(x) },
+            XEffect::MaterializeRandomFromDeck(predicate) => {
+                StandardEffect::MaterializeRandomFromDeck { count: Quantity::X(x), predicate }
+            }
+            XEffect::MaterializeSilentCopy(target) => StandardEffect::MaterializeSilentCopy {
+                quantity: QuantityExpression::Matching(target.clone()),
+                target,
+                count: Quantity::X(x),
+            },
+            XEffect::PutCardsFromYourDeckIntoVoid => {
+                StandardEffect::PutCardsFromYourDeckIntoVoid { count: Quantity::X(x) }
+            }
+            XEffect::PutCardsFromVoidOnTopOfDeck(matching) => {
+                StandardEffect::PutCardsFromVoidOnTopOfDeck { count: Quantity::X(x), matching }
+            }
+            XEffect::ShuffleHandAndDeckAndDraw => {
+                StandardEffect::ShuffleHandAndDeckAndDraw { count: Quantity::X(x) }
+            }
+            XEffect::SparkBecomes(matching) => StandardEffect::SparkBecomes {
+                collection: CollectionExpression::All,
+                matching,
+                spark: Quantity::X(x),
+            },
+        }
+    }
+}
+
+/// Parses an effect whose amount is written as "X", such as "draw X cards".
+pub fn parser<'a>() -> impl Parser<'a, &'a str, XEffect, ErrorType<'a>> {
+    choice((
+        card_effects(),
+        spark_effects(),
+        points_effects(),
+        phrase("gain $x").to(XEffect::GainEnergy),
+        phrase("{kw: foresee} x").to(XEffect::Foresee),
+    ))
+    .labelled("an effect")
+    .boxed()
+}
+
+fn card_effects<'a>() -> impl Parser<'a, &'a str, XEffect, ErrorType<'a>> {
+    choice((
+        phrase("draw x cards").to(XEffect::DrawCards),
+        phrase("discard x cards").to(XEffect::DiscardCards),
+        phrase("banish x cards from the enemy's void").to(XEffect::BanishCardsFromEnemyVoid),
+        phrase("each player discards x cards").to(XEffect::EachPlayerDiscardCards),
+        phrase("each player abandons x")
+            .ignore_then(card_predicate_parser::parser())
+            .map(XEffect::EachPlayerAbandonsCharacters),
+        phrase("each player may shuffle their hand and void into their deck and then draw x cards")
+            .to(XEffect::ShuffleHandAndDeckAndDraw),
+        phrase("materialize x random")
+            .ignore_then(card_predicate_parser::parser())
+            .then_ignore(phrase("from your deck"))
+            .map(XEffect::MaterializeRandomFromDeck),
+        phrase("materialize x {kw: silent} copies of")
+            .ignore_then(determiner_parser::target_parser())
+            .map(XEffect::MaterializeSilentCopy),
+        phrase("put the top x cards of your deck into your void")
+            .to(XEffect::PutCardsFromYourDeckIntoVoid),
+        phrase("put x")
+            .ignore_then(card_predicate_parser::parser())
+            .then_ignore(phrase("from your void on top of your deck"))
+            .map(XEffect::PutCardsFromVoidOnTopOfDeck),
+    ))
+    .boxed()
+}
+
+fn spark_effects<'a>() -> impl Parser<'a, &'a str, XEffect, ErrorType<'a>> {
+    choice((
+        phrase("each")
+            .ignore_then(card_predicate_parser::parser())
+            .then_ignore(phrase("you control gains +x spark until your next main phase"))
+            .map(XEffect::EachMatchingGainsSparkUntilNextMain),
+        phrase("each")
+            .ignore_then(card_predicate_parser::parser())
+            .then_ignore(phrase("you control gains +x spark"))
+            .map(XEffect::EachMatchingGainsSpark),
+        determiner_parser::target_parser()
+            .then_ignore(phrase("gains +x spark"))
+            .map(XEffect::GainsSpark),
+        phrase("{kw: kindle} x").to(XEffect::Kindle),
+        phrase("the spark of each")
+            .ignore_then(card_predicate_parser::parser())
+            .then_ignore(phrase("you control becomes x"))
+            .map(XEffect::SparkBecomes),
+    ))
+    .boxed()
+}
+
+fn points_effects<'a>() -> impl Parser<'a, &'a str, XEffect, ErrorType<'a>> {
+    choice((
+        phrase("gain x $points").to(XEffect::GainPoints),
+        phrase("you lose x $points").to(XEffect::LosePoints),
+        phrase("the enemy gains x $points").to(XEffect::EnemyGainsPoints),
+        phrase("the enemy loses x $points").to(XEffect::EnemyLosesPoints),
+    ))
+    .boxed()
+}
+
+/// Parses the clause defining X, for example "where X is the number of
+/// characters you control".
+pub fn where_x_is<'a>() -> impl Parser<'a, &'a str, QuantityExpression, ErrorType<'a>> {
+    phrase("where x is")
+        .ignore_then(choice((
+            phrase("the energy spent this way").to(QuantityExpression::EnergySpentThisWay),
+            phrase("the number of").ignore_then(quantity_expression_parser::parser()),
+        )))
+        .boxed()
+}
@@ -0,0 +1,218 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ability_data::ability::{Ability, AbilityKind};
+use ability_data::effect::Effect;
+use ability_data::standard_effect::StandardEffect;
+use ability_data::static_ability::{
+    StandardStaticAbility, StaticAbility, StaticAbilityWithOptions,
+};
+use parser::{ability_diagnostics, ability_parser};
+use serde::{Deserialize, Serialize};
+
+/// A card read from a card file: a JSON array of objects with a `name` and
+/// the card's rules `text`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CardText {
+    pub name: String,
+    pub text: String,
+}
+
+/// Result of parsing every card in a set of card files.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReport {
+    pub passed: usize,
+    pub failed: usize,
+
+    /// Errors in cards which failed to parse, grouped by the kind of ability
+    /// and the grammar constructs expected where parsing failed, with the most
+    /// common group first.
+    pub errors: Vec<ErrorGroup>,
+
+    pub cards: Vec<CardReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardReport {
+    pub name: String,
+    pub file: PathBuf,
+    pub passed: bool,
+
+    /// Names of the [StandardEffect] variants used by the abilities which
+    /// could be parsed.
+    pub effects: Vec<String>,
+
+    pub errors: Vec<CardError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardError {
+    /// Kind of ability being parsed where the error occurred.
+    pub ability_kind: AbilityKind,
+
+    /// Grammar constructs which were expected where the error occurred.
+    pub expected: Vec<String>,
+
+    pub message: String,
+}
+
+/// Cards with errors in the same kind of ability and with the same expected
+/// grammar constructs.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorGroup {
+    pub ability_kind: AbilityKind,
+    pub expected: Vec<String>,
+
+    /// Names of the cards with an error in this group, once per error.
+    pub cards: Vec<String>,
+}
+
+/// Reads the card file at `path`, or every `.json` card file in the
+/// directory at `path`, and parses the text of each card.
+pub fn run(path: &Path) -> Result<BatchReport, String> {
+    let mut report = BatchReport { passed: 0, failed: 0, errors: vec![], cards: vec![] };
+    let mut groups = BTreeMap::<(AbilityKind, Vec<String>), Vec<String>>::new();
+    for file in card_files(path)? {
+        let contents =
+            fs::read_to_string(&file).map_err(|e| format!("Error reading {file:?}: {e}"))?;
+        let cards: Vec<CardText> = serde_json::from_str(&contents)
+            .map_err(|e| format!("Error reading cards from {file:?}: {e}"))?;
+        for card in cards {
+            let card = parse_card(card, &file);
+            if card.passed {
+                report.passed += 1;
+            } else {
+                report.failed += 1;
+            }
+            for error in &card.errors {
+                let key = (error.ability_kind, error.expected.clone());
+                groups.entry(key).or_default().push(card.name.clone());
+            }
+            report.cards.push(card);
+        }
+    }
+
+    report.errors = groups
+        .into_iter()
+        .map(|((ability_kind, expected), cards)| ErrorGroup { ability_kind, expected, cards })
+        .collect();
+    report.errors.sort_by_key(|group| Reverse(group.cards.len()));
+    Ok(report)
+}
+
+/// Prints a human-readable summary of `report`.
+pub fn print_summary(report: &BatchReport) {
+    println!(
+        "Parsed {} cards: {} passed, {} failed",
+        report.cards.len(),
+        report.passed,
+        report.failed
+    );
+
+    if !report.errors.is_empty() {
+        println!("\nErrors:");
+    }
+    for group in &report.errors {
+        println!(
+            "  {} x {:?} ability, expected {}",
+            group.cards.len(),
+            group.ability_kind,
+            ability_diagnostics::describe_expected(&group.expected)
+        );
+        for card in &group.cards {
+            println!("      {card}");
+        }
+    }
+
+    println!("\nEffects:");
+    for card in &report.cards {
+        let status = if card.passed { "" } else { " (failed)" };
+        let effects =
+            if card.effects.is_empty() { "none".to_string() } else { card.effects.join(", ") };
+        println!("  {}{status}: {effects}", card.name);
+    }
+}
+
+fn card_files(path: &Path) -> Result<Vec<PathBuf>, String> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let entries = fs::read_dir(path).map_err(|e| format!("Error reading {path:?}: {e}"))?;
+    let mut files = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+fn parse_card(card: CardText, file: &Path) -> CardReport {
+    let (result, errors) = ability_parser::parse(&card.text).into_output_errors();
+    let mut effects = result
+        .iter()
+        .flatten()
+        .flat_map(ability_effects)
+        .flat_map(Effect::standard_effects)
+        .map(variant_name)
+        .collect::<Vec<_>>();
+    effects.sort();
+    effects.dedup();
+
+    CardReport {
+        name: card.name,
+        file: file.to_path_buf(),
+        passed: errors.is_empty(),
+        effects,
+        errors: errors
+            .iter()
+            .map(|e| {
+                let diagnostic = ability_diagnostics::diagnose(&card.text, e);
+                CardError {
+                    ability_kind: ability_parser::ability_kind(&card.text, diagnostic.span.start),
+                    expected: diagnostic.expected,
+                    message: diagnostic.message,
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Returns the effects directly contained within an ability.
+fn ability_effects(ability: &Ability) -> Vec<&Effect> {
+    match ability {
+        Ability::Event(effect) => vec![effect],
+        Ability::Activated(ability) => vec![&ability.effect],
+        Ability::Triggered(ability) => vec![&ability.effect],
+        Ability::Static(StaticAbility::StaticAbility(ability))
+        | Ability::Static(StaticAbility::WithOptions(StaticAbilityWithOptions {
+            ability, ..
+        })) => static_ability_effects(ability),
+    }
+}
+
+fn static_ability_effects(ability: &StandardStaticAbility) -> Vec<&Effect> {
+    match ability {
+        StandardStaticAbility::PlayForAlternateCost(alternate) => {
+            alternate.if_you_do.iter().collect()
+        }
+        StandardStaticAbility::PlayFromVoid(play) => play.if_you_do.iter().collect(),
+        _ => vec![],
+    }
+}
+
+/// Returns the serialized name of a [StandardEffect] variant, e.g.
+/// "drawCards".
+fn variant_name(effect: &StandardEffect) -> String {
+    match serde_json::to_value(effect) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(serde_json::Value::Object(map)) => map.keys().next().cloned().unwrap_or_default(),
+        _ => format!("{effect:?}"),
+    }
+}
@@ -1,38 +1,75 @@
+use std::path::Path;
 use std::{env, process};
 
 use ariadne::{Color, Label, Report, ReportKind, Source};
-use parser::ability_parser;
+use parser::{ability_diagnostics, ability_parser};
+use parser_cli::batch;
+use serde::Serialize;
 
 fn main() {
     let args: Vec<_> = env::args().collect();
-    if args.len() != 2 {
-        println!("Usage: parser_cli <expression>");
-        process::exit(0)
-    }
+    let (spans, expression) = match args.as_slice() {
+        [_, flag, path] if flag == "--batch" => run_batch(path, false),
+        [_, flag, json, path] if flag == "--batch" && json == "--json" => run_batch(path, true),
+        [_, flag, expression] if flag == "--spans" => (true, expression),
+        [_, expression] => (false, expression),
+        _ => {
+            println!("Usage: parser_cli [--spans] <expression>");
+            println!("       parser_cli --batch [--json] <card file or directory>");
+            process::exit(0)
+        }
+    };
 
-    let input = args[1].to_lowercase();
-    let (result, errs) = ability_parser::parse(&input).into_output_errors();
-    if let Some(output) = result.as_ref() {
-        println!(
-            "{}",
-            ron::ser::to_string_pretty(
-                output,
-                ron::ser::PrettyConfig::default().struct_names(true)
-            )
-            .unwrap(),
-        );
-    }
+    let errs = if spans {
+        let (result, errs) = ability_parser::parse_with_spans(expression).into_output_errors();
+        if let Some(output) = result.as_ref() {
+            print_ron(output);
+        }
+        errs
+    } else {
+        let (result, errs) = ability_parser::parse(expression).into_output_errors();
+        if let Some(output) = result.as_ref() {
+            print_ron(output);
+        }
+        errs
+    };
 
-    errs.into_iter().for_each(|e| {
-        Report::build(ReportKind::Error, (), e.span().start)
-            .with_message(e.to_string())
+    errs.iter().map(|e| ability_diagnostics::diagnose(expression, e)).for_each(|diagnostic| {
+        let mut report = Report::build(ReportKind::Error, (), diagnostic.span.start)
+            .with_message(diagnostic.message)
             .with_label(
-                Label::new(e.span().into_range())
-                    .with_message(e.reason().to_string())
-                    .with_color(Color::Red),
-            )
-            .finish()
-            .eprint(Source::from(&args[1]))
-            .unwrap()
+                Label::new(diagnostic.span).with_message(diagnostic.label).with_color(Color::Red),
+            );
+        if let Some(suggestion) = diagnostic.suggestion {
+            report = report.with_help(format!("did you mean '{suggestion}'?"));
+        }
+        report.finish().eprint(Source::from(expression)).unwrap()
     });
 }
+
+/// Parses every card in the card file or directory at `path` and prints a
+/// summary, exiting with a non-zero status if any card fails to parse.
+fn run_batch(path: &str, json: bool) -> ! {
+    let report = match batch::run(Path::new(path)) {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("{error}");
+            process::exit(2)
+        }
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        batch::print_summary(&report);
+    }
+    process::exit(if report.failed > 0 { 1 } else { 0 })
+}
+
+fn print_ron(output: &impl Serialize) {
+    println!(
+        "{}",
+        ron::ser::to_string_pretty(output, ron::ser::PrettyConfig::default().struct_names(true))
+            .unwrap(),
+    );
+}
@@ -1,103 +1,246 @@
 #![allow(clippy::missing_safety_doc)] // You only live once, that's the motto - Drake
 
+use std::fmt::Display;
 use std::panic::{self, UnwindSafe};
+use std::ptr;
+use std::sync::{Arc, Mutex};
 
-use anyhow::Result;
-use display_data::request_data::{ConnectRequest, PerformActionRequest};
-use engine::test_data;
+use database::file_database::FileDatabase;
+use display_data::request_data::{
+    ConnectRequest, ErrorResponse, ErrorType, PerformActionRequest, PollRequest,
+};
+use display_data::wire_format::{self, WireFormat};
+use engine::handle_request;
+use engine::session_registry::SessionRegistry;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+type Result<T> = std::result::Result<T, ErrorResponse>;
+
+static REGISTRY: Mutex<Option<Arc<SessionRegistry>>> = Mutex::new(None);
+
+static LAST_ERROR: Mutex<Option<ErrorResponse>> = Mutex::new(None);
+
+/// Wire formats in the order of the integers which identify them in plugin
+/// function arguments, i.e. 0 for JSON and 1 for MessagePack.
+const WIRE_FORMATS: [WireFormat; 2] = [WireFormat::Json, WireFormat::MessagePack];
+
+/// Initializes the plugin, storing save files in the provided directory.
+///
+/// `path` should be a buffer containing the UTF-8 encoded path of a directory
+/// which the app can write to, of `path_length` bytes. This must be called
+/// before any other plugin function.
+///
+/// Returns 0 on success, or -1 on error. Call [dreamcaller_last_error_owned]
+/// for a description of the error.
+#[no_mangle]
+pub unsafe extern "C" fn dreamcaller_initialize(path: *const u8, path_length: i32) -> i32 {
+    error_boundary(|| initialize_impl(path, path_length))
+}
+
+unsafe fn initialize_impl(path: *const u8, path_length: i32) -> Result<i32> {
+    let path_data = std::slice::from_raw_parts(path, path_length as usize);
+    let directory = std::str::from_utf8(path_data).map_err(invalid_request)?;
+    println!("initialize: {directory}");
+    let database = FileDatabase::new(directory)
+        .map_err(|e| ErrorResponse::new(ErrorType::Internal, e.to_string()))?;
+    *REGISTRY.lock().unwrap() = Some(Arc::new(SessionRegistry::new(Box::new(database))));
+    Ok(0)
+}
 
 /// Synchronize the state of an ongoing game, downloading a full description of
 /// the game state.
 ///
 /// `request` should be a buffer including the json serialization of a
-/// `ConnectRequest` message of `request_length` bytes. `response` should be an
-/// empty buffer of `response_length` bytes, this buffer will be populated with
-/// a json-serialized `ConnectResponse` describing the current state of the
-/// game.
+/// `ConnectRequest` message of `request_length` bytes. On success, `response`
+/// is set to point to a buffer allocated by the plugin containing a serialized
+/// `ConnectResponse` describing the current state of the game. This buffer
+/// must be released by calling [dreamcaller_free].
 ///
-/// Returns the number of bytes written to the `response` buffer, or -1 on
-/// error.
+/// The `wire_format` of the request selects the encoding of the response. The
+/// encoding of subsequent requests is given with each call.
+///
+/// Returns the length of the buffer in bytes, or -1 on error. Call
+/// [dreamcaller_last_error_owned] for a description of the error.
 #[no_mangle]
-pub unsafe extern "C" fn dreamcaller_connect(
+pub unsafe extern "C" fn dreamcaller_connect_owned(
     request: *const u8,
     request_length: i32,
-    response: *mut u8,
-    response_length: i32,
+    response: *mut *mut u8,
 ) -> i32 {
-    error_boundary(|| connect_impl(request, request_length, response, response_length))
+    error_boundary(|| allocate_response(connect_impl(request, request_length)?, response))
 }
 
-unsafe fn connect_impl(
-    request: *const u8,
-    request_length: i32,
-    response: *mut u8,
-    response_length: i32,
-) -> Result<i32> {
-    let request_data = std::slice::from_raw_parts(request, request_length as usize);
-    let deserialized_request = serde_json::from_slice::<ConnectRequest>(request_data)?;
+unsafe fn connect_impl(request: *const u8, request_length: i32) -> Result<Vec<u8>> {
+    let deserialized_request =
+        decode_request::<ConnectRequest>(WireFormat::Json, request, request_length)?;
     println!("connect: {:?}", deserialized_request.metadata.user_id);
-    let scene = test_data::connect(&deserialized_request);
-    let json = serde_json::to_string(&scene)?;
-    let json_bytes = json.as_bytes();
-
-    if json_bytes.len() > response_length as usize {
-        return Err(anyhow::anyhow!("Response buffer too small"));
-    }
-
-    let out = std::slice::from_raw_parts_mut(response, response_length as usize);
-    out[..json_bytes.len()].copy_from_slice(json_bytes);
-    Ok(json_bytes.len() as i32)
+    let registry = registry()?;
+    let response = handle_request::connect(&registry, &deserialized_request)?;
+    encode_response(response.wire_format, &response)
 }
 
 /// Performs a given game action.
 ///
-/// `request` should be a buffer including the json serialization of a
-/// `PerformActionRequest` message of `request_length` bytes. `response` should
-/// be an empty buffer of `response_length` bytes, this buffer will be populated
-/// with a json-serialized `PerformActionResponse` describing the result of
-/// performing this action.
+/// `request` should be a buffer including the serialization of a
+/// `PerformActionRequest` message of `request_length` bytes, encoded in the
+/// wire format identified by `wire_format` (see [WIRE_FORMATS]). On success,
+/// `response` is set to point to a buffer allocated by the plugin containing a
+/// serialized `PerformActionResponse` describing the result of performing
+/// this action. This buffer must be released by calling [dreamcaller_free].
+///
+/// Responses are always returned in a buffer allocated by the plugin because
+/// the action has already been performed by the time the size of the response
+/// is known.
 ///
-/// Returns the number of bytes written to the `response` buffer, or -1 on
-/// error.
+/// Returns the length of the buffer in bytes, or -1 on error. Call
+/// [dreamcaller_last_error_owned] for a description of the error.
 #[no_mangle]
-pub unsafe extern "C" fn dreamcaller_perform_action(
+pub unsafe extern "C" fn dreamcaller_perform_action_owned(
     request: *const u8,
     request_length: i32,
-    response: *mut u8,
-    response_length: i32,
+    wire_format: i32,
+    response: *mut *mut u8,
 ) -> i32 {
-    error_boundary(|| perform_impl(request, request_length, response, response_length))
+    error_boundary(|| {
+        allocate_response(perform_impl(request, request_length, wire_format)?, response)
+    })
 }
 
 unsafe fn perform_impl(
     request: *const u8,
     request_length: i32,
-    response: *mut u8,
-    response_length: i32,
-) -> Result<i32> {
-    let request_data = std::slice::from_raw_parts(request, request_length as usize);
-    let deserialized_request = serde_json::from_slice::<PerformActionRequest>(request_data)?;
+    wire_format: i32,
+) -> Result<Vec<u8>> {
+    let format = wire_format_from_int(wire_format)?;
+    let deserialized_request =
+        decode_request::<PerformActionRequest>(format, request, request_length)?;
     println!("perform_action: {:?}", deserialized_request.metadata.user_id);
-    let scene = test_data::perform_action(&deserialized_request);
-    let json = serde_json::to_string(&scene)?;
-    let json_bytes = json.as_bytes();
+    let registry = registry()?;
+    encode_response(format, &handle_request::perform_action(&registry, &deserialized_request)?)
+}
+
+/// Retrieves updates to the state of an ongoing game which were produced
+/// outside of a user request, such as actions taken by the enemy.
+///
+/// `request` should be a buffer including the serialization of a
+/// `PollRequest` message of `request_length` bytes, encoded in the wire format
+/// identified by `wire_format` (see [WIRE_FORMATS]). On success, `response` is
+/// set to point to a buffer allocated by the plugin containing a serialized
+/// `PollResponse` with any queued commands, or with a full snapshot of the
+/// battle if the request asks to resync. The commands are removed from the
+/// queue once returned. This buffer must be released by calling
+/// [dreamcaller_free].
+///
+/// Returns the length of the buffer in bytes, or -1 on error. Call
+/// [dreamcaller_last_error_owned] for a description of the error.
+#[no_mangle]
+pub unsafe extern "C" fn dreamcaller_poll_owned(
+    request: *const u8,
+    request_length: i32,
+    wire_format: i32,
+    response: *mut *mut u8,
+) -> i32 {
+    error_boundary(|| allocate_response(poll_impl(request, request_length, wire_format)?, response))
+}
+
+unsafe fn poll_impl(request: *const u8, request_length: i32, wire_format: i32) -> Result<Vec<u8>> {
+    let format = wire_format_from_int(wire_format)?;
+    let deserialized_request = decode_request::<PollRequest>(format, request, request_length)?;
+    let registry = registry()?;
+    encode_response(format, &handle_request::poll(&registry, &deserialized_request)?)
+}
+
+/// Returns a description of the most recent error returned by a plugin
+/// function.
+///
+/// On success, `response` is set to point to a buffer allocated by the plugin
+/// containing an `ErrorResponse` serialized in the wire format identified by
+/// `wire_format` (see [WIRE_FORMATS]). This buffer must be released by calling
+/// [dreamcaller_free].
+///
+/// Returns the length of the buffer in bytes, 0 if no error has occurred (in
+/// which case `response` is not modified), or -1 if the wire format is
+/// unknown.
+#[no_mangle]
+pub unsafe extern "C" fn dreamcaller_last_error_owned(
+    wire_format: i32,
+    response: *mut *mut u8,
+) -> i32 {
+    let Some(error) = LAST_ERROR.lock().unwrap().clone() else {
+        return 0;
+    };
+    wire_format_from_int(wire_format)
+        .and_then(|f| encode_response(f, &error))
+        .and_then(|bytes| allocate_response(bytes, response))
+        .unwrap_or(-1)
+}
 
-    if json_bytes.len() > response_length as usize {
-        return Err(anyhow::anyhow!("Response buffer too small"));
+/// Releases a buffer returned by one of the `_owned` plugin functions.
+///
+/// `buffer` and `length` must be exactly the pointer and length returned by
+/// that function, and the buffer must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn dreamcaller_free(buffer: *mut u8, length: i32) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buffer, length as usize)));
     }
+}
 
-    let out = std::slice::from_raw_parts_mut(response, response_length as usize);
-    out[..json_bytes.len()].copy_from_slice(json_bytes);
-    Ok(json_bytes.len() as i32)
+/// Moves `bytes` into a new buffer, storing a pointer to it in `response` and
+/// returning its length.
+unsafe fn allocate_response(bytes: Vec<u8>, response: *mut *mut u8) -> Result<i32> {
+    let bytes = bytes.into_boxed_slice();
+    let length = i32::try_from(bytes.len())
+        .map_err(|e| ErrorResponse::new(ErrorType::Internal, e.to_string()))?;
+    *response = Box::into_raw(bytes).cast::<u8>();
+    Ok(length)
 }
 
+unsafe fn decode_request<T: DeserializeOwned>(
+    format: WireFormat,
+    request: *const u8,
+    request_length: i32,
+) -> Result<T> {
+    let request_data = std::slice::from_raw_parts(request, request_length as usize);
+    wire_format::decode(format, request_data).map_err(invalid_request)
+}
+
+fn encode_response(format: WireFormat, value: &impl Serialize) -> Result<Vec<u8>> {
+    wire_format::encode(format, value)
+        .map_err(|e| ErrorResponse::new(ErrorType::Internal, e.to_string()))
+}
+
+/// Returns the wire format identified by an integer passed to a plugin
+/// function, see [WIRE_FORMATS].
+fn wire_format_from_int(wire_format: i32) -> Result<WireFormat> {
+    usize::try_from(wire_format)
+        .ok()
+        .and_then(|i| WIRE_FORMATS.get(i).copied())
+        .ok_or_else(|| invalid_request(format!("Unknown wire format {wire_format}")))
+}
+
+fn registry() -> Result<Arc<SessionRegistry>> {
+    REGISTRY
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| ErrorResponse::new(ErrorType::Internal, "Plugin has not been initialized"))
+}
+
+fn invalid_request(error: impl Display) -> ErrorResponse {
+    ErrorResponse::new(ErrorType::InvalidRequest, error.to_string())
+}
+
+/// Runs a plugin function, returning -1 and recording the error to be
+/// retrieved via [dreamcaller_last_error_owned] if it fails or panics.
 unsafe fn error_boundary(function: impl FnOnce() -> Result<i32> + UnwindSafe) -> i32 {
-    panic::catch_unwind(|| match function() {
-        Ok(i) => i,
-        Err(e) => {
-            eprintln!("PANIC: {e:?}");
-            -1
-        }
-    })
-    .unwrap_or(-1)
+    let error = match panic::catch_unwind(function) {
+        Ok(Ok(i)) => return i,
+        Ok(Err(error)) => error,
+        Err(payload) => ErrorResponse::from_panic(payload.as_ref()),
+    };
+    eprintln!("ERROR: {error:?}");
+    *LAST_ERROR.lock().unwrap() = Some(error);
+    -1
 }
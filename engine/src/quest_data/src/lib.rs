@@ -0,0 +1,2 @@
+pub mod quest_data;
+pub mod quest_map;
@@ -0,0 +1,66 @@
+use card_data::card_name::CardName;
+use core_data::identifiers::QuestId;
+use core_data::numerics::Essence;
+use deck_data::deck::Deck;
+use rand_xoshiro::Xoshiro256StarStar;
+use serde::{Deserialize, Serialize};
+
+use crate::quest_map::{QuestMap, Site, SiteId};
+
+/// Complete state of an ongoing quest.
+///
+/// A quest is a sequence of dream battles and other encounters which the
+/// player moves through on a [QuestMap]. The player's deck and dreamsigns
+/// persist between battles and grow over the course of the quest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestData {
+    /// Unique identifier for this quest
+    pub id: QuestId,
+
+    /// Deck the player brings into each battle
+    pub deck: Deck,
+
+    /// Dreamsigns the player has collected. These are placed in the player's
+    /// status zone at the start of each battle.
+    pub dreamsigns: Vec<CardName>,
+
+    /// Currency available to spend in shops
+    pub essence: Essence,
+
+    pub map: QuestMap,
+
+    /// Site the player is currently visiting, or None if they have not yet
+    /// entered the map.
+    pub current_site: Option<SiteId>,
+
+    /// Seed which [Self::rng] was created from.
+    ///
+    /// This determines the contents of each site and the seeds of the quest's
+    /// battles, and so must be kept secret. It is 0 for quests saved before
+    /// the seed was recorded.
+    #[serde(default)]
+    pub seed: u64,
+
+    /// Random number generator for this quest
+    pub rng: Xoshiro256StarStar,
+}
+
+impl QuestData {
+    /// Returns the site the player is currently visiting, if any.
+    pub fn site(&self) -> Option<&Site> {
+        self.current_site.and_then(|id| self.map.site(id))
+    }
+
+    /// Returns the sites the player can move to next.
+    ///
+    /// This is the starting site before the map is entered, the connections
+    /// of the current site once it has been completed, and otherwise nothing.
+    pub fn available_sites(&self) -> Vec<SiteId> {
+        match self.site() {
+            None => vec![self.map.start],
+            Some(site) if site.completed => site.connections.clone(),
+            Some(_) => vec![],
+        }
+    }
+}
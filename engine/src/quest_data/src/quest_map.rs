@@ -0,0 +1,76 @@
+use card_data::card_name::CardName;
+use core_data::numerics::Essence;
+use deck_data::deck::Deck;
+use serde::{Deserialize, Serialize};
+
+/// Identifies a site on a [QuestMap].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteId(pub usize);
+
+/// The set of locations a player can visit during a quest.
+///
+/// Sites are connected in a directed graph. The player begins at the
+/// `start` site and can move along connections to later sites, so each path
+/// through the map offers a different sequence of rewards and battles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestMap {
+    pub sites: Vec<Site>,
+
+    /// First site the player visits on this map
+    pub start: SiteId,
+}
+
+impl QuestMap {
+    pub fn site(&self, id: SiteId) -> Option<&Site> {
+        self.sites.get(id.0)
+    }
+
+    pub fn site_mut(&mut self, id: SiteId) -> Option<&mut Site> {
+        self.sites.get_mut(id.0)
+    }
+}
+
+/// A location on the quest map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Site {
+    pub id: SiteId,
+
+    pub kind: SiteKind,
+
+    /// Sites which can be visited after this one
+    pub connections: Vec<SiteId>,
+
+    /// True once the player has finished interacting with this site, e.g. by
+    /// winning its battle or picking a card.
+    pub completed: bool,
+}
+
+/// Possible types of quest sites.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SiteKind {
+    /// A dream battle against an enemy using the provided deck.
+    Battle { enemy: CardName, deck: Deck },
+
+    /// Cards which can be purchased with essence. Any number of offers may be
+    /// bought before leaving the shop.
+    Shop { offers: Vec<ShopOffer> },
+
+    /// Pick one of several cards to add to the quest deck.
+    Draft { choices: Vec<CardName> },
+
+    /// Pick one of several dreamsigns to keep for the rest of the quest.
+    DreamsignReward { choices: Vec<CardName> },
+}
+
+/// A card available for purchase in a shop.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShopOffer {
+    pub card: CardName,
+    pub price: Essence,
+    pub sold: bool,
+}
@@ -0,0 +1,102 @@
+use battle_data::battle_card::Zone;
+use battle_data::battle_data::BattleData;
+use card_data::card_name::CardName;
+use core_data::identifiers::{BattleId, QuestId};
+use core_data::numerics::Energy;
+use core_data::types::Seat;
+use deck_data::deck::Deck;
+use deck_data::decklist;
+use engine::{battle_creation, mutations, quest_creation};
+use uuid::Uuid;
+
+const DECK: &str = "
+4 Titan of Forgotten Echoes
+4 Beacon of Tomorrow
+4 Scrap Reclaimer
+4 Evacuation Enforcer
+4 Moonlit Voyage
+";
+
+fn new_battle() -> BattleData {
+    let deck = decklist::parse(DECK).unwrap();
+    battle_creation::create(BattleId(Uuid::from_u128(1)), 1, &deck, &deck).unwrap()
+}
+
+/// Advances to the start of the user's next turn.
+fn next_user_turn(battle: &mut BattleData) {
+    mutations::end_turn(battle).unwrap();
+    mutations::end_turn(battle).unwrap();
+}
+
+fn revealed_dreamwell_card(battle: &BattleData) -> CardName {
+    battle.card(battle.turn.dreamwell_card.unwrap()).unwrap().name
+}
+
+#[test]
+fn test_first_turn_reveals_dreamwell_card() {
+    let battle = new_battle();
+    assert_eq!(battle.turn.active_player, Seat::Host);
+    assert_eq!(revealed_dreamwell_card(&battle), CardName::Skypath);
+    assert_eq!(battle.user.produced_energy, Energy(1));
+    assert_eq!(battle.user.energy, Energy(1));
+    assert_eq!(battle.enemy.produced_energy, Energy(0));
+}
+
+#[test]
+fn test_energy_production_increases_each_turn() {
+    let mut battle = new_battle();
+    mutations::end_turn(&mut battle).unwrap();
+    assert_eq!(battle.turn.active_player, Seat::Guest);
+    assert_eq!(battle.enemy.energy, Energy(1));
+    mutations::end_turn(&mut battle).unwrap();
+    assert_eq!(revealed_dreamwell_card(&battle), CardName::AutumnGlade);
+    assert_eq!(battle.user.produced_energy, Energy(2));
+    assert_eq!(battle.user.energy, Energy(2));
+}
+
+#[test]
+fn test_dreamwell_abilities() {
+    let mut battle = new_battle();
+    next_user_turn(&mut battle);
+    next_user_turn(&mut battle);
+    assert_eq!(revealed_dreamwell_card(&battle), CardName::TwilightRadiance);
+    assert_eq!(battle.user.produced_energy, Energy(3));
+    assert_eq!(battle.user.energy, Energy(4));
+
+    next_user_turn(&mut battle);
+    assert_eq!(revealed_dreamwell_card(&battle), CardName::AstralInterface);
+    assert_eq!(battle.user.produced_energy, Energy(3));
+    assert_eq!(battle.user.energy, Energy(3));
+
+    next_user_turn(&mut battle);
+    assert_eq!(revealed_dreamwell_card(&battle), CardName::MidnightGathering);
+    assert_eq!(battle.user.produced_energy, Energy(4));
+    assert_eq!(battle.user.energy, Energy(8));
+
+    next_user_turn(&mut battle);
+    assert_eq!(revealed_dreamwell_card(&battle), CardName::Skypath);
+    assert_eq!(battle.user.produced_energy, Energy(5));
+}
+
+#[test]
+fn test_gain_energy_trigger_once_per_turn() {
+    let mut quest =
+        quest_creation::create(QuestId(Uuid::from_u128(2)), 2, decklist::parse(DECK).unwrap());
+    quest.dreamsigns.push(CardName::EmberChalice);
+    let mut battle = battle_creation::for_quest(
+        BattleId(Uuid::from_u128(3)),
+        3,
+        &quest,
+        CardName::Korrak,
+        &Deck::default(),
+    )
+    .unwrap();
+    let hand_size = battle_creation::STARTING_HAND_SIZE + 1;
+    assert_eq!(battle.cards_in_zone(Seat::Host, Zone::Hand).len(), hand_size);
+
+    // Twilight Radiance gains energy twice, but the trigger only fires once.
+    next_user_turn(&mut battle);
+    next_user_turn(&mut battle);
+    assert_eq!(revealed_dreamwell_card(&battle), CardName::TwilightRadiance);
+    assert_eq!(battle.cards_in_zone(Seat::Host, Zone::Hand).len(), hand_size + 2);
+}
@@ -0,0 +1,74 @@
+use ability_data::effect::{Effect, ModalEffect, ModalEffectMode};
+use ability_data::predicate::{CardPredicate, Predicate};
+use ability_data::quantity::Quantity;
+use ability_data::quantity_expression::QuantityExpression;
+use ability_data::standard_effect::StandardEffect;
+use battle_data::battle_card::Zone;
+use battle_data::battle_data::BattleData;
+use core_data::identifiers::BattleId;
+use core_data::types::Seat;
+use deck_data::decklist;
+use engine::effects::EffectError;
+use engine::{battle_creation, effects};
+use uuid::Uuid;
+
+const DECK: &str = "
+4 Titan of Forgotten Echoes
+4 Beacon of Tomorrow
+4 Scrap Reclaimer
+4 Evacuation Enforcer
+4 Moonlit Voyage
+";
+
+fn new_battle() -> BattleData {
+    let deck = decklist::parse(DECK).unwrap();
+    battle_creation::create(BattleId(Uuid::from_u128(1)), 1, &deck, &deck).unwrap()
+}
+
+fn draw_x(x: QuantityExpression) -> Effect {
+    Effect::Effect(StandardEffect::DrawCards { count: Quantity::X(x) })
+}
+
+fn hand_size(battle: &BattleData) -> usize {
+    battle.cards_in_zone(Seat::Host, Zone::Hand).len()
+}
+
+#[test]
+fn test_draw_x_cards_in_void() {
+    let mut battle = new_battle();
+    let deck = battle.cards_in_zone(Seat::Host, Zone::Deck);
+    let discarded = deck.iter().take(3).map(|card| card.id).collect::<Vec<_>>();
+    for id in discarded {
+        battle.move_card(id, Zone::Void);
+    }
+
+    let before = hand_size(&battle);
+    let x = QuantityExpression::Matching(Predicate::YourVoid(CardPredicate::Card));
+    effects::apply(&mut battle, Seat::Host, &draw_x(x)).unwrap();
+    assert_eq!(hand_size(&battle), before + 3);
+}
+
+#[test]
+fn test_unsupported_x_is_an_error() {
+    let mut battle = new_battle();
+    let before = hand_size(&battle);
+    let x = QuantityExpression::AbandonedThisTurn(CardPredicate::Character);
+    assert_eq!(
+        effects::apply(&mut battle, Seat::Host, &draw_x(x.clone())),
+        Err(EffectError::UnsupportedQuantity(x))
+    );
+    assert_eq!(hand_size(&battle), before);
+}
+
+#[test]
+fn test_modal_effect_is_an_error() {
+    let mut battle = new_battle();
+    let before = hand_size(&battle);
+    let draw = Effect::Effect(StandardEffect::DrawCards { count: Quantity::Fixed(1) });
+    let modal = Effect::Modal(ModalEffect {
+        choose: 1,
+        modes: vec![ModalEffectMode { cost: None, effect: draw }],
+    });
+    assert_eq!(effects::apply(&mut battle, Seat::Host, &modal), Err(EffectError::ModalEffect));
+    assert_eq!(hand_size(&battle), before);
+}
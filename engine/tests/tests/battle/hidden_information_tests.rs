@@ -0,0 +1,194 @@
+use ability_data::effect::Effect;
+use ability_data::predicate::CardPredicate;
+use ability_data::standard_effect::StandardEffect;
+use action_data::battle_action::CardBrowserType;
+use battle_data::battle_card::Zone;
+use battle_data::battle_data::BattleData;
+use core_data::identifiers::{BattleId, CardId};
+use core_data::types::{CardFacing, PlayerName, Seat};
+use deck_data::decklist;
+use display_data::battle_view::BattleView;
+use display_data::card_view::CardView;
+use display_data::object_position::Position;
+use engine::{battle_creation, battle_rendering, effects, test_data};
+use uuid::Uuid;
+
+const DECK: &str = "
+4 Titan of Forgotten Echoes
+4 Beacon of Tomorrow
+4 Scrap Reclaimer
+4 Evacuation Enforcer
+4 Moonlit Voyage
+";
+
+fn new_battle() -> BattleData {
+    let deck = decklist::parse(DECK).unwrap();
+    battle_creation::create(BattleId(Uuid::from_u128(1)), 1, &deck, &deck).unwrap()
+}
+
+fn cards_at(view: &BattleView, position: Position) -> Vec<&CardView> {
+    view.cards.iter().filter(|card| card.position.position == position).collect()
+}
+
+fn is_real_id(battle: &BattleData, id: CardId) -> bool {
+    battle.card(id).is_some()
+}
+
+#[test]
+fn test_battle_seed_is_not_derived_from_id() {
+    let id = BattleId(Uuid::from_u128(1));
+    assert_ne!(test_data::new_battle(id).seed, test_data::new_battle(id).seed);
+}
+
+#[test]
+fn test_opponent_hand_is_hidden() {
+    let battle = new_battle();
+    let view = battle_rendering::battle_view(&battle, Seat::Host);
+
+    let enemy_hand = cards_at(&view, Position::InHand(PlayerName::Enemy));
+    assert_eq!(enemy_hand.len(), battle_creation::STARTING_HAND_SIZE);
+    for card in enemy_hand {
+        assert!(card.revealed.is_none());
+        assert_eq!(card.card_facing, CardFacing::FaceDown);
+        assert!(!is_real_id(&battle, card.id));
+    }
+
+    for card in cards_at(&view, Position::InHand(PlayerName::User)) {
+        assert!(card.revealed.is_some());
+        assert!(is_real_id(&battle, card.id));
+    }
+}
+
+#[test]
+fn test_views_differ_per_player() {
+    let battle = new_battle();
+    let enemy_view = battle_rendering::battle_view(&battle, Seat::Guest);
+
+    // The enemy sees their own hand as the user's hand.
+    let own_hand = cards_at(&enemy_view, Position::InHand(PlayerName::User));
+    assert_eq!(own_hand.len(), battle_creation::STARTING_HAND_SIZE);
+    for card in own_hand {
+        assert!(card.revealed.is_some());
+        assert_eq!(battle.card(card.id).unwrap().owner, Seat::Guest);
+    }
+    assert!(cards_at(&enemy_view, Position::InHand(PlayerName::Enemy))
+        .iter()
+        .all(|card| card.revealed.is_none()));
+}
+
+#[test]
+fn test_hidden_ids_are_stable_until_cards_move() {
+    let mut battle = new_battle();
+    let deck_ids = |battle: &BattleData| {
+        let view = battle_rendering::battle_view(battle, Seat::Host);
+        cards_at(&view, Position::InDeck(PlayerName::User))
+            .iter()
+            .map(|card| card.id)
+            .collect::<Vec<_>>()
+    };
+
+    let before = deck_ids(&battle);
+    assert!(before.iter().all(|id| !is_real_id(&battle, *id)));
+    assert_eq!(before, deck_ids(&battle));
+
+    battle.shuffle_deck(Seat::Host);
+    let after = deck_ids(&battle);
+    assert!(after.iter().all(|id| !before.contains(id)));
+}
+
+#[test]
+fn test_browsing_deck_does_not_link_hidden_ids() {
+    let mut battle = new_battle();
+    let view = battle_rendering::battle_view(&battle, Seat::Host);
+    let hidden = cards_at(&view, Position::InDeck(PlayerName::User))
+        .iter()
+        .map(|card| card.id)
+        .collect::<Vec<_>>();
+
+    battle.user.card_browser = Some(CardBrowserType::UserDeck);
+    let view = battle_rendering::battle_view(&battle, Seat::Host);
+    let browsed = cards_at(&view, Position::Browser);
+    assert_eq!(browsed.len(), hidden.len());
+    for card in browsed {
+        assert!(card.revealed.is_some());
+        assert!(is_real_id(&battle, card.id));
+        assert!(!hidden.contains(&card.id));
+    }
+}
+
+#[test]
+fn test_reveal_opponent_hand() {
+    let mut battle = new_battle();
+    let effect = StandardEffect::DiscardCardFromEnemyHand { predicate: CardPredicate::Card };
+    effects::apply(&mut battle, Seat::Host, &Effect::Effect(effect)).unwrap();
+
+    let view = battle_rendering::battle_view(&battle, Seat::Host);
+    for card in cards_at(&view, Position::InHand(PlayerName::Enemy)) {
+        assert!(card.revealed.is_some());
+        assert!(card.revealed_to_opponents);
+        assert!(is_real_id(&battle, card.id));
+    }
+
+    // Cards are hidden again once they change zones, even if they return to
+    // the same zone.
+    let moved = battle.cards_in_zone(Seat::Guest, Zone::Hand)[0].id;
+    battle.move_card(moved, Zone::Deck);
+    battle.move_card(moved, Zone::Hand);
+    let view = battle_rendering::battle_view(&battle, Seat::Host);
+    assert!(view.cards.iter().all(|card| card.id != moved));
+}
+
+#[test]
+fn test_create_position_only_for_newly_revealed_cards() {
+    let mut battle = new_battle();
+    let played = battle.cards_in_zone(Seat::Host, Zone::Hand)[0].id;
+    battle.move_card(played, Zone::Battlefield);
+    let enemy_played = battle.cards_in_zone(Seat::Guest, Zone::Hand)[0].id;
+    battle.move_card(enemy_played, Zone::Battlefield);
+
+    let view = battle_rendering::battle_view(&battle, Seat::Host);
+    let card = |id| view.cards.iter().find(|card| card.id == id).unwrap();
+    assert_eq!(card(played).create_position, None);
+    assert_eq!(
+        card(enemy_played).create_position.as_ref().map(|p| p.position),
+        Some(Position::InHand(PlayerName::Enemy))
+    );
+    for drawn in cards_at(&view, Position::InHand(PlayerName::User)) {
+        assert_eq!(
+            drawn.create_position.as_ref().map(|p| p.position),
+            Some(Position::InDeck(PlayerName::User))
+        );
+    }
+}
+
+#[test]
+fn test_hidden_card_order_does_not_depend_on_identity() {
+    let battle = new_battle();
+    let mut swapped = battle.clone();
+    let hand = swapped.cards_in_zone(Seat::Guest, Zone::Hand)[0].id;
+    let deck = swapped.cards_in_zone(Seat::Guest, Zone::Deck)[0].id;
+    let (hand_zone, hand_key) = {
+        let card = swapped.card(hand).unwrap();
+        (card.zone, card.sorting_key)
+    };
+    let (deck_zone, deck_key) = {
+        let card = swapped.card(deck).unwrap();
+        (card.zone, card.sorting_key)
+    };
+    let card = swapped.card_mut(hand).unwrap();
+    card.zone = deck_zone;
+    card.sorting_key = deck_key;
+    let card = swapped.card_mut(deck).unwrap();
+    card.zone = hand_zone;
+    card.sorting_key = hand_key;
+
+    let hidden_positions = |battle: &BattleData| {
+        battle_rendering::battle_view(battle, Seat::Host)
+            .cards
+            .into_iter()
+            .filter(|card| card.revealed.is_none())
+            .map(|card| card.position)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(hidden_positions(&battle), hidden_positions(&swapped));
+}
@@ -0,0 +1,5 @@
+mod dreamwell_tests;
+mod effect_tests;
+mod hidden_information_tests;
+mod play_card_tests;
+mod query_tests;
@@ -0,0 +1,57 @@
+use action_data::battle_action::BattleAction;
+use action_data::user_action::UserAction;
+use battle_data::battle_card::Zone;
+use battle_data::battle_data::BattleData;
+use core_data::identifiers::{BattleId, CardId};
+use core_data::numerics::Energy;
+use core_data::types::Seat;
+use deck_data::decklist;
+use engine::request_error::RequestError;
+use engine::{battle_creation, legal_actions, queries, test_data};
+use uuid::Uuid;
+
+const DECK: &str = "
+4 Titan of Forgotten Echoes
+4 Scrap Reclaimer
+4 Evacuation Enforcer
+";
+
+fn new_battle() -> BattleData {
+    let deck = decklist::parse(DECK).unwrap();
+    battle_creation::create(BattleId(Uuid::from_u128(1)), 1, &deck, &deck).unwrap()
+}
+
+/// Returns a card in the user's hand along with its cost.
+fn card_in_hand(battle: &BattleData) -> (CardId, Energy) {
+    let card = battle.cards_in_zone(Seat::Host, Zone::Hand)[0];
+    (card.id, queries::cost(battle, card).unwrap())
+}
+
+fn play(id: CardId) -> UserAction {
+    UserAction::BattleAction(BattleAction::PlayCard(id))
+}
+
+#[test]
+fn test_unaffordable_card_cannot_be_played() {
+    let mut battle = new_battle();
+    let (id, cost) = card_in_hand(&battle);
+    battle.user.energy = Energy(cost.0 - 1);
+
+    assert!(!legal_actions::is_legal(&battle, Seat::Host, play(id)));
+    let result = test_data::perform_action(&mut battle, Seat::Host, play(id));
+    assert!(matches!(result, Err(RequestError::IllegalAction(_))));
+    assert_eq!(battle.card(id).unwrap().zone, Zone::Hand);
+    assert_eq!(battle.user.energy, Energy(cost.0 - 1));
+}
+
+#[test]
+fn test_playing_card_spends_energy() {
+    let mut battle = new_battle();
+    let (id, cost) = card_in_hand(&battle);
+    battle.user.energy = cost + Energy(1);
+
+    assert!(legal_actions::is_legal(&battle, Seat::Host, play(id)));
+    test_data::perform_action(&mut battle, Seat::Host, play(id)).unwrap();
+    assert_ne!(battle.card(id).unwrap().zone, Zone::Hand);
+    assert_eq!(battle.user.energy, Energy(1));
+}
@@ -0,0 +1,72 @@
+use ability_data::predicate::{CardPredicate, Operator, Predicate};
+use ability_data::quantity_expression::QuantityExpression;
+use battle_data::battle_card::{BattleCard, Zone};
+use battle_data::battle_data::BattleData;
+use card_data::card_name::CardName;
+use core_data::character_type::CharacterType;
+use core_data::identifiers::{BattleId, CardId};
+use core_data::numerics::Energy;
+use core_data::types::Seat;
+use deck_data::decklist;
+use engine::{battle_creation, queries};
+use uuid::Uuid;
+
+const DECK: &str = "
+4 Titan of Forgotten Echoes
+4 Beacon of Tomorrow
+4 Scrap Reclaimer
+4 Evacuation Enforcer
+4 Moonlit Voyage
+";
+
+fn new_battle() -> BattleData {
+    let deck = decklist::parse(DECK).unwrap();
+    battle_creation::create(BattleId(Uuid::from_u128(1)), 1, &deck, &deck).unwrap()
+}
+
+fn card(battle: &BattleData, id: CardId) -> &BattleCard {
+    battle.card(id).unwrap()
+}
+
+fn non_warrior_character() -> CardPredicate {
+    CardPredicate::And(vec![
+        CardPredicate::Character,
+        CardPredicate::Not(Box::new(CardPredicate::CharacterType(CharacterType::Warrior))),
+    ])
+}
+
+#[test]
+fn test_non_warrior_character_excludes_warriors() {
+    let mut battle = new_battle();
+    let warrior = battle.create_card(CardName::EmberwardVanguard, Seat::Host, Zone::Void);
+    let tinkerer = battle.create_card(CardName::ScrapReclaimer, Seat::Host, Zone::Void);
+    let predicate = non_warrior_character();
+    assert!(!queries::matches(card(&battle, warrior), Seat::Host, &predicate));
+    assert!(queries::matches(card(&battle, tinkerer), Seat::Host, &predicate));
+
+    let x = QuantityExpression::Matching(Predicate::YourVoid(predicate));
+    assert_eq!(queries::quantity(&battle, Seat::Host, &x), Some(1));
+}
+
+#[test]
+fn test_with_cost_compares_printed_cost() {
+    let mut battle = new_battle();
+    let id = battle.create_card(CardName::EmberwardVanguard, Seat::Host, Zone::Hand);
+    let matches = |operator| {
+        let predicate = CardPredicate::WithCost(Energy(2), operator);
+        queries::matches(card(&battle, id), Seat::Host, &predicate)
+    };
+    assert!(matches(Operator::OrMore));
+    assert!(!matches(Operator::OrLess));
+    assert!(!matches(Operator::Exactly));
+}
+
+#[test]
+fn test_negated_unsupported_predicate_never_matches() {
+    let mut battle = new_battle();
+    let id = battle.create_card(CardName::ScrapReclaimer, Seat::Host, Zone::Hand);
+    let unsupported = CardPredicate::CharacterWithMaterializedAbility;
+    assert!(!queries::matches(card(&battle, id), Seat::Host, &unsupported));
+    let negated = CardPredicate::Not(Box::new(unsupported));
+    assert!(!queries::matches(card(&battle, id), Seat::Host, &negated));
+}
@@ -0,0 +1 @@
+pub mod save_tests;
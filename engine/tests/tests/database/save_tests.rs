@@ -0,0 +1,31 @@
+use core_data::identifiers::UserId;
+use database::database::Database;
+use database::file_database::FileDatabase;
+use database::save_file::SaveFile;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// Creates a database in a new temporary directory. The directory is deleted
+/// when the returned [TempDir] is dropped, so it must be kept alive for as
+/// long as the database is in use.
+pub fn new_database() -> (TempDir, FileDatabase) {
+    let directory = TempDir::new().unwrap();
+    let database = FileDatabase::new(directory.path()).unwrap();
+    (directory, database)
+}
+
+#[test]
+fn test_missing_save_file() {
+    let (_directory, database) = new_database();
+    assert!(database.fetch_save(UserId(Uuid::new_v4())).unwrap().is_none());
+}
+
+#[test]
+fn test_save_files_are_per_user() {
+    let (_directory, database) = new_database();
+    let user_one = UserId(Uuid::new_v4());
+    let user_two = UserId(Uuid::new_v4());
+    database.write_save(&SaveFile::new(user_one)).unwrap();
+    assert!(database.fetch_save(user_one).unwrap().is_some());
+    assert!(database.fetch_save(user_two).unwrap().is_none());
+}
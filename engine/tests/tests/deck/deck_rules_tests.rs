@@ -0,0 +1,41 @@
+use card_data::card_name::CardName;
+use core_data::types::Rarity;
+use deck_data::deck_rules::{DeckRuleViolation, DeckRules};
+use deck_data::decklist;
+
+#[test]
+fn test_legal_deck() {
+    let deck = decklist::parse(
+        "4 Titan of Forgotten Echoes
+        4 Beacon of Tomorrow
+        4 Scrap Reclaimer
+        4 Evacuation Enforcer
+        4 Moonlit Voyage",
+    )
+    .unwrap();
+    assert!(DeckRules::default().is_legal(&deck));
+}
+
+#[test]
+fn test_too_few_cards() {
+    let deck = decklist::parse("4 Scrap Reclaimer").unwrap();
+    assert_eq!(DeckRules::default().validate(&deck), vec![DeckRuleViolation::TooFewCards {
+        count: 4,
+        minimum: 20
+    }]);
+}
+
+#[test]
+fn test_copy_and_rarity_limits() {
+    let rules = DeckRules { minimum_size: 0, ..DeckRules::default() };
+    let deck = decklist::parse("9 Titan of Forgotten Echoes\n1 Dragon Egg").unwrap();
+    assert_eq!(rules.validate(&deck), vec![
+        DeckRuleViolation::TooManyCopies {
+            card: CardName::TitanOfForgottenEchoes,
+            count: 9,
+            maximum: 4
+        },
+        DeckRuleViolation::NotADeckCard(CardName::DragonEgg),
+        DeckRuleViolation::RarityCapExceeded { rarity: Rarity::Rare, count: 10, maximum: 8 },
+    ]);
+}
@@ -0,0 +1,52 @@
+use card_data::card_name::CardName;
+use deck_data::decklist;
+use deck_data::decklist::DecklistErrorKind;
+use insta::assert_ron_snapshot;
+
+#[test]
+fn test_parse_decklist() {
+    let result = decklist::parse(
+        "# Main deck
+        3 Moonlit Voyage
+        2x beacon of tomorrow
+
+        Scrap Reclaimer",
+    )
+    .unwrap();
+    assert_ron_snapshot!(result, @r###"
+    Deck(
+      cards: {
+        beaconOfTomorrow: 2,
+        scrapReclaimer: 1,
+        moonlitVoyage: 3,
+      },
+    )
+    "###);
+}
+
+#[test]
+fn test_duplicate_lines_are_combined() {
+    let result = decklist::parse("2 Moonlit Voyage\n1 Moonlit Voyage").unwrap();
+    assert_eq!(result.count(CardName::MoonlitVoyage), 3);
+}
+
+#[test]
+fn test_unknown_card() {
+    let error = decklist::parse("1 Moonlit Voyage\n2 Lightning Bolt").unwrap_err();
+    assert_eq!(error.line_number, 2);
+    assert_eq!(error.kind, DecklistErrorKind::UnknownCard("Lightning Bolt".to_string()));
+}
+
+#[test]
+fn test_invalid_count() {
+    let error = decklist::parse("0 Moonlit Voyage").unwrap_err();
+    assert_eq!(error.kind, DecklistErrorKind::InvalidCount);
+}
+
+#[test]
+fn test_format_round_trip() {
+    let deck = decklist::parse("4 Titan of Forgotten Echoes\n1 Scrap Reclaimer").unwrap();
+    let text = decklist::format(&deck);
+    assert_eq!(text, "4 Titan of Forgotten Echoes\n1 Scrap Reclaimer\n");
+    assert_eq!(decklist::parse(&text).unwrap(), deck);
+}
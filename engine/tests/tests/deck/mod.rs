@@ -0,0 +1,2 @@
+mod deck_rules_tests;
+mod decklist_tests;
@@ -1 +1,6 @@
+mod battle;
+mod database;
+mod deck;
 mod parser;
+mod quest;
+mod session;
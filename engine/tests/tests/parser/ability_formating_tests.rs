@@ -1,4 +1,5 @@
 use insta::assert_ron_snapshot;
+use parser::{ability_parser, ability_serializer};
 
 use crate::parser::test_utils::parse;
 
@@ -10,10 +11,10 @@ fn test_multiple_abilities_with_br() {
         @r###"
     [
       event(effect(drawCards(
-        count: 1,
+        count: fixed(1),
       ))),
       event(effect(gainEnergy(
-        gains: Energy(2),
+        gains: fixed(Energy(2)),
       ))),
     ]
     "###
@@ -26,7 +27,7 @@ fn test_flavor_text() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(drawCards(
-        count: 1,
+        count: fixed(1),
       ))),
     ]
     "###);
@@ -42,7 +43,7 @@ fn test_multiple_abilities_with_flavor() {
         @r###"
     [
       event(effect(drawCards(
-        count: 1,
+        count: fixed(1),
       ))),
       event(effect(payCost(
         cost: discardCards(card, 1),
@@ -58,7 +59,7 @@ fn test_reminder_text() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(drawCards(
-        count: 1,
+        count: fixed(1),
       ))),
     ]
     "###);
@@ -74,7 +75,7 @@ fn test_multiple_abilities_with_reminder() {
         @r###"
     [
       event(effect(drawCards(
-        count: 1,
+        count: fixed(1),
       ))),
       event(effect(payCost(
         cost: discardCards(card, 1),
@@ -94,7 +95,7 @@ fn test_reminder_and_flavor() {
         @r###"
     [
       event(effect(drawCards(
-        count: 1,
+        count: fixed(1),
       ))),
       event(effect(payCost(
         cost: discardCards(card, 1),
@@ -103,3 +104,36 @@ fn test_reminder_and_flavor() {
     "###
     );
 }
+
+#[test]
+fn test_keywords_ignore_case() {
+    assert_eq!(
+        parse("$FASTACTIVATED $2: DISSOLVE AN ENEMY CHARACTER WITH COST $2 OR LESS."),
+        parse("$fastactivated $2: dissolve an enemy character with cost $2 or less.")
+    );
+}
+
+#[test]
+fn test_error_span_in_original_text() {
+    let text = "Dissolve an enemy charactr.";
+    let errors = ability_parser::parse(text).into_errors();
+    assert_eq!(&text[errors[0].span().into_range()], "charactr");
+}
+
+#[test]
+fn test_recovers_at_br() {
+    let (result, errors) =
+        ability_parser::parse("Draw a card. $br Dissolve an enemy charactr. $br Gain $2.")
+            .into_output_errors();
+    assert_eq!(ability_serializer::serialize(&result.unwrap()), "Draw a card. $br Gain $2.");
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn test_recovers_at_sentence_end() {
+    let text = "Gain $2. Negate an enemy drem. {reminder: It's gone.} Draw a card.";
+    let (result, errors) = ability_parser::parse(text).into_output_errors();
+    assert_eq!(ability_serializer::serialize(&result.unwrap()), "Gain $2. $br Draw a card.");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span().start, text.find("drem").unwrap());
+}
@@ -14,11 +14,10 @@ fn test_banish_from_void_dissolve_enemy_character() {
           banishCardsFromYourVoid(3),
         ],
         effect: effect(dissolveCharacter(
-          target: enemy(cardWithCost(
-            target: character,
-            cost_operator: orLess,
-            cost: Energy(2),
-          )),
+          target: enemy(and([
+            character,
+            withCost(Energy(2), orLess),
+          ])),
         )),
         options: None,
       )),
@@ -86,7 +85,7 @@ fn test_multi_activated_draw() {
           energy(Energy(2)),
         ],
         effect: effect(drawCards(
-          count: 1,
+          count: fixed(1),
         )),
         options: Some(ActivatedAbilityOptions(
           isFast: false,
@@ -108,10 +107,13 @@ fn test_abandon_character_with_spark() {
     [
       activated(ActivatedAbility(
         costs: [
-          abandonCharacters(another(characterWithSpark(Spark(2), orLess)), 1),
+          abandonCharacters(another(and([
+            character,
+            withSpark(Spark(2), orLess),
+          ])), 1),
         ],
         effect: effect(drawCards(
-          count: 1,
+          count: fixed(1),
         )),
         options: None,
       )),
@@ -129,10 +131,13 @@ fn test_activated_ability_multiple_costs() {
       activated(ActivatedAbility(
         costs: [
           energy(Energy(2)),
-          abandonCharacters(another(characterWithSpark(Spark(1), orLess)), 1),
+          abandonCharacters(another(and([
+            character,
+            withSpark(Spark(1), orLess),
+          ])), 1),
         ],
         effect: effect(drawCards(
-          count: 2,
+          count: fixed(2),
         )),
         options: Some(ActivatedAbilityOptions(
           isFast: false,
@@ -156,7 +161,7 @@ fn test_discard_hand_gain_energy() {
           discardHand,
         ],
         effect: effect(gainEnergy(
-          gains: Energy(1),
+          gains: fixed(Energy(1)),
         )),
         options: None,
       )),
@@ -179,7 +184,7 @@ fn test_multiple_costs_abandon_this() {
           discardHand,
         ],
         effect: effect(drawCards(
-          count: 3,
+          count: fixed(3),
         )),
         options: None,
       )),
@@ -223,14 +228,14 @@ fn test_discard_card_draw_gain_point() {
         effect: list([
           EffectWithOptions(
             effect: drawCards(
-              count: 1,
+              count: fixed(1),
             ),
             optional: None,
             condition: None,
           ),
           EffectWithOptions(
             effect: gainPoints(
-              gains: Points(1),
+              gains: fixed(Points(1)),
             ),
             optional: None,
             condition: None,
@@ -255,7 +260,7 @@ fn test_discard_warriors_gain_energy() {
           discardCards(characterType(warrior), 2),
         ],
         effect: effect(gainEnergy(
-          gains: Energy(1),
+          gains: fixed(Energy(1)),
         )),
         options: None,
       )),
@@ -288,6 +293,7 @@ fn test_immediate_activated_banish_materialize() {
             ),
             optional: None,
             condition: None,
+            then: true,
           ),
         ]),
         options: Some(ActivatedAbilityOptions(
@@ -311,7 +317,7 @@ fn test_immediate_multi_activated() {
           abandonCharacters(another(character), 1),
         ],
         effect: effect(putCardsFromYourDeckIntoVoid(
-          count: 2,
+          count: fixed(2),
         )),
         options: Some(ActivatedAbilityOptions(
           isFast: false,
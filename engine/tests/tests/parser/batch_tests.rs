@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use ability_data::ability::AbilityKind;
+use parser_cli::batch;
+
+#[test]
+fn test_batch_groups_errors_by_ability_kind_and_expected() {
+    let report =
+        batch::run(&Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/cards.json")).unwrap();
+    assert_eq!(report.passed, 2);
+    assert_eq!(report.failed, 4);
+
+    let groups = report
+        .errors
+        .iter()
+        .map(|group| (group.ability_kind, group.expected.join(", "), group.cards.join(", ")))
+        .collect::<Vec<_>>();
+    assert_eq!(groups, vec![
+        (
+            AbilityKind::Event,
+            "a card predicate".to_string(),
+            "Ripple of Defiance, Dreamscatter".to_string()
+        ),
+        (AbilityKind::Activated, "an effect".to_string(), "Minstrel of Falling Light".to_string()),
+        (AbilityKind::Triggered, "an effect".to_string(), "Sundown Surfer".to_string()),
+    ]);
+}
+
+#[test]
+fn test_batch_reports_effects() {
+    let report =
+        batch::run(&Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/cards.json")).unwrap();
+    let immolate = report.cards.iter().find(|card| card.name == "Immolate").unwrap();
+    assert!(immolate.passed);
+    assert_eq!(immolate.effects, vec!["dissolveCharacter"]);
+    assert!(immolate.errors.is_empty());
+}
@@ -0,0 +1,126 @@
+use ability_data::ability::Ability;
+use ability_data::standard_effect::StandardEffectKind;
+use card_data::card_definitions;
+use parser::ability_diagnostics::{self, Diagnostic, EFFECT_TEMPLATES};
+use parser::ability_parser;
+use strum::IntoEnumIterator;
+
+/// Parses `text`, which must fail to parse, and returns the diagnostic for
+/// the first error.
+fn diagnose(text: &str) -> Diagnostic {
+    let errors = ability_parser::parse(text).into_errors();
+    ability_diagnostics::diagnose(text, errors.first().expect("Expected a parse error"))
+}
+
+#[test]
+fn test_misspelled_card_predicate() {
+    let text = "Dissolve an enemy charactr.";
+    let diagnostic = diagnose(text);
+    assert_eq!(diagnostic.message, "expected a card predicate after 'Dissolve an enemy'");
+    assert_eq!(&text[diagnostic.span], "charactr");
+    assert_eq!(diagnostic.label, "found 'charactr'");
+    assert_eq!(diagnostic.suggestion.as_deref(), Some("dissolve an enemy character"));
+}
+
+#[test]
+fn test_misspelled_effect_after_cost() {
+    let diagnostic = diagnose("$activated $2: Drw a card.");
+    assert_eq!(diagnostic.message, "expected an effect");
+    assert_eq!(diagnostic.suggestion.as_deref(), Some("draw a card"));
+}
+
+#[test]
+fn test_error_in_second_sentence() {
+    let diagnostic = diagnose("Gain $2. Negate an enemy drem.");
+    assert_eq!(diagnostic.message, "expected a card predicate after 'Negate an enemy'");
+    assert_eq!(diagnostic.suggestion.as_deref(), Some("negate an enemy dream"));
+}
+
+#[test]
+fn test_error_at_multibyte_character() {
+    let text = "Dissolve an énemy character.";
+    let diagnostic = diagnose(text);
+    assert_eq!(&text[diagnostic.span], "énemy");
+    assert_eq!(diagnostic.suggestion.as_deref(), Some("dissolve an enemy character"));
+}
+
+#[test]
+fn test_suggestion_for_multiple_sentences() {
+    let diagnostic =
+        diagnose("Look at the enemy's hand. Chose a card from it. The enemy discards that card.");
+    assert_eq!(
+        diagnostic.suggestion.as_deref(),
+        Some("look at the enemy's hand. Choose a card from it. The enemy discards that card")
+    );
+}
+
+#[test]
+fn test_missing_period() {
+    let diagnostic = diagnose("Draw a card");
+    assert_eq!(diagnostic.label, "found end of input");
+    assert_eq!(diagnostic.suggestion, None);
+}
+
+#[test]
+fn test_modal_effect_without_choice() {
+    let diagnostic = diagnose("Choose two: $br • Draw a card. $br • Gain $1.");
+    assert_eq!(diagnostic.message, "Choosing 2 requires more than 2 modes, but there are 2");
+}
+
+#[test]
+fn test_effect_templates_parse_and_cover_every_effect() {
+    // Templates are parsed as the paragraphs of a single text, since building
+    // the parser dominates the cost of parsing short texts.
+    let text = EFFECT_TEMPLATES
+        .iter()
+        .map(|template| format!("{template}."))
+        .collect::<Vec<_>>()
+        .join("$br");
+    let (abilities, errors) = ability_parser::parse(&text).into_output_errors();
+    assert!(errors.is_empty(), "Error parsing effect templates: {errors:?}");
+    let abilities = abilities.unwrap_or_default();
+    assert_eq!(abilities.len(), EFFECT_TEMPLATES.len());
+
+    let mut kinds = vec![];
+    for (template, ability) in EFFECT_TEMPLATES.iter().zip(&abilities) {
+        let Ability::Event(effect) = ability else {
+            panic!("Template {template:?} is not an event");
+        };
+        kinds.extend(effect.standard_effects().into_iter().map(StandardEffectKind::from));
+    }
+    // Not produced by the parser, so has no phrasing to suggest.
+    let unparsed = [StandardEffectKind::ThenMaterializeIt];
+    let missing = StandardEffectKind::iter()
+        .filter(|kind| !kinds.contains(kind) && !unparsed.contains(kind))
+        .collect::<Vec<_>>();
+    assert!(missing.is_empty(), "No effect template for {missing:?}");
+}
+
+#[test]
+fn test_reminder_text_matches_keywords() {
+    let text =
+        "Draw 2 cards. Discard 2 cards.$br{kw: Reclaim}. {reminder: (You may play this card \
+                from your void, then banish it.)}";
+    assert!(ability_diagnostics::reminder_text_errors(text).is_empty());
+}
+
+#[test]
+fn test_hand_written_reminder_text_is_reported() {
+    let text =
+        "Draw 2 cards. Discard 2 cards.$br{kw: Reclaim}. {reminder: (you may play this dream \
+                from your void, then banish it.)}";
+    assert_eq!(ability_diagnostics::reminder_text_errors(text), vec![
+        "reminder text 'you may play this dream from your void, then banish it.' should be 'You \
+         may play this card from your void, then banish it.'"
+    ]);
+}
+
+#[test]
+fn test_card_reminder_text_matches_keywords() {
+    for definition in card_definitions::all() {
+        if let Some(text) = definition.ability_text {
+            let errors = ability_diagnostics::reminder_text_errors(text);
+            assert!(errors.is_empty(), "Incorrect reminder text in '{text}': {errors:?}");
+        }
+    }
+}
@@ -17,14 +17,14 @@ fn test_multiple_effects() {
           EffectWithOptions(
             effect: gainsSpark(
               target: this,
-              gains: Spark(1),
+              gains: fixed(Spark(1)),
             ),
             optional: None,
             condition: None,
           ),
           EffectWithOptions(
             effect: banishCardsFromEnemyVoid(
-              count: 1,
+              count: fixed(1),
             ),
             optional: Some(noCost),
             condition: None,
@@ -44,7 +44,7 @@ fn test_then_separator() {
       event(list([
         EffectWithOptions(
           effect: drawCards(
-            count: 1,
+            count: fixed(1),
           ),
           optional: None,
           condition: None,
@@ -55,6 +55,7 @@ fn test_then_separator() {
           ),
           optional: None,
           condition: None,
+          then: true,
         ),
       ])),
     ]
@@ -70,7 +71,7 @@ fn test_optional_draw() {
     [
       event(withOptions(EffectWithOptions(
         effect: drawCards(
-          count: 1,
+          count: fixed(1),
         ),
         optional: Some(noCost),
         condition: None,
@@ -89,7 +90,7 @@ fn test_conditional_gain_energy() {
     [
       event(withOptions(EffectWithOptions(
         effect: gainEnergy(
-          gains: Energy(1),
+          gains: fixed(Energy(1)),
         ),
         optional: None,
         condition: Some(predicateCount(
@@ -111,7 +112,7 @@ fn test_conditional_optional_gain_energy() {
     [
       event(withOptions(EffectWithOptions(
         effect: gainEnergy(
-          gains: Energy(1),
+          gains: fixed(Energy(1)),
         ),
         optional: Some(noCost),
         condition: Some(predicateCount(
@@ -133,7 +134,7 @@ fn test_until_end_of_turn() {
         trigger: TriggeredAbility(
           trigger: play(your(character)),
           effect: effect(drawCards(
-            count: 1,
+            count: fixed(1),
           )),
           options: Some(TriggeredAbilityOptions(
             oncePerTurn: false,
@@ -175,7 +176,7 @@ fn test_optional_cost_banish_enemy() {
         costs: [],
         effect: withOptions(EffectWithOptions(
           effect: gainEnergy(
-            gains: Energy(1),
+            gains: fixed(Energy(1)),
           ),
           optional: Some(banishCardsFromEnemyVoid(1)),
           condition: None,
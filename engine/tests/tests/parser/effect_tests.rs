@@ -33,12 +33,11 @@ fn test_materialize_random_characters() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(materializeRandomFromDeck(
-        count: 2,
-        predicate: cardWithCost(
-          target: character,
-          cost_operator: orLess,
-          cost: Energy(3),
-        ),
+        count: fixed(2),
+        predicate: and([
+          character,
+          withCost(Energy(3), orLess),
+        ]),
       ))),
     ]
     "###);
@@ -77,7 +76,27 @@ fn test_spend_all_energy_draw_discard() {
     let result = parse("Spend all your remaining energy. Draw X cards then discard X cards, where X is the energy spent this way.");
     assert_ron_snapshot!(result, @r###"
     [
-      event(effect(spendAllEnergyDrawAndDiscard)),
+      event(list([
+        EffectWithOptions(
+          effect: spendAllEnergy,
+          optional: None,
+          condition: None,
+        ),
+        EffectWithOptions(
+          effect: drawCards(
+            count: x(energySpentThisWay),
+          ),
+          optional: None,
+          condition: None,
+        ),
+        EffectWithOptions(
+          effect: discardCards(
+            count: x(energySpentThisWay),
+          ),
+          optional: None,
+          condition: None,
+        ),
+      ])),
     ]
     "###);
 }
@@ -113,25 +132,23 @@ fn test_discard_card_from_enemy_hand() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(discardCardFromEnemyHand(
-        predicate: cardWithCost(
-          target: card,
-          cost_operator: orLess,
-          cost: Energy(3),
-        ),
+        predicate: and([
+          card,
+          withCost(Energy(3), orLess),
+        ]),
       ))),
     ]
     "###);
 }
 
 #[test]
-fn test_each_matching_gains_spark_for_each() {
+fn test_each_matching_gains_spark_x() {
     let result = parse("Each {cardtype: spirit animal} you control gains +X spark, where X is the number of {cardtype: spirit animals} you control.");
     assert_ron_snapshot!(result, @r###"
     [
-      event(effect(eachMatchingGainsSparkForEach(
+      event(effect(eachMatchingGainsSpark(
         each: characterType(spiritAnimal),
-        gains: Spark(1),
-        for_each: characterType(spiritAnimal),
+        gains: x(matching(your(characterType(spiritAnimal)))),
       ))),
     ]
     "###);
@@ -173,6 +190,7 @@ fn test_banish_then_materialize() {
             ),
             optional: None,
             condition: None,
+            then: true,
           ),
         ]),
         options: None,
@@ -201,6 +219,7 @@ fn test_banish_any_number_then_materialize() {
           ),
           optional: None,
           condition: None,
+          then: true,
         ),
       ])),
     ]
@@ -227,6 +246,7 @@ fn test_banish_up_to_two() {
           ),
           optional: None,
           condition: None,
+          then: true,
         ),
       ])),
     ]
@@ -259,6 +279,7 @@ fn test_banish_up_to_two_activated() {
             ),
             optional: None,
             condition: None,
+            then: true,
           ),
         ]),
         options: None,
@@ -355,11 +376,10 @@ fn test_discover_card_with_cost() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(discover(
-        predicate: cardWithCost(
-          target: card,
-          cost_operator: exactly,
-          cost: Energy(2),
-        ),
+        predicate: and([
+          card,
+          withCost(Energy(2), exactly),
+        ]),
       ))),
     ]
     "###);
@@ -391,7 +411,7 @@ fn test_foresee() {
           materialized,
         ]),
         effect: effect(foresee(
-          count: 2,
+          count: fixed(2),
         )),
         options: None,
       )),
@@ -416,7 +436,7 @@ fn test_lose_points() {
         ),
         EffectWithOptions(
           effect: losePoints(
-            loses: Points(4),
+            loses: fixed(Points(4)),
           ),
           optional: None,
           condition: None,
@@ -446,7 +466,7 @@ fn test_enemy_points() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(enemyGainsPoints(
-        count: 2,
+        count: fixed(Points(2)),
       ))),
     ]
     "###);
@@ -455,7 +475,7 @@ fn test_enemy_points() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(enemyLosesPoints(
-        count: 1,
+        count: fixed(Points(1)),
       ))),
     ]
     "###);
@@ -467,11 +487,10 @@ fn test_banish_character() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(banishCharacter(
-        target: enemy(cardWithCost(
-          target: character,
-          cost_operator: orLess,
-          cost: Energy(2),
-        )),
+        target: enemy(and([
+          character,
+          withCost(Energy(2), orLess),
+        ])),
       ))),
     ]
     "###);
@@ -488,7 +507,10 @@ fn test_enemy_gains_points_equal_to_its_spark() {
       event(list([
         EffectWithOptions(
           effect: banishCharacter(
-            target: enemy(notCharacterType(warrior)),
+            target: enemy(and([
+              character,
+              not(characterType(warrior)),
+            ])),
           ),
           optional: None,
           condition: None,
@@ -512,11 +534,10 @@ fn test_materialize_character_from_void() {
         trigger: play(your(characterType(warrior))),
         effect: withOptions(EffectWithOptions(
           effect: materializeCharacterFromVoid(
-            target: cardWithCost(
-              target: character,
-              cost_operator: orLess,
-              cost: Energy(3),
-            ),
+            target: and([
+              character,
+              withCost(Energy(3), orLess),
+            ]),
           ),
           optional: Some(noCost),
           condition: None,
@@ -558,11 +579,10 @@ fn test_gain_control() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(gainControl(
-        target: enemy(cardWithCost(
-          target: character,
-          cost_operator: orLess,
-          cost: Energy(2),
-        )),
+        target: enemy(and([
+          character,
+          withCost(Energy(2), orLess),
+        ])),
       ))),
     ]
     "###);
@@ -574,11 +594,10 @@ fn test_return_to_hand() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(returnToHand(
-        target: your(cardWithCost(
-          target: character,
-          cost_operator: orMore,
-          cost: Energy(3),
-        )),
+        target: your(and([
+          character,
+          withCost(Energy(3), orMore),
+        ])),
       ))),
     ]
     "###);
@@ -800,7 +819,7 @@ fn test_shuffle_hand_and_deck_and_draw() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(shuffleHandAndDeckAndDraw(
-        count: 4,
+        count: fixed(4),
       ))),
     ]
     "###);
@@ -825,7 +844,7 @@ fn test_put_cards_from_deck_into_void() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(putCardsFromYourDeckIntoVoid(
-        count: 3,
+        count: fixed(3),
       ))),
     ]
     "###);
@@ -853,7 +872,7 @@ fn test_each_matching_gains_spark_until_next_main() {
     [
       event(effect(eachMatchingGainsSparkUntilNextMain(
         each: characterType(spiritAnimal),
-        gains: Spark(2),
+        gains: fixed(Spark(2)),
       ))),
     ]
     "###);
@@ -888,7 +907,7 @@ fn test_spark_becomes() {
         effect: effect(sparkBecomes(
           collection: all,
           matching: characterType(spiritAnimal),
-          spark: Spark(5),
+          spark: fixed(Spark(5)),
         )),
         options: None,
       )),
@@ -949,6 +968,7 @@ fn test_materialize_character_at_end_of_turn() {
           ),
           optional: None,
           condition: None,
+          then: true,
         ),
       ])),
     ]
@@ -1021,7 +1041,7 @@ fn test_each_player_discard_cards() {
           materialized,
         ]),
         effect: effect(eachPlayerDiscardCards(
-          count: 1,
+          count: fixed(1),
         )),
         options: None,
       )),
@@ -1035,7 +1055,7 @@ fn test_put_cards_from_void_on_top_of_deck() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(putCardsFromVoidOnTopOfDeck(
-        count: 1,
+        count: fixed(1),
         matching: character,
       ))),
     ]
@@ -1050,7 +1070,7 @@ fn test_put_cards_from_void_on_top_of_deck() {
         ],
         effect: withOptions(EffectWithOptions(
           effect: putCardsFromVoidOnTopOfDeck(
-            count: 1,
+            count: fixed(1),
             matching: character,
           ),
           optional: Some(noCost),
@@ -1077,7 +1097,7 @@ fn test_materialize_silent_copy() {
         ]),
         effect: effect(materializeSilentCopy(
           target: this,
-          count: 1,
+          count: fixed(1),
           quantity: playedThisTurn(dream),
         )),
         options: None,
@@ -1094,7 +1114,7 @@ fn test_materialize_silent_copy() {
         ]),
         effect: effect(materializeSilentCopy(
           target: this,
-          count: 2,
+          count: fixed(2),
           quantity: matching(this),
         )),
         options: None,
@@ -1110,7 +1130,7 @@ fn test_materialize_silent_copy() {
         trigger: play(your(character)),
         effect: effect(materializeSilentCopy(
           target: this,
-          count: 1,
+          count: fixed(1),
           quantity: matching(this),
         )),
         options: None,
@@ -1169,7 +1189,7 @@ fn test_each_player_abandons_characters() {
         ]),
         effect: effect(eachPlayerAbandonsCharacters(
           matching: character,
-          count: 1,
+          count: fixed(1),
         )),
         options: None,
       )),
@@ -1180,12 +1200,11 @@ fn test_each_player_abandons_characters() {
     assert_ron_snapshot!(result, @r###"
     [
       event(effect(eachPlayerAbandonsCharacters(
-        matching: cardWithCost(
-          target: character,
-          cost_operator: orMore,
-          cost: Energy(3),
-        ),
-        count: 2,
+        matching: and([
+          character,
+          withCost(Energy(3), orMore),
+        ]),
+        count: fixed(2),
       ))),
     ]
     "###);
@@ -1195,7 +1214,110 @@ fn test_each_player_abandons_characters() {
     [
       event(effect(eachPlayerAbandonsCharacters(
         matching: characterType(warrior),
-        count: 1,
+        count: fixed(1),
+      ))),
+    ]
+    "###);
+}
+
+#[test]
+fn test_where_x_is_number_of() {
+    let result =
+        parse("Gain X $points, where X is the number of characters you control. Draw a card.");
+    assert_ron_snapshot!(result, @r###"
+    [
+      event(list([
+        EffectWithOptions(
+          effect: gainPoints(
+            gains: x(matching(your(character))),
+          ),
+          optional: None,
+          condition: None,
+        ),
+        EffectWithOptions(
+          effect: drawCards(
+            count: fixed(1),
+          ),
+          optional: None,
+          condition: None,
+        ),
+      ])),
+    ]
+    "###);
+}
+
+#[test]
+fn test_or_predicate() {
+    let result = parse("Dissolve an enemy character or event.");
+    assert_ron_snapshot!(result, @r###"
+    [
+      event(effect(dissolveCharacter(
+        target: enemy(or([
+          character,
+          event,
+        ])),
+      ))),
+    ]
+    "###);
+}
+
+#[test]
+fn test_non_predicate() {
+    let result = parse("Banish a non-{cardtype: warrior} character.");
+    assert_ron_snapshot!(result, @r###"
+    [
+      event(effect(banishCharacter(
+        target: any(and([
+          character,
+          not(characterType(warrior)),
+        ])),
+      ))),
+    ]
+    "###);
+}
+
+#[test]
+fn test_with_filters_predicate() {
+    let result = parse("Dissolve an enemy event with cost $2 or less and spark 1 or more.");
+    assert_ron_snapshot!(result, @r###"
+    [
+      event(effect(dissolveCharacter(
+        target: enemy(and([
+          event,
+          withCost(Energy(2), orLess),
+          withSpark(Spark(1), orMore),
+        ])),
+      ))),
+    ]
+    "###);
+}
+
+#[test]
+fn test_modal_effect() {
+    let result = parse(
+        "Choose one: $br • $1: Draw 2 cards. $br • $3: Dissolve an enemy character with cost $2 or less.",
+    );
+    assert_ron_snapshot!(result, @r###"
+    [
+      event(modal(ModalEffect(
+        choose: 1,
+        modes: [
+          ModalEffectMode(
+            cost: Some(energy(Energy(1))),
+            effect: effect(drawCards(
+              count: fixed(2),
+            )),
+          ),
+          ModalEffectMode(
+            cost: Some(energy(Energy(3))),
+            effect: effect(dissolveCharacter(
+              target: enemy(and([
+                character,
+                withCost(Energy(2), orLess),
+              ])),
+            )),
+          ),
+        ],
       ))),
     ]
     "###);
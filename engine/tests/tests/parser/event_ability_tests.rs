@@ -70,7 +70,7 @@ fn test_abandon_and_gain_energy_for_spark() {
         ),
         EffectWithOptions(
           effect: drawCards(
-            count: 1,
+            count: fixed(1),
           ),
           optional: None,
           condition: None,
@@ -0,0 +1,47 @@
+use ability_data::keyword::{self, Keyword};
+use strum::IntoEnumIterator;
+
+use crate::parser::test_utils::parse;
+
+#[test]
+fn test_trigger_and_effect_keywords() {
+    let abilities = parse("$materialized: {kw: Discover} a character.");
+    assert_eq!(keyword::keywords(&abilities), vec![Keyword::Materialized, Keyword::Discover]);
+}
+
+#[test]
+fn test_keywords_without_duplicates() {
+    let abilities = parse("$materialized, $judgment: {kw: Kindle} 1.$br$judgment: {kw: Kindle} 2.");
+    assert_eq!(keyword::keywords(&abilities), vec![
+        Keyword::Materialized,
+        Keyword::Judgment,
+        Keyword::Kindle
+    ]);
+}
+
+#[test]
+fn test_static_ability_keywords() {
+    let abilities = parse("Draw 2 cards. Discard 2 cards.$br{kw: Reclaim}.");
+    assert_eq!(keyword::keywords(&abilities), vec![Keyword::Reclaim]);
+    let abilities = parse("Characters in your hand have '$fast'.");
+    assert_eq!(keyword::keywords(&abilities), vec![Keyword::Fast]);
+}
+
+#[test]
+fn test_disabling_enemy_materialized_abilities_has_no_keywords() {
+    let abilities = parse("Disable the \"$materialized\" abilities of enemy characters.");
+    assert!(keyword::keywords(&abilities).is_empty());
+}
+
+#[test]
+fn test_no_keywords() {
+    assert!(keyword::keywords(&parse("Draw a card.")).is_empty());
+}
+
+#[test]
+fn test_keyword_definitions() {
+    for keyword in Keyword::iter() {
+        assert!(keyword.ability_text().to_lowercase().contains(&keyword.name().to_lowercase()));
+        assert!(keyword.reminder_text().ends_with('.'));
+    }
+}
@@ -1,8 +1,13 @@
 mod ability_formating_tests;
 mod activated_ability_tests;
+mod batch_tests;
+mod diagnostic_tests;
 mod effect_formatting_tests;
 mod effect_tests;
 mod event_ability_tests;
+mod keyword_tests;
+mod serializer_tests;
+mod span_tests;
 mod static_ability_tests;
 mod test_utils;
 mod triggered_ability_tests;
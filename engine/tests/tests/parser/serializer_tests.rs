@@ -0,0 +1,156 @@
+use ability_data::predicate::{CardPredicate, Predicate};
+use core_data::character_type::CharacterType;
+use parser::predicate_serializer::serialize_card_predicate_plural;
+use parser::serializer_utils::{a_or_an, ordinal_number};
+
+use crate::parser::test_utils::serialize;
+
+#[test]
+fn test_serialize_event() {
+    assert_eq!(serialize("Draw a card, then discard a card."), "Draw a card, then discard a card.");
+}
+
+#[test]
+fn test_serialize_activated_ability() {
+    assert_eq!(
+        serialize(
+            "$multiActivated $2, Abandon another character with spark 1 or less: Draw 2 cards."
+        ),
+        "$multiActivated $2, abandon another character with spark 1 or less: Draw 2 cards."
+    );
+}
+
+#[test]
+fn test_serialize_triggered_ability() {
+    assert_eq!(
+        serialize("Once per turn, when you discard a card, gain $1 and then {kw: kindle} 2."),
+        "Once per turn, when you discard a card, gain $1, then {kw: kindle} 2."
+    );
+}
+
+#[test]
+fn test_serialize_keyword_trigger() {
+    assert_eq!(
+        serialize(
+            "$materialized, $judgment: If you control 2 other {cardtype: warriors}, gain $1."
+        ),
+        "$materialized, $judgment: If you control 2 other {cardtype: warriors}, gain $1."
+    );
+}
+
+#[test]
+fn test_serialize_static_ability() {
+    assert_eq!(
+        serialize("You may play this character for $0 by abandoning a character. If you do, abandon this character at end of turn."),
+        "You may play this character for $0 by abandoning a character. If you do, abandon this character at end of turn."
+    );
+}
+
+#[test]
+fn test_serialize_multiple_abilities() {
+    assert_eq!(
+        serialize("Draw 2 cards. Discard 2 cards.$br{kw: Reclaim}. {reminder: (you may play this dream from your void, then banish it.)}"),
+        "Draw 2 cards. Discard 2 cards. $br {kw: Reclaim}."
+    );
+}
+
+#[test]
+fn test_serialize_an_event() {
+    assert_eq!(
+        serialize("Whenever you play an event from your hand, copy it."),
+        "Whenever you play an event from your hand, copy it."
+    );
+}
+
+#[test]
+fn test_serialize_where_x() {
+    assert_eq!(
+        serialize("Spend all your remaining energy. Draw X cards then discard X cards, where X is the energy spent this way."),
+        "Spend all your remaining energy. Draw X cards, then discard X cards, where X is the energy spent this way."
+    );
+}
+
+#[test]
+fn test_serialize_compound_predicates() {
+    assert_eq!(
+        serialize("Dissolve up to two non-{cardtype: warrior} characters or events with cost $3 or less and spark 2."),
+        "Dissolve up to two non-{cardtype: warrior} characters or events with cost $3 or less and spark 2."
+    );
+}
+
+#[test]
+fn test_serialize_excluded_character_type() {
+    assert_eq!(
+        serialize("Banish an enemy character that is not a {cardtype: warrior}."),
+        "Banish an enemy non-{cardtype: warrior} character."
+    );
+}
+
+#[test]
+fn test_serialize_zone_and_controller_filters() {
+    let non_warrior = CardPredicate::And(vec![
+        CardPredicate::Character,
+        CardPredicate::Not(Box::new(CardPredicate::CharacterType(CharacterType::Warrior))),
+    ]);
+    let in_void = Predicate::YourVoid(non_warrior).card_predicate().unwrap();
+    assert_eq!(
+        serialize_card_predicate_plural(&in_void),
+        "non-{cardtype: warrior} characters in your void"
+    );
+    let enemy = Predicate::Enemy(CardPredicate::Event).card_predicate().unwrap();
+    assert_eq!(serialize_card_predicate_plural(&enemy), "events the enemy controls");
+}
+
+#[test]
+fn test_serialize_modal_effect() {
+    assert_eq!(
+        serialize(
+            "$materialized: Choose one: $br • Draw a card, then discard a card. $br • $2: Gain $3."
+        ),
+        "$materialized: Choose one: $br • Draw a card, then discard a card. $br • $2: Gain $3."
+    );
+}
+
+#[test]
+fn test_serialize_separate_sentences() {
+    assert_eq!(serialize("Draw a card. Discard a card."), "Draw a card. Discard a card.");
+}
+
+#[test]
+fn test_serialize_ordinal_trigger() {
+    assert_eq!(
+        serialize("Whenever you materialize your 21st character in a turn, draw a card."),
+        "Whenever you materialize your 21st character in a turn, draw a card."
+    );
+}
+
+#[test]
+fn test_ordinal_number() {
+    let ordinals = [2, 11, 12, 13, 21, 22, 23, 101, 111].map(ordinal_number);
+    assert_eq!(ordinals, [
+        "second", "11th", "12th", "13th", "21st", "22nd", "23rd", "101st", "111th"
+    ]);
+}
+
+#[test]
+fn test_a_or_an_directive() {
+    assert_eq!(a_or_an("{cardtype: ancient}"), "an {cardtype: ancient}");
+    assert_eq!(a_or_an("{cardtype: warrior}"), "a {cardtype: warrior}");
+    assert_eq!(a_or_an("event"), "an event");
+}
+
+#[test]
+fn test_serialize_x_amounts() {
+    for text in [
+        "{kw: Foresee} X, where X is the number of characters you control.",
+        "The enemy loses X $points, where X is the number of cards in your void.",
+        "Each player discards X cards, then {kw: kindle} X, where X is the number of characters \
+         you control.",
+        "Materialize X random characters from your deck, where X is the number of cards in your \
+         void.",
+        "The spark of each character you control becomes X, where X is the number of cards in \
+         your void.",
+    ] {
+        assert_eq!(serialize(text), text);
+    }
+}
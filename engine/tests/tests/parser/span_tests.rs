@@ -0,0 +1,97 @@
+use parser::ability_parser;
+use parser::ability_spans::{AstNode, SpannedAbility};
+
+/// Parses `text` with spans, returning the text of each ability followed by
+/// the kind and text of each node within it.
+fn spans(text: &str) -> Vec<String> {
+    let abilities = ability_parser::parse_with_spans(text).into_output().expect("Error parsing");
+    abilities.iter().flat_map(|ability| describe(text, ability)).collect()
+}
+
+fn describe(text: &str, ability: &SpannedAbility) -> Vec<String> {
+    let mut result = vec![format!("ability: {}", &text[ability.span.clone()])];
+    result.extend(ability.nodes.iter().map(|node| {
+        let kind = match node.node {
+            AstNode::Effect(_) => "effect",
+            AstNode::Cost(_) => "cost",
+            AstNode::Condition(_) => "condition",
+            AstNode::Predicate(_) => "predicate",
+            AstNode::CardPredicate(_) => "card predicate",
+        };
+        format!("{kind}: {}", &text[node.span.clone()])
+    }));
+    result
+}
+
+#[test]
+fn test_event_spans() {
+    assert_eq!(spans("Dissolve an enemy character with cost $2 or less."), vec![
+        "ability: Dissolve an enemy character with cost $2 or less.",
+        "effect: Dissolve an enemy character with cost $2 or less",
+        "predicate: an enemy character with cost $2 or less",
+        "card predicate: character with cost $2 or less",
+    ]);
+}
+
+#[test]
+fn test_activated_ability_spans() {
+    assert_eq!(
+        spans("$multiActivated $2, Abandon another character with spark 1 or less: Draw 2 cards."),
+        vec![
+            "ability: $multiActivated $2, Abandon another character with spark 1 or less: Draw 2 cards.",
+            "cost: $2",
+            "cost: Abandon another character with spark 1 or less",
+            "predicate: another character with spark 1 or less",
+            "card predicate: character with spark 1 or less",
+            "effect: Draw 2 cards",
+        ]
+    );
+}
+
+#[test]
+fn test_condition_spans() {
+    assert_eq!(spans("If you control 2 other {cardtype: warriors}, you may gain $1."), vec![
+        "ability: If you control 2 other {cardtype: warriors}, you may gain $1.",
+        "condition: you control 2 other {cardtype: warriors}",
+        "card predicate: {cardtype: warriors}",
+        "effect: gain $1",
+    ]);
+}
+
+#[test]
+fn test_multiple_ability_spans() {
+    assert_eq!(spans("Draw a card. {reminder: Card draw is good.}$br Draw a card."), vec![
+        "ability: Draw a card. {reminder: Card draw is good.}",
+        "effect: Draw a card",
+        "ability: Draw a card.",
+        "effect: Draw a card",
+    ]);
+}
+
+#[test]
+fn test_triggered_ability_spans() {
+    assert_eq!(
+        spans("Whenever you materialize a character, this character gains +1 spark."),
+        vec![
+            "ability: Whenever you materialize a character, this character gains +1 spark.",
+            "predicate: a character",
+            "card predicate: character",
+            "effect: this character gains +1 spark",
+            "predicate: this character",
+        ]
+    );
+}
+
+#[test]
+fn test_backtracking_discards_spans() {
+    // Alternatives which fail after parsing part of the text must not leave
+    // spans behind.
+    assert_eq!(spans("Banish up to two characters you control, then materialize them."), vec![
+        "ability: Banish up to two characters you control, then materialize them.",
+        "effect: Banish up to two characters you control",
+        "predicate: characters you control",
+        "card predicate: characters",
+        "effect: materialize them",
+        "predicate: them",
+    ]);
+}
@@ -25,11 +25,10 @@ fn test_once_per_turn_play_2_or_less_from_void() {
     assert_ron_snapshot!(result, @r###"
     [
       static(StaticAbility(oncePerTurnPlayFromVoid(
-        matching: cardWithCost(
-          target: character,
-          cost_operator: orLess,
-          cost: Energy(2),
-        ),
+        matching: and([
+          character,
+          withCost(Energy(2), orLess),
+        ]),
       ))),
     ]
     "###);
@@ -261,7 +260,7 @@ fn test_reclaim_with_draw_discard() {
       event(list([
         EffectWithOptions(
           effect: drawCards(
-            count: 2,
+            count: fixed(2),
           ),
           optional: None,
           condition: None,
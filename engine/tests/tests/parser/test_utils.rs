@@ -1,10 +1,26 @@
 use ability_data::ability::Ability;
 use ariadne::{Color, Label, Report, ReportKind, Source};
-use parser::ability_parser;
+use parser::{ability_parser, ability_serializer};
 
+/// Parses `text` into abilities, panicking on failure.
+///
+/// Also checks that serializing the result and parsing it again produces the
+/// same abilities, so every parser test doubles as a round-trip test.
 pub fn parse(text: &str) -> Vec<Ability> {
-    let input = text.to_lowercase();
-    let (result, errs) = ability_parser::parse(&input).into_output_errors();
+    let result = parse_text(text);
+    let serialized = ability_serializer::serialize(&result);
+    let round_trip = parse_text(&serialized);
+    assert_eq!(result, round_trip, "Round trip of {text:?} via {serialized:?} changed abilities");
+    result
+}
+
+/// Parses `text`, serializes the result, and returns the serialized text.
+pub fn serialize(text: &str) -> String {
+    ability_serializer::serialize(&parse(text))
+}
+
+fn parse_text(text: &str) -> Vec<Ability> {
+    let (result, errs) = ability_parser::parse(text).into_output_errors();
 
     if !errs.is_empty() {
         errs.into_iter().for_each(|e| {
@@ -15,7 +15,7 @@ fn test_materialize_warrior_gain_spark() {
         trigger: materialize(another(characterType(warrior))),
         effect: effect(gainsSpark(
           target: this,
-          gains: Spark(1),
+          gains: fixed(Spark(1)),
         )),
         options: None,
       )),
@@ -34,7 +34,7 @@ fn test_keyword_trigger_draw() {
           materialized,
         ]),
         effect: effect(drawCards(
-          count: 1,
+          count: fixed(1),
         )),
         options: None,
       )),
@@ -53,7 +53,7 @@ fn test_multiple_keyword_trigger() {
           dissolved,
         ]),
         effect: effect(drawCards(
-          count: 1,
+          count: fixed(1),
         )),
         options: None,
       )),
@@ -73,7 +73,7 @@ fn test_three_keyword_trigger() {
           dissolved,
         ]),
         effect: effect(drawCards(
-          count: 1,
+          count: fixed(1),
         )),
         options: None,
       )),
@@ -90,13 +90,12 @@ fn test_once_per_turn() {
         @r###"
     [
       triggered(TriggeredAbility(
-        trigger: materialize(your(cardWithCost(
-          target: character,
-          cost_operator: orLess,
-          cost: Energy(2),
-        ))),
+        trigger: materialize(your(and([
+          character,
+          withCost(Energy(2), orLess),
+        ]))),
         effect: effect(drawCards(
-          count: 1,
+          count: fixed(1),
         )),
         options: Some(TriggeredAbilityOptions(
           oncePerTurn: true,
@@ -123,7 +122,7 @@ fn test_multiple_keyword_trigger_conditional() {
         ]),
         effect: withOptions(EffectWithOptions(
           effect: gainEnergy(
-            gains: Energy(1),
+            gains: fixed(Energy(1)),
           ),
           optional: None,
           condition: Some(predicateCount(
@@ -146,7 +145,7 @@ fn test_once_per_turn_materialize() {
       triggered(TriggeredAbility(
         trigger: materialize(your(character)),
         effect: effect(gainEnergy(
-          gains: Energy(1),
+          gains: fixed(Energy(1)),
         )),
         options: Some(TriggeredAbilityOptions(
           oncePerTurn: true,
@@ -184,7 +183,7 @@ fn test_gain_spark_on_materialize() {
         trigger: materialize(your(character)),
         effect: effect(gainsSpark(
           target: this,
-          gains: Spark(1),
+          gains: fixed(Spark(1)),
         )),
         options: None,
       )),
@@ -220,17 +219,18 @@ fn test_once_per_turn_multiple_effects() {
         effect: list([
           EffectWithOptions(
             effect: gainEnergy(
-              gains: Energy(1),
+              gains: fixed(Energy(1)),
             ),
             optional: None,
             condition: None,
           ),
           EffectWithOptions(
             effect: kindle(
-              amount: Spark(2),
+              amount: fixed(Spark(2)),
             ),
             optional: None,
             condition: None,
+            then: true,
           ),
         ]),
         options: Some(TriggeredAbilityOptions(
@@ -271,7 +271,7 @@ fn test_end_of_turn() {
       triggered(TriggeredAbility(
         trigger: endOfYourTurn,
         effect: effect(gainEnergy(
-          gains: Energy(2),
+          gains: fixed(Energy(2)),
         )),
         options: None,
       )),
@@ -339,7 +339,7 @@ fn test_banished_character_gains_spark() {
         trigger: banished(your(character)),
         effect: effect(gainsSpark(
           target: this,
-          gains: Spark(1),
+          gains: fixed(Spark(1)),
         )),
         options: None,
       )),
@@ -355,7 +355,7 @@ fn test_dissolved_character_gains_spark() {
       triggered(TriggeredAbility(
         trigger: dissolved(your(character)),
         effect: effect(drawCards(
-          count: 1,
+          count: fixed(1),
         )),
         options: None,
       )),
@@ -372,7 +372,7 @@ fn test_banish_until_next_main() {
         trigger: TriggeredAbility(
           trigger: banished(your(character)),
           effect: effect(drawCards(
-            count: 1,
+            count: fixed(1),
           )),
           options: Some(TriggeredAbilityOptions(
             oncePerTurn: false,
@@ -393,7 +393,7 @@ fn test_abandon_character_gains_spark() {
         trigger: abandon(your(character)),
         effect: effect(gainsSpark(
           target: this,
-          gains: Spark(1),
+          gains: fixed(Spark(1)),
         )),
         options: None,
       )),
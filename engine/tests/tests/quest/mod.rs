@@ -0,0 +1 @@
+mod quest_tests;
@@ -0,0 +1,147 @@
+use battle_data::battle_card::Zone;
+use card_data::card_definitions;
+use card_data::card_name::CardName;
+use core_data::identifiers::{BattleId, QuestId};
+use core_data::numerics::Energy;
+use core_data::types::Seat;
+use deck_data::deck_rules::{DeckRuleViolation, DeckRules};
+use deck_data::decklist;
+use engine::quest_mutations::QuestError;
+use engine::{battle_creation, queries, quest_creation, quest_mutations};
+use parser::ability_parser;
+use quest_data::quest_data::QuestData;
+use quest_data::quest_map::{SiteId, SiteKind};
+use uuid::Uuid;
+
+const STARTING_DECK: &str = "
+4 Titan of Forgotten Echoes
+4 Beacon of Tomorrow
+4 Scrap Reclaimer
+4 Evacuation Enforcer
+4 Moonlit Voyage
+";
+
+fn new_quest() -> QuestData {
+    quest_creation::create(QuestId(Uuid::from_u128(1)), 1, decklist::parse(STARTING_DECK).unwrap())
+}
+
+/// Rules which allow a fifth copy of the cards in [STARTING_DECK].
+fn relaxed_rules() -> DeckRules {
+    DeckRules { maximum_copies: 5, ..DeckRules::default() }
+}
+
+#[test]
+fn test_all_ability_text_parses() {
+    for definition in card_definitions::all() {
+        if let Some(text) = definition.ability_text {
+            let errors = ability_parser::parse(text).into_errors();
+            assert!(errors.is_empty(), "Error parsing ability text '{text}': {errors:?}");
+        }
+    }
+}
+
+#[test]
+fn test_draft_pick_adds_card_to_deck() {
+    let mut quest = new_quest();
+    let start = quest.map.start;
+    quest_mutations::visit_site(&mut quest, start).unwrap();
+    let SiteKind::Draft { choices } = &quest.site().unwrap().kind else {
+        panic!("Expected draft site");
+    };
+    let pick = choices[0];
+    quest_mutations::draft_pick(&mut quest, pick, &relaxed_rules()).unwrap();
+    assert_eq!(quest.deck.count(pick), 5);
+    assert_eq!(quest.deck.len(), 21);
+    assert_eq!(
+        quest_mutations::draft_pick(&mut quest, pick, &relaxed_rules()),
+        Err(QuestError::InvalidSiteAction)
+    );
+}
+
+#[test]
+fn test_draft_pick_checks_deck_rules() {
+    let mut quest = new_quest();
+    let start = quest.map.start;
+    quest_mutations::visit_site(&mut quest, start).unwrap();
+    let SiteKind::Draft { choices } = &quest.site().unwrap().kind else {
+        panic!("Expected draft site");
+    };
+    let pick = choices[0];
+    assert_eq!(quest.deck.count(pick), 4);
+    assert_eq!(
+        quest_mutations::draft_pick(&mut quest, pick, &DeckRules::default()),
+        Err(QuestError::DeckRule(DeckRuleViolation::TooManyCopies {
+            card: pick,
+            count: 5,
+            maximum: 4
+        }))
+    );
+    assert_eq!(quest.deck.count(pick), 4);
+    assert!(!quest.site().unwrap().completed);
+}
+
+#[test]
+fn test_cannot_skip_sites() {
+    let mut quest = new_quest();
+    let start = quest.map.start;
+    quest_mutations::visit_site(&mut quest, start).unwrap();
+    let next = quest.map.site(start).unwrap().connections[0];
+    assert_eq!(
+        quest_mutations::visit_site(&mut quest, next),
+        Err(QuestError::SiteNotAvailable(next))
+    );
+    assert_eq!(
+        quest_mutations::visit_site(&mut quest, SiteId(100)),
+        Err(QuestError::SiteNotAvailable(SiteId(100)))
+    );
+}
+
+#[test]
+fn test_win_battle_awards_essence() {
+    let mut quest = new_quest();
+    let start = quest.map.start;
+    quest_mutations::visit_site(&mut quest, start).unwrap();
+    let SiteKind::Draft { choices } = &quest.site().unwrap().kind else {
+        panic!("Expected draft site");
+    };
+    let pick = choices[0];
+    quest_mutations::draft_pick(&mut quest, pick, &relaxed_rules()).unwrap();
+    let battle_site = quest.available_sites()[0];
+    quest_mutations::visit_site(&mut quest, battle_site).unwrap();
+    let battle = quest_mutations::start_battle(&mut quest, BattleId(Uuid::from_u128(2))).unwrap();
+    assert_eq!(battle.cards_in_zone(Seat::Host, Zone::Hand).len(), 5);
+    let essence = quest.essence;
+    quest_mutations::win_battle(&mut quest).unwrap();
+    assert_eq!(quest.essence, essence + quest_mutations::BATTLE_REWARD);
+}
+
+#[test]
+fn test_dreamsign_reduces_cost() {
+    let mut quest = new_quest();
+    quest.dreamsigns.push(CardName::ShimmeringLantern);
+    let enemy_deck = decklist::parse(STARTING_DECK).unwrap();
+    let battle = battle_creation::for_quest(
+        BattleId(Uuid::from_u128(3)),
+        3,
+        &quest,
+        CardName::Korrak,
+        &enemy_deck,
+    )
+    .unwrap();
+    let status = battle.cards_in_zone(Seat::Host, Zone::Status);
+    assert_eq!(status.len(), 1);
+    assert_eq!(status[0].name, CardName::ShimmeringLantern);
+
+    let titan = battle
+        .cards
+        .values()
+        .find(|c| c.owner == Seat::Host && c.name == CardName::TitanOfForgottenEchoes)
+        .unwrap();
+    assert_eq!(queries::cost(&battle, titan), Some(Energy(5)));
+    let enemy_titan = battle
+        .cards
+        .values()
+        .find(|c| c.owner == Seat::Guest && c.name == CardName::TitanOfForgottenEchoes)
+        .unwrap();
+    assert_eq!(queries::cost(&battle, enemy_titan), Some(Energy(6)));
+}
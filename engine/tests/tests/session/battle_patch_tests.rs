@@ -0,0 +1,211 @@
+use std::sync::Arc;
+
+use action_data::debug_action::DebugAction;
+use action_data::user_action::UserAction;
+use core_data::identifiers::UserId;
+use core_data::types::Seat;
+use display_data::battle_view::BattleView;
+use display_data::command::{Command, CommandSequence, PatchBattleCommand};
+use display_data::request_data::{ConnectRequest, Metadata, PerformActionRequest, PollRequest};
+use display_data::wire_format::WireFormat;
+use engine::battle_patches::ViewTracker;
+use engine::session_registry::SessionRegistry;
+use engine::{battle_patches, battle_rendering, handle_request};
+use uuid::Uuid;
+
+use crate::database::save_tests::new_database;
+
+fn connect(registry: &SessionRegistry, battle_patches: bool) -> (Metadata, CommandSequence) {
+    let metadata = Metadata { user_id: UserId(Uuid::new_v4()), battle_id: None, version: None };
+    reconnect(registry, metadata, battle_patches)
+}
+
+fn reconnect(
+    registry: &SessionRegistry,
+    metadata: Metadata,
+    battle_patches: bool,
+) -> (Metadata, CommandSequence) {
+    let request =
+        ConnectRequest { metadata, wire_format: WireFormat::Json, battle_patches, opponent: None };
+    let response = handle_request::connect(registry, &request).unwrap();
+    (response.metadata, response.commands)
+}
+
+fn perform(
+    registry: &Arc<SessionRegistry>,
+    metadata: Metadata,
+    action: impl Into<UserAction>,
+) -> CommandSequence {
+    let request = PerformActionRequest { metadata, action: action.into() };
+    handle_request::perform_action(registry, &request).unwrap().commands
+}
+
+fn commands(sequence: CommandSequence) -> Vec<Command> {
+    sequence.groups.into_iter().flat_map(|group| group.commands).collect()
+}
+
+fn snapshot(sequence: CommandSequence) -> (u64, BattleView) {
+    commands(sequence)
+        .into_iter()
+        .find_map(|command| match command {
+            Command::UpdateBattle(update) => Some((update.view_revision, update.battle)),
+            _ => None,
+        })
+        .expect("No battle update")
+}
+
+fn apply(view: &mut BattleView, patch: PatchBattleCommand) {
+    if let Some(user) = patch.user {
+        view.user = user;
+    }
+    if let Some(enemy) = patch.enemy {
+        view.enemy = enemy;
+    }
+    if let Some(status_description) = patch.status_description {
+        view.status_description = status_description;
+    }
+    if let Some(interface) = patch.interface {
+        view.interface = interface;
+    }
+    view.cards.retain(|card| !patch.removed_cards.contains(&card.id));
+    for card in patch.cards {
+        match view.cards.iter_mut().find(|existing| existing.id == card.id) {
+            Some(existing) => *existing = card,
+            None => view.cards.push(card),
+        }
+    }
+}
+
+fn sorted(mut view: BattleView) -> BattleView {
+    view.cards.sort_by_key(|card| card.id);
+    view
+}
+
+#[test]
+fn test_connect_sends_snapshot() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let (_, sequence) = connect(&registry, true);
+    assert!(commands(sequence).iter().all(|c| !matches!(c, Command::PatchBattle(_))));
+}
+
+#[test]
+fn test_patches_disabled_by_default() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let (metadata, sequence) = connect(&registry, false);
+    let (revision, _) = snapshot(sequence);
+    let (next_revision, _) = snapshot(perform(&registry, metadata, DebugAction::DrawCard));
+    assert!(next_revision > revision);
+}
+
+#[test]
+fn test_patch_applies_to_previous_snapshot() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let (metadata, sequence) = connect(&registry, true);
+    let (mut revision, mut view) = snapshot(sequence);
+    let total_cards = view.cards.len();
+
+    let mut patch_count = 0;
+    for command in commands(perform(&registry, metadata, DebugAction::DrawCard)) {
+        if let Command::PatchBattle(patch) = command {
+            assert_eq!(patch.base_view_revision, revision);
+            assert!(patch.cards.len() < total_cards);
+            revision = patch.view_revision;
+            apply(&mut view, patch);
+            patch_count += 1;
+        }
+    }
+    assert!(patch_count > 0);
+
+    let (_, resync) = reconnect(&registry, metadata, true);
+    let (resync_revision, expected) = snapshot(resync);
+    assert!(resync_revision > revision);
+    assert_eq!(sorted(view), sorted(expected));
+}
+
+#[test]
+fn test_resync_sends_snapshot() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let (metadata, _) = connect(&registry, true);
+    let metadata = Metadata { version: None, ..metadata };
+    perform(&registry, metadata, DebugAction::DrawCard);
+
+    let request = PollRequest { metadata, resync: true };
+    let response = handle_request::poll(&registry, &request).unwrap();
+    let (revision, view) = snapshot(response.commands);
+    let battle = registry.find_battle(metadata.user_id, metadata.battle_id.unwrap()).unwrap();
+    assert_eq!(response.metadata.version, battle.as_ref().map(|battle| battle.version));
+    assert_eq!(view, battle_rendering::battle_view(&battle.unwrap(), Seat::Host));
+
+    // Later updates are patches relative to the snapshot
+    let patch = commands(perform(&registry, metadata, DebugAction::DrawCard))
+        .into_iter()
+        .find_map(|command| match command {
+            Command::PatchBattle(patch) => Some(patch),
+            _ => None,
+        })
+        .expect("No battle patch");
+    assert_eq!(patch.base_view_revision, revision);
+}
+
+#[test]
+fn test_stream_resync_sends_snapshot() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let (metadata, _) = connect(&registry, true);
+    let mut view = ViewTracker::default();
+    let request = ConnectRequest {
+        metadata,
+        wire_format: WireFormat::Json,
+        battle_patches: true,
+        opponent: None,
+    };
+    let (connected, _) =
+        snapshot(handle_request::connect_stream(&registry, &request, &mut view).unwrap().commands);
+
+    let response = handle_request::resync_stream(&registry, &metadata, &mut view).unwrap();
+    assert_eq!(response.metadata.version, metadata.version);
+    let (revision, _) = snapshot(response.commands);
+    assert!(revision > connected);
+}
+
+#[test]
+fn test_diff_identical_views_is_empty() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let (_, view) = snapshot(connect(&registry, true).1);
+    let patch = battle_patches::diff(&view, &view);
+    assert!(patch.cards.is_empty());
+    assert!(patch.removed_cards.is_empty());
+    assert!(patch.user.is_none() && patch.enemy.is_none() && patch.interface.is_none());
+}
+
+#[test]
+fn test_stream_connections_track_separate_views() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let (metadata, _) = connect(&registry, true);
+    let mut patched = ViewTracker::default();
+    let mut unpatched = ViewTracker::default();
+    for (view, battle_patches) in [(&mut patched, true), (&mut unpatched, false)] {
+        let request = ConnectRequest {
+            metadata,
+            wire_format: WireFormat::Json,
+            battle_patches,
+            opponent: None,
+        };
+        handle_request::connect_stream(&registry, &request, view).unwrap();
+    }
+
+    let is_patch = |command: &Command| matches!(command, Command::PatchBattle(_));
+    let request = PerformActionRequest { metadata, action: DebugAction::DrawCard.into() };
+    let response = handle_request::perform_action_stream(&registry, &request, &mut patched);
+    assert!(commands(response.unwrap().commands).iter().any(is_patch));
+    let request =
+        PerformActionRequest { metadata: Metadata { version: None, ..metadata }, ..request };
+    let response = handle_request::perform_action_stream(&registry, &request, &mut unpatched);
+    assert!(!commands(response.unwrap().commands).iter().any(is_patch));
+}
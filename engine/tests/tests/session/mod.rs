@@ -0,0 +1,5 @@
+mod battle_patch_tests;
+mod pvp_tests;
+mod session_registry_tests;
+mod spectator_tests;
+mod wire_format_tests;
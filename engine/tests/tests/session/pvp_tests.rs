@@ -0,0 +1,200 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use action_data::battle_action::{BattleAction, CardBrowserType};
+use action_data::user_action::UserAction;
+use battle_data::battle_card::Zone;
+use core_data::identifiers::UserId;
+use core_data::types::{PlayerName, Seat};
+use display_data::battle_view::BattleView;
+use display_data::card_view::CardView;
+use display_data::command::{Command, CommandSequence, GameMessageType};
+use display_data::object_position::Position;
+use display_data::request_data::{
+    ConnectRequest, Metadata, PerformActionRequest, PerformActionResponse, PollRequest,
+};
+use display_data::wire_format::WireFormat;
+use engine::request_error::RequestError;
+use engine::session_registry::SessionRegistry;
+use engine::{battle_rendering, handle_request};
+use uuid::Uuid;
+
+use crate::database::save_tests::new_database;
+
+fn new_user() -> UserId {
+    UserId(Uuid::new_v4())
+}
+
+fn connect(registry: &SessionRegistry, user_id: UserId, opponent: UserId) -> Metadata {
+    let request = ConnectRequest {
+        metadata: Metadata { user_id, battle_id: None, version: None },
+        wire_format: WireFormat::Json,
+        battle_patches: false,
+        opponent: Some(opponent),
+    };
+    handle_request::connect(registry, &request).unwrap().metadata
+}
+
+fn perform(
+    registry: &Arc<SessionRegistry>,
+    metadata: Metadata,
+    action: impl Into<UserAction>,
+) -> Result<PerformActionResponse, RequestError> {
+    let request = PerformActionRequest {
+        metadata: Metadata { version: None, ..metadata },
+        action: action.into(),
+    };
+    handle_request::perform_action(registry, &request)
+}
+
+fn poll(registry: &SessionRegistry, metadata: Metadata) -> CommandSequence {
+    handle_request::poll(registry, &PollRequest { metadata, resync: false }).unwrap().commands
+}
+
+fn commands(sequence: &CommandSequence) -> Vec<&Command> {
+    sequence.groups.iter().flat_map(|group| group.commands.iter()).collect()
+}
+
+fn battle_view(sequence: &CommandSequence) -> &BattleView {
+    commands(sequence)
+        .into_iter()
+        .find_map(|command| match command {
+            Command::UpdateBattle(update) => Some(&update.battle),
+            _ => None,
+        })
+        .unwrap()
+}
+
+/// Starts a battle between two new users, returning the metadata of the user
+/// who created it followed by that of the user who joined it.
+fn start_battle(registry: &SessionRegistry) -> (Metadata, Metadata) {
+    let (host_id, guest_id) = (new_user(), new_user());
+    let host = connect(registry, host_id, guest_id);
+    let guest = connect(registry, guest_id, host_id);
+    (host, guest)
+}
+
+#[test]
+fn test_users_join_same_battle() {
+    let (_directory, database) = new_database();
+    let registry = SessionRegistry::new(Box::new(database));
+    let (host, guest) = start_battle(&registry);
+    assert_eq!(host.battle_id, guest.battle_id);
+
+    let (host_id, seat) = registry.find_seat(guest.user_id, guest.battle_id.unwrap()).unwrap();
+    assert_eq!(host_id, host.user_id);
+    assert_eq!(seat, Seat::Guest);
+}
+
+#[test]
+fn test_each_user_sees_themselves_as_user() {
+    let (_directory, database) = new_database();
+    let registry = SessionRegistry::new(Box::new(database));
+    let (host, guest) = start_battle(&registry);
+    let battle = registry.find_battle(host.user_id, host.battle_id.unwrap()).unwrap().unwrap();
+
+    for (metadata, seat) in [(host, Seat::Host), (guest, Seat::Guest)] {
+        let request = ConnectRequest {
+            metadata,
+            wire_format: WireFormat::Json,
+            battle_patches: false,
+            opponent: None,
+        };
+        let response = handle_request::connect(&registry, &request).unwrap();
+        let view = battle_view(&response.commands);
+        let own_hand = view
+            .cards
+            .iter()
+            .filter(|card| card.position.position == Position::InHand(PlayerName::User))
+            .collect::<Vec<_>>();
+        assert!(!own_hand.is_empty());
+        for card in own_hand {
+            assert!(card.revealed.is_some());
+            assert_eq!(battle.card(card.id).unwrap().owner, seat);
+        }
+    }
+}
+
+#[test]
+fn test_users_take_turns() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let (host, guest) = start_battle(&registry);
+
+    assert!(matches!(
+        perform(&registry, guest, BattleAction::EndTurn),
+        Err(RequestError::IllegalAction(_))
+    ));
+    perform(&registry, host, BattleAction::EndTurn).unwrap();
+
+    let update = poll(&registry, guest);
+    assert!(matches!(commands(&update)[0], Command::DisplayGameMessage(GameMessageType::YourTurn)));
+
+    // The enemy seat is not played by the AI.
+    thread::sleep(Duration::from_millis(100));
+    let battle = registry.find_battle(guest.user_id, guest.battle_id.unwrap()).unwrap().unwrap();
+    assert_eq!(battle.turn.active_player, Seat::Guest);
+    assert!(poll(&registry, host).groups.is_empty());
+
+    assert!(matches!(
+        perform(&registry, host, BattleAction::EndTurn),
+        Err(RequestError::IllegalAction(_))
+    ));
+    perform(&registry, guest, BattleAction::EndTurn).unwrap();
+    let update = poll(&registry, host);
+    assert!(matches!(commands(&update)[0], Command::DisplayGameMessage(GameMessageType::YourTurn)));
+    let battle = registry.find_battle(host.user_id, host.battle_id.unwrap()).unwrap().unwrap();
+    assert_eq!(battle.turn.active_player, Seat::Host);
+    assert_eq!(battle.turn.turn_number, 3);
+}
+
+#[test]
+fn test_each_user_has_own_card_browser() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let (host, guest) = start_battle(&registry);
+    let browse = BattleAction::BrowseCards(CardBrowserType::UserDeck);
+    let response = perform(&registry, guest, browse).unwrap();
+    assert_eq!(response.metadata.version, guest.version);
+
+    // The guest browses their own deck, without affecting the host.
+    let battle = registry.find_battle(host.user_id, host.battle_id.unwrap()).unwrap().unwrap();
+    let browsed =
+        battle_view(&response.commands).cards.iter().filter(|card| is_browsed(card)).count();
+    assert_eq!(browsed, battle.cards_in_zone(Seat::Guest, Zone::Deck).len());
+    assert!(poll(&registry, host).groups.is_empty());
+    let view = battle_rendering::battle_view(&battle, Seat::Host);
+    assert!(!view.cards.iter().any(is_browsed));
+}
+
+#[test]
+fn test_end_turn_button_shown_to_active_player() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let (host, guest) = start_battle(&registry);
+    let battle = registry.find_battle(host.user_id, host.battle_id.unwrap()).unwrap().unwrap();
+    let button =
+        |seat| battle_rendering::battle_view(&battle, seat).interface.primary_action_button;
+    assert_eq!(button(Seat::Host), Some("End Turn".to_string()));
+    assert_eq!(button(Seat::Guest), None);
+
+    perform(&registry, host, BattleAction::EndTurn).unwrap();
+    let update = poll(&registry, guest);
+    assert_eq!(battle_view(&update).interface.primary_action_button, Some("End Turn".to_string()));
+}
+
+#[test]
+fn test_seats_convert_to_player_names() {
+    for viewer in [Seat::Host, Seat::Guest] {
+        assert_eq!(viewer.player_name(viewer), PlayerName::User);
+        assert_eq!(viewer.opponent().player_name(viewer), PlayerName::Enemy);
+        for player in [PlayerName::User, PlayerName::Enemy] {
+            assert_eq!(Seat::of_player(player, viewer).player_name(viewer), player);
+        }
+    }
+}
+
+fn is_browsed(card: &CardView) -> bool {
+    card.position.position == Position::Browser
+}
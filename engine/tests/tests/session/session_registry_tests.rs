@@ -0,0 +1,400 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use action_data::battle_action::{BattleAction, CardBrowserType};
+use action_data::debug_action::DebugAction;
+use action_data::user_action::UserAction;
+use core_data::identifiers::{BattleId, CardId, UserId};
+use core_data::types::Seat;
+use database::database::Database;
+use database::file_database::FileDatabase;
+use display_data::command::{Command, CommandSequence, GameMessageType};
+use display_data::request_data::{
+    ConnectRequest, ConnectResponse, ErrorResponse, ErrorType, Metadata, PerformActionRequest,
+    PerformActionResponse, PollRequest, PollResponse,
+};
+use display_data::wire_format::WireFormat;
+use engine::handle_request;
+use engine::request_error::RequestError;
+use engine::session_registry::SessionRegistry;
+use uuid::Uuid;
+
+use crate::database::save_tests::new_database;
+
+fn new_registry(database: &FileDatabase) -> Arc<SessionRegistry> {
+    Arc::new(SessionRegistry::new(Box::new(database.clone())))
+}
+
+fn connect(registry: &SessionRegistry, metadata: Metadata) -> ConnectResponse {
+    connect_with_opponent(registry, metadata, None)
+}
+
+fn connect_with_opponent(
+    registry: &SessionRegistry,
+    metadata: Metadata,
+    opponent: Option<UserId>,
+) -> ConnectResponse {
+    handle_request::connect(registry, &ConnectRequest {
+        metadata,
+        wire_format: WireFormat::Json,
+        battle_patches: false,
+        opponent,
+    })
+    .unwrap()
+}
+
+fn perform(
+    registry: &Arc<SessionRegistry>,
+    metadata: Metadata,
+    action: impl Into<UserAction>,
+) -> Result<PerformActionResponse, RequestError> {
+    handle_request::perform_action(registry, &PerformActionRequest {
+        metadata,
+        action: action.into(),
+    })
+}
+
+fn poll(registry: &SessionRegistry, metadata: Metadata) -> Result<PollResponse, RequestError> {
+    handle_request::poll(registry, &PollRequest { metadata, resync: false })
+}
+
+fn new_user() -> Metadata {
+    Metadata { user_id: UserId(Uuid::new_v4()), battle_id: None, version: None }
+}
+
+#[test]
+fn test_connect_saves_battle() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = new_user();
+    let response = connect(&registry, metadata);
+    let save = database.fetch_save(metadata.user_id).unwrap().unwrap();
+    assert_eq!(save.battle.map(|b| b.id), response.metadata.battle_id);
+}
+
+#[test]
+fn test_connect_resumes_battle_after_restart() {
+    let (_directory, database) = new_database();
+    let first = connect(&new_registry(&database), new_user());
+    perform(
+        &new_registry(&database),
+        first.metadata,
+        BattleAction::BrowseCards(CardBrowserType::UserVoid),
+    )
+    .unwrap();
+
+    let resumed = connect(&new_registry(&database), first.metadata);
+    assert_eq!(resumed.metadata.battle_id, first.metadata.battle_id);
+    let save = database.fetch_save(first.metadata.user_id).unwrap().unwrap();
+    assert_eq!(save.battle.unwrap().user.card_browser, Some(CardBrowserType::UserVoid));
+}
+
+#[test]
+fn test_connect_without_battle_id_resumes_saved_battle() {
+    let (_directory, database) = new_database();
+    let metadata = new_user();
+    let first = connect(&new_registry(&database), metadata);
+    let second = connect(&new_registry(&database), metadata);
+    assert_eq!(first.metadata.battle_id, second.metadata.battle_id);
+    let save = database.fetch_save(metadata.user_id).unwrap().unwrap();
+    assert_eq!(save.battle.map(|b| b.id), first.metadata.battle_id);
+}
+
+#[test]
+fn test_connect_with_new_opponent_starts_new_battle() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = new_user();
+    let first = connect(&registry, metadata);
+    let opponent = Some(UserId(Uuid::new_v4()));
+    let second = connect_with_opponent(&registry, metadata, opponent);
+    assert_ne!(first.metadata.battle_id, second.metadata.battle_id);
+    let resumed = connect(&registry, metadata);
+    assert_eq!(resumed.metadata.battle_id, second.metadata.battle_id);
+}
+
+#[test]
+fn test_connect_to_unknown_battle() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    let battle_id = BattleId(Uuid::new_v4());
+    let result = handle_request::connect(&registry, &ConnectRequest {
+        metadata: Metadata { battle_id: Some(battle_id), ..metadata },
+        wire_format: WireFormat::Json,
+        battle_patches: false,
+        opponent: None,
+    });
+    assert!(matches!(result, Err(RequestError::UnknownBattle(id)) if id == battle_id));
+    let save = database.fetch_save(metadata.user_id).unwrap().unwrap();
+    assert_eq!(save.battle.map(|b| b.id), metadata.battle_id);
+}
+
+#[test]
+fn test_unknown_user_is_rejected_without_save() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let battle_id = BattleId(Uuid::new_v4());
+    let metadata = Metadata { battle_id: Some(battle_id), ..new_user() };
+    let result = handle_request::connect(&registry, &ConnectRequest {
+        metadata,
+        wire_format: WireFormat::Json,
+        battle_patches: false,
+        opponent: None,
+    });
+    assert!(matches!(result, Err(RequestError::UnknownBattle(id)) if id == battle_id));
+    assert!(matches!(
+        perform(&registry, metadata, BattleAction::CloseCardBrowser),
+        Err(RequestError::UnknownBattle(_))
+    ));
+    assert!(matches!(poll(&registry, metadata), Err(RequestError::UnknownBattle(_))));
+    assert!(registry.current_battle(metadata.user_id, None).unwrap().is_none());
+    assert!(database.fetch_save(metadata.user_id).unwrap().is_none());
+}
+
+#[test]
+fn test_panic_during_request_does_not_poison_registry() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    let other = connect(&registry, new_user()).metadata;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        registry.with_battle(metadata.user_id, metadata.battle_id, |battle| {
+            battle.version += 1;
+            panic!("Failed during request");
+        })
+    }));
+    assert!(result.is_err());
+
+    registry.evict_idle_sessions();
+    let battle = registry.find_battle(metadata.user_id, metadata.battle_id.unwrap()).unwrap();
+    assert_eq!(battle.map(|battle| battle.version), metadata.version);
+    assert!(perform(&registry, metadata, BattleAction::CloseCardBrowser).is_ok());
+    assert!(perform(&registry, other, BattleAction::CloseCardBrowser).is_ok());
+}
+
+#[test]
+fn test_users_have_separate_battles() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let user_one = connect(&registry, new_user()).metadata;
+    let user_two = connect(&registry, new_user()).metadata;
+    assert_ne!(user_one.battle_id, user_two.battle_id);
+
+    let result = perform(
+        &registry,
+        Metadata { battle_id: user_one.battle_id, ..user_two },
+        BattleAction::CloseCardBrowser,
+    );
+    assert!(matches!(result, Err(RequestError::UnknownBattle(_))));
+    assert!(perform(&registry, user_two, BattleAction::CloseCardBrowser).is_ok());
+}
+
+#[test]
+fn test_perform_action_requires_battle_id() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    let result = perform(
+        &registry,
+        Metadata { battle_id: None, ..metadata },
+        BattleAction::CloseCardBrowser,
+    );
+    assert!(matches!(result, Err(RequestError::MissingBattleId)));
+}
+
+#[test]
+fn test_replaced_battle_is_unknown() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = new_user();
+    let old = connect(&registry, metadata).metadata;
+    connect_with_opponent(&registry, metadata, Some(UserId(Uuid::new_v4())));
+    let result = perform(&registry, old, BattleAction::CloseCardBrowser);
+    assert!(matches!(result, Err(RequestError::UnknownBattle(id)) if Some(id) == old.battle_id));
+
+    let unknown = Metadata { battle_id: Some(BattleId(Uuid::new_v4())), ..old };
+    assert!(matches!(
+        perform(&registry, unknown, BattleAction::CloseCardBrowser),
+        Err(RequestError::UnknownBattle(_))
+    ));
+}
+
+#[test]
+fn test_poll_without_updates_is_empty() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    assert!(poll(&registry, metadata).unwrap().commands.groups.is_empty());
+}
+
+#[test]
+fn test_poll_unknown_battle() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    let unknown = Metadata { battle_id: Some(BattleId(Uuid::new_v4())), ..metadata };
+    assert!(matches!(poll(&registry, unknown), Err(RequestError::UnknownBattle(_))));
+    assert!(matches!(
+        poll(&registry, Metadata { battle_id: None, ..metadata }),
+        Err(RequestError::MissingBattleId)
+    ));
+}
+
+#[test]
+fn test_poll_returns_enemy_turn() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    perform(&registry, metadata, BattleAction::EndTurn).unwrap();
+
+    let mut commands = vec![];
+    for _ in 0..100 {
+        commands.extend(
+            poll(&registry, metadata).unwrap().commands.groups.into_iter().flat_map(|g| g.commands),
+        );
+        if commands
+            .iter()
+            .any(|c| matches!(c, Command::DisplayGameMessage(GameMessageType::YourTurn)))
+        {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert!(commands
+        .iter()
+        .any(|c| matches!(c, Command::DisplayGameMessage(GameMessageType::YourTurn))));
+    let save = database.fetch_save(metadata.user_id).unwrap().unwrap();
+    let battle = save.battle.unwrap();
+    assert_eq!(battle.turn.active_player, Seat::Host);
+    assert_eq!(battle.turn.turn_number, 3);
+}
+
+#[test]
+fn test_poll_returns_scheduled_update() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    let mut pending = true;
+    handle_request::schedule_update(
+        &registry,
+        metadata.user_id,
+        metadata.battle_id.unwrap(),
+        Duration::from_millis(20),
+        move |battle| {
+            battle.user.card_browser = Some(CardBrowserType::UserVoid);
+            Ok(std::mem::take(&mut pending).then(|| {
+                CommandSequence::sequential(vec![Command::DisplayGameMessage(
+                    GameMessageType::Victory,
+                )])
+            }))
+        },
+    );
+
+    let mut commands = vec![];
+    for _ in 0..100 {
+        commands.extend(
+            poll(&registry, metadata).unwrap().commands.groups.into_iter().flat_map(|g| g.commands),
+        );
+        if !commands.is_empty() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert!(matches!(commands[..], [Command::DisplayGameMessage(GameMessageType::Victory)]));
+    let save = database.fetch_save(metadata.user_id).unwrap().unwrap();
+    assert_eq!(save.battle.unwrap().user.card_browser, Some(CardBrowserType::UserVoid));
+}
+
+#[test]
+fn test_subscribers_receive_updates_instead_of_poll() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    let mut first = registry.subscribe(metadata.user_id).unwrap();
+    let mut second = registry.subscribe(metadata.user_id).unwrap();
+    let version = perform(&registry, metadata, BattleAction::EndTurn).unwrap().metadata.version;
+
+    let update = first.blocking_recv().unwrap();
+    assert_eq!(Some(update.battle_id), metadata.battle_id);
+    assert!(Some(update.version) > version);
+    assert_eq!(second.blocking_recv().unwrap().version, update.version);
+    assert!(poll(&registry, metadata).unwrap().commands.groups.is_empty());
+}
+
+#[test]
+fn test_connect_discards_queued_updates() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    perform(&registry, metadata, BattleAction::EndTurn).unwrap();
+    for _ in 0..100 {
+        let battle = registry.find_battle(metadata.user_id, metadata.battle_id.unwrap()).unwrap();
+        if battle.unwrap().turn.active_player == Seat::Host {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let resumed = connect(&registry, metadata).metadata;
+    assert_eq!(resumed.battle_id, metadata.battle_id);
+    assert!(poll(&registry, resumed).unwrap().commands.groups.is_empty());
+}
+
+#[test]
+fn test_illegal_action() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    let result = perform(&registry, metadata, BattleAction::PlayCard(CardId::from_int(9999)));
+    let Err(error) = result else { panic!("Expected error") };
+    assert!(matches!(error, RequestError::IllegalAction(_)));
+    assert_eq!(ErrorResponse::from(error).error_type, ErrorType::IllegalAction);
+}
+
+#[test]
+fn test_action_increments_version() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    assert_eq!(metadata.version, Some(0));
+    let response = perform(&registry, metadata, DebugAction::DrawCard).unwrap();
+    assert_eq!(response.metadata.version, Some(1));
+    let response = perform(&registry, response.metadata, DebugAction::DrawCard).unwrap();
+    assert_eq!(response.metadata.version, Some(2));
+}
+
+#[test]
+fn test_browsing_cards_does_not_increment_version() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    let response =
+        perform(&registry, metadata, BattleAction::BrowseCards(CardBrowserType::UserVoid)).unwrap();
+    assert_eq!(response.metadata.version, Some(0));
+    let response = perform(&registry, response.metadata, BattleAction::CloseCardBrowser).unwrap();
+    assert_eq!(response.metadata.version, Some(0));
+}
+
+#[test]
+fn test_stale_action_resyncs() {
+    let (_directory, database) = new_database();
+    let registry = new_registry(&database);
+    let metadata = connect(&registry, new_user()).metadata;
+    let current = perform(&registry, metadata, DebugAction::DrawCard).unwrap().metadata;
+
+    let response = perform(&registry, metadata, DebugAction::DrawCard).unwrap();
+    assert_eq!(response.metadata.version, current.version);
+    assert!(response
+        .commands
+        .groups
+        .iter()
+        .flat_map(|group| group.commands.iter())
+        .any(|command| matches!(command, Command::UpdateBattle(_))));
+    let battle = registry.find_battle(metadata.user_id, metadata.battle_id.unwrap()).unwrap();
+    assert_eq!(battle.unwrap().action_log.len(), 1);
+}
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use action_data::battle_action::{BattleAction, CardBrowserType};
+use action_data::debug_action::DebugAction;
+use action_data::user_action::UserAction;
+use core_data::identifiers::{BattleId, UserId};
+use core_data::types::{PlayerName, Seat};
+use display_data::battle_view::BattleView;
+use display_data::command::{Command, CommandSequence};
+use display_data::object_position::Position;
+use display_data::request_data::{
+    ConnectRequest, Metadata, PerformActionRequest, ReplayRequest, SpectateRequest,
+};
+use display_data::wire_format::WireFormat;
+use engine::request_error::RequestError;
+use engine::session_registry::SessionRegistry;
+use engine::spectator::Spectator;
+use engine::{battle_rendering, handle_request, spectator};
+use uuid::Uuid;
+
+use crate::database::save_tests::new_database;
+
+fn connect(registry: &SessionRegistry) -> Metadata {
+    let metadata = Metadata { user_id: UserId(Uuid::new_v4()), battle_id: None, version: None };
+    let request = ConnectRequest {
+        metadata,
+        wire_format: WireFormat::Json,
+        battle_patches: false,
+        opponent: None,
+    };
+    handle_request::connect(registry, &request).unwrap().metadata
+}
+
+fn perform(
+    registry: &Arc<SessionRegistry>,
+    metadata: Metadata,
+    action: impl Into<UserAction>,
+) -> Metadata {
+    let request = PerformActionRequest { metadata, action: action.into() };
+    handle_request::perform_action(registry, &request).unwrap().metadata
+}
+
+fn battle_views(commands: &CommandSequence) -> Vec<&BattleView> {
+    commands
+        .groups
+        .iter()
+        .flat_map(|group| group.commands.iter())
+        .filter_map(|command| match command {
+            Command::UpdateBattle(update) => Some(&update.battle),
+            _ => None,
+        })
+        .collect()
+}
+
+fn replay_request(metadata: Metadata, turn_number: u32) -> ReplayRequest {
+    ReplayRequest {
+        user_id: metadata.user_id,
+        battle_id: metadata.battle_id.unwrap(),
+        perspective: Seat::Host,
+        turn_number,
+    }
+}
+
+#[test]
+fn test_list_battles() {
+    let (_directory, database) = new_database();
+    let registry = SessionRegistry::new(Box::new(database));
+    let first = connect(&registry);
+    let second = connect(&registry);
+    let battles = spectator::list_battles(&registry).unwrap().battles;
+    assert_eq!(battles.len(), 2);
+    for metadata in [first, second] {
+        assert!(battles.iter().any(|battle| battle.user_id == metadata.user_id
+            && Some(battle.battle_id) == metadata.battle_id));
+    }
+}
+
+#[test]
+fn test_spectate_from_enemy_perspective() {
+    let (_directory, database) = new_database();
+    let registry = SessionRegistry::new(Box::new(database));
+    let metadata = connect(&registry);
+    let request = SpectateRequest {
+        user_id: metadata.user_id,
+        battle_id: metadata.battle_id.unwrap(),
+        perspective: Seat::Guest,
+    };
+    let response = spectator::spectate(&registry, &request).unwrap();
+    assert_eq!(Some(response.version), metadata.version);
+
+    // From the enemy's perspective, the user's hand is the opponent's hand.
+    let views = battle_views(&response.commands);
+    let user_hand = views[0]
+        .cards
+        .iter()
+        .filter(|card| card.position.position == Position::InHand(PlayerName::Enemy))
+        .collect::<Vec<_>>();
+    assert!(!user_hand.is_empty());
+    assert!(user_hand.iter().all(|card| card.revealed.is_none()));
+}
+
+#[test]
+fn test_spectate_unknown_battle() {
+    let (_directory, database) = new_database();
+    let registry = SessionRegistry::new(Box::new(database));
+    let metadata = connect(&registry);
+    let request = SpectateRequest {
+        user_id: metadata.user_id,
+        battle_id: BattleId(Uuid::new_v4()),
+        perspective: Seat::Host,
+    };
+    assert!(matches!(
+        spectator::spectate(&registry, &request),
+        Err(RequestError::UnknownBattle(_))
+    ));
+}
+
+#[test]
+fn test_spectate_without_loading_session() {
+    let (_directory, database) = new_database();
+    let registry = SessionRegistry::new(Box::new(database.clone()));
+    let metadata = connect(&registry);
+
+    // A registry with no sessions in memory reads the battle from the database.
+    let observer = SessionRegistry::new(Box::new(database));
+    let battles = spectator::list_battles(&observer).unwrap().battles;
+    assert_eq!(battles.len(), 1);
+    let request = SpectateRequest {
+        user_id: metadata.user_id,
+        battle_id: metadata.battle_id.unwrap(),
+        perspective: Seat::Host,
+    };
+    let response = spectator::spectate(&observer, &request).unwrap();
+    assert_eq!(Some(response.version), metadata.version);
+    let replay = spectator::replay(&observer, &replay_request(metadata, 1)).unwrap();
+    assert_eq!(battle_views(&replay.commands).len(), 1);
+}
+
+#[test]
+fn test_watch_battles_notifies_changes() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let metadata = connect(&registry);
+    let mut changes = registry.watch_battles();
+    assert!(changes.try_recv().is_err());
+
+    perform(&registry, metadata, DebugAction::DrawCard);
+    assert_eq!(changes.try_recv().ok(), metadata.battle_id);
+}
+
+#[test]
+fn test_spectator_applies_new_actions() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let metadata = connect(&registry);
+    let request = SpectateRequest {
+        user_id: metadata.user_id,
+        battle_id: metadata.battle_id.unwrap(),
+        perspective: Seat::Guest,
+    };
+    let (mut spectator, _) = Spectator::new(&registry, request).unwrap();
+    assert!(spectator.update(&registry).unwrap().is_none());
+
+    let current = perform(&registry, metadata, DebugAction::DrawCard);
+    let response = spectator.update(&registry).unwrap().unwrap();
+    assert_eq!(Some(response.version), current.version);
+    let snapshot = spectator::spectate(&registry, &request).unwrap();
+    assert_eq!(battle_views(&response.commands), battle_views(&snapshot.commands));
+    assert!(spectator.update(&registry).unwrap().is_none());
+}
+
+#[test]
+fn test_replay_turns() {
+    let (_directory, database) = new_database();
+    let registry = Arc::new(SessionRegistry::new(Box::new(database)));
+    let metadata = connect(&registry);
+    let mut current = perform(&registry, metadata, DebugAction::DrawCard);
+    current = perform(&registry, current, BattleAction::BrowseCards(CardBrowserType::UserVoid));
+    current = perform(&registry, current, BattleAction::CloseCardBrowser);
+    perform(&registry, current, BattleAction::EndTurn);
+
+    let battle_id = metadata.battle_id.unwrap();
+    let mut battle = None;
+    for _ in 0..100 {
+        battle = registry.find_battle(metadata.user_id, battle_id).unwrap();
+        if battle.as_ref().is_some_and(|b| b.turn.active_player == Seat::Host) {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    let battle = battle.unwrap();
+    assert_eq!(battle.turn.turn_number, 3);
+
+    let first_turn = spectator::replay(&registry, &replay_request(metadata, 1)).unwrap();
+    assert_eq!(first_turn.turn_number, 1);
+    assert_eq!(first_turn.last_turn_number, 3);
+    assert_eq!(battle_views(&first_turn.commands).len(), 5);
+
+    // Replaying the whole action log must reproduce the current state.
+    let current_turn = spectator::replay(&registry, &replay_request(metadata, 10)).unwrap();
+    assert_eq!(current_turn.turn_number, 3);
+    assert_eq!(battle_views(&current_turn.commands), vec![&battle_rendering::battle_view(
+        &battle,
+        Seat::Host
+    )]);
+}
@@ -0,0 +1,65 @@
+use core_data::identifiers::UserId;
+use core_data::types::Seat;
+use display_data::request_data::{ConnectRequest, ConnectResponse, Metadata};
+use display_data::wire_format::{self, WireFormat};
+use engine::handle_request;
+use engine::session_registry::SessionRegistry;
+use uuid::Uuid;
+
+use crate::database::save_tests::new_database;
+
+fn connect_response(wire_format: WireFormat) -> ConnectResponse {
+    let (_directory, database) = new_database();
+    let registry = SessionRegistry::new(Box::new(database));
+    let metadata = Metadata { user_id: UserId(Uuid::new_v4()), battle_id: None, version: None };
+    handle_request::connect(&registry, &ConnectRequest {
+        metadata,
+        wire_format,
+        battle_patches: false,
+        opponent: None,
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_connect_response_uses_requested_format() {
+    assert_eq!(connect_response(WireFormat::Json).wire_format, WireFormat::Json);
+    assert_eq!(connect_response(WireFormat::MessagePack).wire_format, WireFormat::MessagePack);
+}
+
+#[test]
+fn test_message_pack_round_trip() {
+    let response = connect_response(WireFormat::MessagePack);
+    let encoded = wire_format::encode(WireFormat::MessagePack, &response).unwrap();
+    let decoded: ConnectResponse = wire_format::decode(WireFormat::MessagePack, &encoded).unwrap();
+    let json = wire_format::encode(WireFormat::Json, &response).unwrap();
+    assert_eq!(wire_format::encode(WireFormat::Json, &decoded).unwrap(), json);
+    assert!(encoded.len() < json.len());
+}
+
+#[test]
+fn test_connect_request_defaults_to_json() {
+    let user_id = Uuid::new_v4();
+    let json = format!(r#"{{"metadata":{{"userId":"{user_id}","battleId":null}}}}"#);
+    let request: ConnectRequest = wire_format::decode(WireFormat::Json, json.as_bytes()).unwrap();
+    assert_eq!(request.wire_format, WireFormat::Json);
+}
+
+#[test]
+fn test_seat_accepts_player_names_from_old_saves() {
+    let decode = |json: &str| wire_format::decode::<Seat>(WireFormat::Json, json.as_bytes());
+    assert_eq!(decode(r#""user""#).unwrap(), Seat::Host);
+    assert_eq!(decode(r#""enemy""#).unwrap(), Seat::Guest);
+    assert_eq!(wire_format::encode(WireFormat::Json, &Seat::Host).unwrap(), br#""host""#);
+}
+
+#[test]
+fn test_content_type() {
+    for format in [WireFormat::Json, WireFormat::MessagePack] {
+        assert_eq!(WireFormat::from_content_type(format.content_type()), Some(format));
+    }
+    assert_eq!(
+        WireFormat::from_content_type("application/json; charset=utf-8"),
+        Some(WireFormat::Json)
+    );
+}